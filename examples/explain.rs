@@ -0,0 +1,18 @@
+//! Shows how every enabled metric scores a pair of strings.
+//!
+//! ```text
+//! cargo run --example explain -- kitten sitting
+//! ```
+
+use fuzzt::diagnostics::compare_algorithms;
+use std::env;
+use std::process;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let [a, b] = args.as_slice() else {
+        eprintln!("usage: explain <a> <b>");
+        process::exit(2);
+    };
+    print!("{}", compare_algorithms(a, b));
+}
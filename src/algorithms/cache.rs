@@ -0,0 +1,261 @@
+use crate::algorithms::{Similarity, SimilarityMetric};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const NONE: usize = usize::MAX;
+
+struct Entry {
+    key: String,
+    value: Similarity,
+    prev: usize,
+    next: usize,
+}
+
+/// A map that evicts its least recently used entry once it's full. Entries
+/// are kept in a doubly linked list threaded through a vector, so lookups,
+/// insertions and evictions run in constant time.
+struct LruCache {
+    capacity: usize,
+    index: HashMap<String, usize>,
+    entries: Vec<Entry>,
+    // most recently used entry
+    head: usize,
+    // least recently used entry
+    tail: usize,
+    hits: u64,
+    misses: u64,
+}
+
+impl LruCache {
+    fn new(capacity: usize) -> Self {
+        LruCache {
+            capacity,
+            index: HashMap::with_capacity(capacity),
+            entries: Vec::with_capacity(capacity),
+            head: NONE,
+            tail: NONE,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn unlink(&mut self, slot: usize) {
+        let (prev, next) = (self.entries[slot].prev, self.entries[slot].next);
+        match prev {
+            NONE => self.head = next,
+            prev => self.entries[prev].next = next,
+        }
+        match next {
+            NONE => self.tail = prev,
+            next => self.entries[next].prev = prev,
+        }
+    }
+
+    fn push_front(&mut self, slot: usize) {
+        self.entries[slot].prev = NONE;
+        self.entries[slot].next = self.head;
+        match self.head {
+            NONE => self.tail = slot,
+            head => self.entries[head].prev = slot,
+        }
+        self.head = slot;
+    }
+
+    fn get(&mut self, key: &str) -> Option<Similarity> {
+        match self.index.get(key).copied() {
+            Some(slot) => {
+                self.hits += 1;
+                self.unlink(slot);
+                self.push_front(slot);
+                Some(self.entries[slot].value)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, key: String, value: Similarity) {
+        if let Some(&slot) = self.index.get(&key) {
+            self.entries[slot].value = value;
+            self.unlink(slot);
+            self.push_front(slot);
+            return;
+        }
+
+        let slot = if self.entries.len() < self.capacity {
+            self.entries.push(Entry {
+                key: key.clone(),
+                value,
+                prev: NONE,
+                next: NONE,
+            });
+            self.entries.len() - 1
+        } else {
+            let slot = self.tail;
+            self.unlink(slot);
+            let evicted = std::mem::replace(&mut self.entries[slot].key, key.clone());
+            self.index.remove(&evicted);
+            self.entries[slot].value = value;
+            slot
+        };
+        self.index.insert(key, slot);
+        self.push_front(slot);
+    }
+
+    fn clear(&mut self) {
+        self.index.clear();
+        self.entries.clear();
+        self.head = NONE;
+        self.tail = NONE;
+    }
+}
+
+/// Wraps a metric and remembers the scores of the most recently compared
+/// pairs of strings.
+///
+/// Comparing a pair that is already cached skips the wrapped metric entirely,
+/// which pays off when the same pairs are scored over and over, e.g. by
+/// repeated `get_top_n` calls against the same choices. Pairs are cached as
+/// the metric receives them, i.e. after the matcher applied its processor.
+/// Once `capacity` pairs are cached, the least recently used one is evicted.
+///
+/// The cache is behind a mutex, so a single `CachedMetric` can be shared
+/// across threads and matcher calls, as long as the wrapped metric is `Sync`
+/// too.
+///
+/// ```
+/// use fuzzt::algorithms::{CachedMetric, Levenshtein, Similarity, SimilarityMetric};
+///
+/// let cached = CachedMetric::new(&Levenshtein, 1024);
+/// assert_eq!(Similarity::Usize(3), cached.compute_metric("kitten", "sitting"));
+/// assert_eq!(Similarity::Usize(3), cached.compute_metric("kitten", "sitting"));
+/// assert_eq!((1, 1), (cached.hits(), cached.misses()));
+/// ```
+pub struct CachedMetric<'m> {
+    metric: &'m (dyn SimilarityMetric + Sync),
+    cache: Mutex<LruCache>,
+}
+
+impl<'m> CachedMetric<'m> {
+    /// Creates a cache of up to `capacity` pairs in front of `metric`.
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn new(metric: &'m (dyn SimilarityMetric + Sync), capacity: usize) -> Self {
+        assert!(capacity > 0, "the cache must hold at least one pair");
+        CachedMetric {
+            metric,
+            cache: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    fn key(a: &str, b: &str) -> String {
+        // the length prefix keeps ("ab", "c") and ("a", "bc") apart
+        format!("{}:{a}{b}", a.len())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache> {
+        // the cache stays consistent even if a metric panicked while it was
+        // locked, since it isn't locked while the metric runs
+        self.cache
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Returns the number of pairs currently cached.
+    pub fn len(&self) -> usize {
+        self.lock().index.len()
+    }
+
+    /// Returns `true` if no pair is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns how many comparisons were answered from the cache.
+    pub fn hits(&self) -> u64 {
+        self.lock().hits
+    }
+
+    /// Returns how many comparisons had to be computed by the wrapped metric.
+    pub fn misses(&self) -> u64 {
+        self.lock().misses
+    }
+
+    /// Forgets all cached pairs.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+}
+
+impl SimilarityMetric for CachedMetric<'_> {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        let key = Self::key(a, b);
+        if let Some(similarity) = self.lock().get(&key) {
+            return similarity;
+        }
+        let similarity = self.metric.compute_metric(a, b);
+        self.lock().insert(key, similarity);
+        similarity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{Levenshtein, SequenceMatcher};
+
+    #[test]
+    fn cached_metric_evicts_least_recently_used() {
+        let cached = CachedMetric::new(&Levenshtein, 2);
+        cached.compute_metric("a", "b");
+        cached.compute_metric("a", "c");
+        // refresh ("a", "b") so that ("a", "c") is evicted next
+        cached.compute_metric("a", "b");
+        cached.compute_metric("a", "d");
+        assert_eq!(2, cached.len());
+        assert_eq!((1, 3), (cached.hits(), cached.misses()));
+
+        cached.compute_metric("a", "b");
+        assert_eq!(2, cached.hits());
+        cached.compute_metric("a", "c");
+        assert_eq!(4, cached.misses());
+    }
+
+    #[test]
+    fn cached_metric_keys_are_unambiguous() {
+        let cached = CachedMetric::new(&Levenshtein, 8);
+        assert_eq!(Similarity::Usize(1), cached.compute_metric("ab", "b"));
+        assert_eq!(Similarity::Usize(2), cached.compute_metric("a", "bb"));
+        assert_eq!(Similarity::Usize(1), cached.compute_metric("ab", "b"));
+        assert_eq!(1, cached.hits());
+    }
+
+    #[test]
+    fn cached_metric_clear() {
+        let cached = CachedMetric::new(&SequenceMatcher, 1);
+        cached.compute_metric("abc", "abd");
+        assert!(!cached.is_empty());
+        cached.clear();
+        assert!(cached.is_empty());
+        cached.compute_metric("abc", "abd");
+        assert_eq!(0, cached.hits());
+    }
+
+    #[test]
+    fn cached_metric_is_sync() {
+        fn assert_sync<T: Sync>(_: &T) {}
+        assert_sync(&CachedMetric::new(&Levenshtein, 8));
+    }
+
+    #[test]
+    fn cached_metric_in_matcher() {
+        let cached = CachedMetric::new(&SequenceMatcher, 16);
+        let choices = ["brazil", "braziu", "spain"];
+        let first = crate::get_top_n("brazil", &choices, None, None, None, Some(&cached));
+        let second = crate::get_top_n("brazil", &choices, None, None, None, Some(&cached));
+        assert_eq!(first, second);
+        assert_eq!((3, 3), (cached.hits(), cached.misses()));
+    }
+}
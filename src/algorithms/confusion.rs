@@ -0,0 +1,190 @@
+use crate::algorithms::{Similarity, SimilarityMetric};
+use std::collections::HashMap;
+
+/// A substitution of one sequence of characters with another.
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    from: Vec<char>,
+    to: Vec<char>,
+    cost: f64,
+}
+
+/// Characters that are easily confused with each other, such as `rn` and `m`
+/// in OCR output, and the cost of substituting them.
+///
+/// Used as a `SimilarityMetric`, the matrix scores pairs with
+/// `1 - distance / max_len`, where `max_len` is the length of the longer
+/// string.
+///
+/// ```
+/// use fuzzt::algorithms::ConfusionMatrix;
+///
+/// let mut matrix = ConfusionMatrix::new();
+/// matrix.insert("rn", "m", 0.2);
+/// matrix.insert("l", "1", 0.2);
+///
+/// assert_eq!(0.2, matrix.distance("modern", "rnodern"));
+/// assert_eq!(0.4, matrix.distance("lamp", "1arnp"));
+/// assert_eq!(1.0, matrix.distance("lamp", "ramp"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfusionMatrix {
+    // rules by the last character of the sequence they replace
+    rules: HashMap<char, Vec<Rule>>,
+}
+
+impl ConfusionMatrix {
+    /// Creates a matrix without confusions, whose distance is the
+    /// Levenshtein distance.
+    pub fn new() -> Self {
+        ConfusionMatrix::default()
+    }
+
+    /// Confusions commonly made by OCR engines on Latin text, such as `rn`
+    /// and `m`, `l` and `1`, or `O` and `0`, each costing 0.25.
+    pub fn ocr() -> Self {
+        let mut matrix = ConfusionMatrix::new();
+        for (a, b) in [
+            ("rn", "m"),
+            ("vv", "w"),
+            ("cl", "d"),
+            ("ri", "n"),
+            ("l", "1"),
+            ("l", "I"),
+            ("I", "1"),
+            ("|", "l"),
+            ("O", "0"),
+            ("o", "0"),
+            ("S", "5"),
+            ("s", "5"),
+            ("B", "8"),
+            ("Z", "2"),
+            ("G", "6"),
+            ("e", "c"),
+            ("u", "v"),
+        ] {
+            matrix.insert(a, b, 0.25);
+        }
+        matrix
+    }
+
+    /// Lets `a` be substituted with `b`, and `b` with `a`, for `cost`. Sequences
+    /// of different lengths, e.g. `rn` and `m`, cost the same as any other
+    /// substitution. Inserting a pair again replaces its cost.
+    ///
+    /// Panics if the cost is negative or NaN, or if `a` or `b` is empty.
+    pub fn insert(&mut self, a: &str, b: &str, cost: f64) {
+        assert!(cost >= 0.0, "substitution costs must be non-negative");
+        assert!(
+            !a.is_empty() && !b.is_empty(),
+            "substituted sequences must not be empty"
+        );
+        self.insert_rule(a.chars().collect(), b.chars().collect(), cost);
+        self.insert_rule(b.chars().collect(), a.chars().collect(), cost);
+    }
+
+    fn insert_rule(&mut self, from: Vec<char>, to: Vec<char>, cost: f64) {
+        let last = *from.last().expect("sequences are not empty");
+        let rules = self.rules.entry(last).or_default();
+        match rules
+            .iter_mut()
+            .find(|rule| rule.from == from && rule.to == to)
+        {
+            Some(rule) => rule.cost = cost,
+            None => rules.push(Rule { from, to, cost }),
+        }
+    }
+
+    /// Calculates the minimal cost of transforming `a` into `b` with
+    /// insertions, deletions and substitutions of single characters, which
+    /// cost 1.0, and the substitutions of the matrix.
+    pub fn distance(&self, a: &str, b: &str) -> f64 {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        let width = b.len() + 1;
+
+        // costs[i * width + j] is the distance between the first `i`
+        // characters of `a` and the first `j` characters of `b`
+        let mut costs = vec![0.0; (a.len() + 1) * width];
+        for (j, cost) in costs.iter_mut().take(width).enumerate() {
+            *cost = j as f64;
+        }
+        for i in 1..=a.len() {
+            costs[i * width] = i as f64;
+            let rules = self.rules.get(&a[i - 1]);
+            for j in 1..width {
+                let substitution = if a[i - 1] == b[j - 1] { 0.0 } else { 1.0 };
+                let mut cost = (costs[(i - 1) * width + j - 1] + substitution)
+                    .min(costs[(i - 1) * width + j] + 1.0)
+                    .min(costs[i * width + j - 1] + 1.0);
+
+                for rule in rules.into_iter().flatten() {
+                    let (from, to) = (rule.from.len(), rule.to.len());
+                    if from <= i
+                        && to <= j
+                        && a[i - from..i] == rule.from
+                        && b[j - to..j] == rule.to
+                    {
+                        cost = cost.min(costs[(i - from) * width + j - to] + rule.cost);
+                    }
+                }
+                costs[i * width + j] = cost;
+            }
+        }
+        costs[costs.len() - 1]
+    }
+
+    /// Calculates the distance normalized by the length of the longer string,
+    /// subtracted from 1.0. Empty strings are identical.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        let max_len = a.chars().count().max(b.chars().count());
+        if max_len == 0 {
+            return 1.0;
+        }
+        1.0 - self.distance(a, b) / max_len as f64
+    }
+}
+
+impl SimilarityMetric for ConfusionMatrix {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.similarity(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confusion_without_rules() {
+        let matrix = ConfusionMatrix::new();
+        assert_delta!(0.0, matrix.distance("", ""));
+        assert_delta!(3.0, matrix.distance("kitten", "sitting"));
+        assert_delta!(3.0, matrix.distance("", "öঙ香"));
+        assert_delta!(1.0, matrix.similarity("", ""));
+    }
+
+    #[test]
+    fn confusion_ocr() {
+        let matrix = ConfusionMatrix::ocr();
+        assert_delta!(0.5, matrix.distance("Invoice 1O", "lnvoice 10"));
+        assert_delta!(0.25, matrix.distance("wall", "vvall"));
+        assert_delta!(1.25, matrix.distance("burn", "bum!"));
+        assert!(matrix.similarity("modern", "rnodern") > matrix.similarity("modern", "lodern"));
+        assert!(matches!(
+            matrix.compute_metric("m", "rn"),
+            Similarity::Float(r) if (r - 0.875).abs() < 1e-9
+        ));
+    }
+
+    #[test]
+    fn confusion_replaces_costs() {
+        let mut matrix = ConfusionMatrix::new();
+        matrix.insert("a", "b", 0.5);
+        matrix.insert("b", "a", 2.0);
+        // expensive substitutions fall back to regular edits
+        assert_delta!(1.0, matrix.distance("a", "b"));
+        matrix.insert("a", "b", 0.1);
+        assert_delta!(0.2, matrix.distance("ab", "ba"));
+    }
+}
@@ -0,0 +1,296 @@
+use crate::algorithms::{NGramProfile, Similarity, SimilarityMetric};
+use crate::processors::Tokenizer;
+
+/// Calculates the fraction of the character n-grams of `a` that also occur in
+/// `b`, counting repetitions.
+///
+/// Unlike symmetric measures such as `sorensen_dice`, the score isn't
+/// penalized by the length of `b`, so a short query fully contained in a long
+/// title scores 1.0. Strings shorter than `n` characters are contained in `b`
+/// if they are a substring of it.
+///
+/// Panics if `n` is 0.
+///
+/// ```
+/// use fuzzt::algorithms::containment_ngrams;
+///
+/// assert_eq!(1.0, containment_ngrams("iphone", "apple iphone 12 pro", 3));
+/// assert_eq!(4.0 / 17.0, containment_ngrams("apple iphone 12 pro", "iphone", 3));
+/// assert_eq!(1.0, containment_ngrams("12", "apple iphone 12 pro", 3));
+/// ```
+pub fn containment_ngrams(a: &str, b: &str, n: usize) -> f64 {
+    let a_ngrams = NGramProfile::new(a, n);
+    if a_ngrams.is_empty() {
+        return if b.contains(a) { 1.0 } else { 0.0 };
+    }
+
+    let b_ngrams = NGramProfile::new(b, n);
+    a_ngrams.intersection(&b_ngrams) as f64 / a_ngrams.len() as f64
+}
+
+/// Calculates the fraction of the character bigrams of `a` that also occur in
+/// `b`, counting repetitions.
+///
+/// ```
+/// use fuzzt::algorithms::containment;
+///
+/// assert_eq!(1.0, containment("ferris", "ferris the crab"));
+/// assert_eq!(0.8, containment("ferris", "iris fer"));
+/// assert_eq!(0.0, containment("french", "quebec"));
+/// ```
+pub fn containment(a: &str, b: &str) -> f64 {
+    containment_ngrams(a, b, 2)
+}
+
+/// Which of the two compared strings is looked for in the other one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainmentDirection {
+    /// How much of the first string is contained in the second one.
+    FirstInSecond,
+    /// How much of the second string is contained in the first one.
+    SecondInFirst,
+    /// How much of the string with fewer characters is contained in the
+    /// other one. The first string is used when both have the same length.
+    ShorterInLonger,
+}
+
+/// Scores strings by how much of one is contained in the other, over
+/// character n-grams.
+///
+/// ```
+/// use fuzzt::algorithms::{Containment, ContainmentDirection, Similarity, SimilarityMetric};
+///
+/// let scorer = Containment::new(2, ContainmentDirection::ShorterInLonger);
+/// assert!(matches!(
+///     scorer.compute_metric("ferris the crab", "ferris"),
+///     Similarity::Float(r) if r == 1.0
+/// ));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Containment {
+    n: usize,
+    direction: ContainmentDirection,
+}
+
+impl Containment {
+    /// Creates a scorer over n-grams of `n` characters.
+    ///
+    /// Panics if `n` is 0.
+    pub fn new(n: usize, direction: ContainmentDirection) -> Self {
+        assert!(n > 0, "n-grams must contain at least one character");
+        Containment { n, direction }
+    }
+
+    /// Calculates the containment of the strings in the configured direction.
+    pub fn containment(&self, a: &str, b: &str) -> f64 {
+        match self.direction {
+            ContainmentDirection::FirstInSecond => containment_ngrams(a, b, self.n),
+            ContainmentDirection::SecondInFirst => containment_ngrams(b, a, self.n),
+            ContainmentDirection::ShorterInLonger => {
+                if b.chars().count() < a.chars().count() {
+                    containment_ngrams(b, a, self.n)
+                } else {
+                    containment_ngrams(a, b, self.n)
+                }
+            }
+        }
+    }
+}
+
+impl Default for Containment {
+    /// Scores how much of the first string is contained in the second one,
+    /// over bigrams.
+    fn default() -> Self {
+        Containment::new(2, ContainmentDirection::FirstInSecond)
+    }
+}
+
+impl SimilarityMetric for Containment {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.containment(a, b))
+    }
+}
+
+/// Scores how many tokens of the first string fuzzily match some token of the
+/// second string, such as a search query with typos against a long title.
+///
+/// A query token is covered if the `inner` metric scores it against at least
+/// one candidate token at or above `threshold`. If `inner` is a distance, the
+/// score must instead be at most `threshold`. The score is the fraction of
+/// query tokens covered, and a query without tokens is always covered.
+///
+/// The first string is the query unless another direction is set with
+/// [`FuzzyTokenContainment::with_direction`], e.g. when queries are sometimes
+/// longer than the titles they are matched against.
+///
+/// ```
+/// use fuzzt::algorithms::{FuzzyTokenContainment, NormalizedLevenshtein};
+/// use fuzzt::processors::WordTokenizer;
+///
+/// let scorer = FuzzyTokenContainment::new(&NormalizedLevenshtein, 0.6, &WordTokenizer);
+/// let title = "apple iphone 12 pro max (256gb, graphite)";
+/// assert_eq!(1.0, scorer.containment("iphnoe graphit", title));
+/// assert_eq!(0.5, scorer.containment("iphone silver", title));
+/// ```
+pub struct FuzzyTokenContainment<'m> {
+    inner: &'m dyn SimilarityMetric,
+    threshold: f64,
+    tokenizer: &'m dyn Tokenizer,
+    direction: ContainmentDirection,
+}
+
+impl<'m> FuzzyTokenContainment<'m> {
+    /// Creates a scorer that compares the tokens produced by `tokenizer` with
+    /// `inner`.
+    pub fn new(
+        inner: &'m dyn SimilarityMetric,
+        threshold: f64,
+        tokenizer: &'m dyn Tokenizer,
+    ) -> Self {
+        FuzzyTokenContainment {
+            inner,
+            threshold,
+            tokenizer,
+            direction: ContainmentDirection::FirstInSecond,
+        }
+    }
+
+    /// Sets which string provides the tokens looked for in the other one.
+    /// With `ContainmentDirection::ShorterInLonger`, the string with fewer
+    /// tokens is the query.
+    ///
+    /// ```
+    /// use fuzzt::algorithms::{
+    ///     ContainmentDirection, FuzzyTokenContainment, NormalizedLevenshtein, SimilarityMetric,
+    /// };
+    /// use fuzzt::processors::WordTokenizer;
+    ///
+    /// let scorer = FuzzyTokenContainment::new(&NormalizedLevenshtein, 0.6, &WordTokenizer)
+    ///     .with_direction(ContainmentDirection::ShorterInLonger);
+    /// let query = "apple iphone 12 pro max graphite 256gb";
+    /// assert_eq!(1.0, scorer.compute_metric(query, "iphone 12").as_f64());
+    /// ```
+    pub fn with_direction(mut self, direction: ContainmentDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    fn matches(&self, a: &str, b: &str) -> bool {
+        let score = self.inner.compute_metric(a, b);
+        if score.is_distance() {
+            score.as_f64() <= self.threshold
+        } else {
+            score.as_f64() >= self.threshold
+        }
+    }
+
+    /// Returns the fraction of the tokens of `query` that fuzzily match a
+    /// token of `candidate`, or the other way around if the configured
+    /// direction says so.
+    pub fn containment(&self, query: &str, candidate: &str) -> f64 {
+        let mut query_tokens = self.tokenizer.tokenize(query);
+        let mut candidate_tokens = self.tokenizer.tokenize(candidate);
+        let swap = match self.direction {
+            ContainmentDirection::FirstInSecond => false,
+            ContainmentDirection::SecondInFirst => true,
+            ContainmentDirection::ShorterInLonger => candidate_tokens.len() < query_tokens.len(),
+        };
+        if swap {
+            std::mem::swap(&mut query_tokens, &mut candidate_tokens);
+        }
+        if query_tokens.is_empty() {
+            return 1.0;
+        }
+
+        let covered = query_tokens
+            .iter()
+            .filter(|query_token| {
+                candidate_tokens
+                    .iter()
+                    .any(|candidate_token| self.matches(query_token, candidate_token))
+            })
+            .count();
+        covered as f64 / query_tokens.len() as f64
+    }
+}
+
+impl SimilarityMetric for FuzzyTokenContainment<'_> {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.containment(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn containment_empty() {
+        assert_delta!(1.0, containment("", ""));
+        assert_delta!(1.0, containment("", "abc"));
+        assert_delta!(0.0, containment("abc", ""));
+    }
+
+    #[test]
+    fn containment_short_needle() {
+        assert_delta!(1.0, containment("b", "abc"));
+        assert_delta!(0.0, containment("d", "abc"));
+    }
+
+    #[test]
+    fn containment_counts_repetitions() {
+        // "aaaa" has three "aa" bigrams, only one of which occurs in "aab"
+        assert_delta!(1.0 / 3.0, containment("aaaa", "aab"));
+        assert_delta!(0.5, containment("aab", "aaaa"));
+    }
+
+    #[test]
+    fn fuzzy_token_containment() {
+        use crate::algorithms::{Levenshtein, NormalizedLevenshtein};
+        use crate::processors::WhitespaceTokenizer;
+
+        let scorer = FuzzyTokenContainment::new(&NormalizedLevenshtein, 0.8, &WhitespaceTokenizer);
+        assert_delta!(1.0, scorer.containment("", "anything"));
+        assert_delta!(0.0, scorer.containment("query", ""));
+        assert_delta!(1.0, scorer.containment("rust crab", "the crab of rust"));
+
+        // distances must be at most the threshold
+        let scorer = FuzzyTokenContainment::new(&Levenshtein, 1.0, &WhitespaceTokenizer);
+        assert_delta!(
+            2.0 / 3.0,
+            scorer.containment("receve teh mail", "receive the email")
+        );
+    }
+
+    #[test]
+    fn containment_directions() {
+        let (title, query) = ("apple iphone 12 pro", "iphone");
+        let first = Containment::new(2, ContainmentDirection::FirstInSecond);
+        let second = Containment::new(2, ContainmentDirection::SecondInFirst);
+        let shorter = Containment::new(2, ContainmentDirection::ShorterInLonger);
+        assert_delta!(5.0 / 18.0, first.containment(title, query));
+        assert_delta!(1.0, second.containment(title, query));
+        assert_delta!(1.0, shorter.containment(title, query));
+        assert_delta!(1.0, shorter.containment(query, title));
+    }
+
+    #[test]
+    fn fuzzy_token_containment_directions() {
+        use crate::algorithms::NormalizedLevenshtein;
+        use crate::processors::WhitespaceTokenizer;
+
+        let scorer = |direction| {
+            FuzzyTokenContainment::new(&NormalizedLevenshtein, 0.8, &WhitespaceTokenizer)
+                .with_direction(direction)
+        };
+        let (query, title) = ("red rust crab plush", "rust crab");
+        let first = scorer(ContainmentDirection::FirstInSecond);
+        let second = scorer(ContainmentDirection::SecondInFirst);
+        let shorter = scorer(ContainmentDirection::ShorterInLonger);
+        assert_delta!(0.5, first.containment(query, title));
+        assert_delta!(1.0, second.containment(query, title));
+        assert_delta!(1.0, shorter.containment(query, title));
+        assert_delta!(1.0, shorter.containment(title, query));
+        assert_delta!(1.0, second.containment("anything", ""));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::algorithms::{NGramProfile, Similarity, SimilarityMetric};
+
+/// Calculates the cosine similarity between the character n-gram frequency
+/// vectors of two strings. Strings shorter than `n` characters only match
+/// identical strings.
+///
+/// Unlike `sorensen_dice`, n-grams are weighted by how often they occur, which
+/// makes the score more robust for longer texts with repeated words.
+///
+/// Panics if `n` is 0.
+///
+/// ```
+/// use fuzzt::algorithms::cosine_ngrams;
+///
+/// assert_eq!(1.0, cosine_ngrams("", "", 3));
+/// assert_eq!(0.0, cosine_ngrams("ab", "ac", 3));
+/// assert!((cosine_ngrams("night", "nacht", 2) - 0.25).abs() < 0.00001);
+/// ```
+pub fn cosine_ngrams(a: &str, b: &str, n: usize) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+
+    let a_ngrams = NGramProfile::new(a, n);
+    let b_ngrams = NGramProfile::new(b, n);
+
+    if a_ngrams.is_empty() || b_ngrams.is_empty() {
+        return 0.0;
+    }
+
+    a_ngrams.cosine(&b_ngrams)
+}
+
+/// Calculates the cosine similarity between the character bigram frequency
+/// vectors of two strings.
+/// See <https://en.wikipedia.org/wiki/Cosine_similarity>.
+///
+/// ```
+/// use fuzzt::algorithms::cosine;
+///
+/// assert_eq!(1.0, cosine("ferris", "ferris"));
+/// assert_eq!(0.0, cosine("french", "quebec"));
+/// assert!((cosine("abab", "ab") - 0.89442).abs() < 0.00001);
+/// ```
+pub fn cosine(a: &str, b: &str) -> f64 {
+    cosine_ngrams(a, b, 2)
+}
+
+/// Scores strings with the cosine similarity of their n-grams.
+///
+/// ```
+/// use fuzzt::algorithms::{Cosine, Similarity, SimilarityMetric};
+///
+/// assert!(matches!(Cosine::default().compute_metric("ferris", "ferris"), Similarity::Float(r) if r == 1.0));
+/// assert!(matches!(Cosine::new(3).compute_metric("ab", "ac"), Similarity::Float(r) if r == 0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cosine {
+    n: usize,
+}
+
+impl Cosine {
+    /// Creates a scorer over n-grams of `n` characters.
+    ///
+    /// Panics if `n` is 0.
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0, "n-grams must contain at least one character");
+        Cosine { n }
+    }
+}
+
+impl Default for Cosine {
+    /// Scores strings over bigrams.
+    fn default() -> Self {
+        Cosine::new(2)
+    }
+}
+
+impl SimilarityMetric for Cosine {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(cosine_ngrams(a, b, self.n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cosine_empty() {
+        assert_delta!(1.0, cosine("", ""));
+        assert_delta!(0.0, cosine("", "ab"));
+        assert_delta!(0.0, cosine("a", "b"));
+    }
+
+    #[test]
+    fn cosine_counts_repetitions() {
+        // ab, ba, ab against ab
+        assert_delta!(0.89442, cosine("abab", "ab"));
+        assert_delta!(0.99228, cosine("abab", "ababab"));
+    }
+
+    #[test]
+    fn cosine_multibyte() {
+        assert_delta!(0.5, cosine("öঙ香", "öঙa"));
+    }
+
+    #[test]
+    fn cosine_unigrams_ignore_order() {
+        assert_delta!(1.0, cosine_ngrams("listen", "silent", 1));
+        assert_delta!(
+            1.0,
+            Cosine::new(1).compute_metric("listen", "silent").as_f64()
+        );
+    }
+}
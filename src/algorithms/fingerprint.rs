@@ -0,0 +1,228 @@
+use crate::algorithms::{Similarity, SimilarityMetric};
+use crate::utils::RollingHash;
+use std::collections::HashSet;
+
+/// Builds fingerprints of long documents from the hashes of their k-grams of
+/// characters, keeping only one in `sampling` hashes.
+///
+/// Fingerprints take memory proportional to the number of sampled hashes
+/// rather than to the length of the document, and comparing two of them is
+/// linear in their size. This makes them suitable for documents far too long
+/// for the edit distances, e.g. to detect plagiarism between texts of several
+/// megabytes. Documents can be fed in chunks with [`Fingerprinter::stream`].
+///
+/// ```
+/// use fuzzt::algorithms::Fingerprinter;
+///
+/// let fingerprinter = Fingerprinter::new(5, 1);
+/// let a = fingerprinter.fingerprint("the quick brown fox jumps over the lazy dog");
+/// let b = fingerprinter.fingerprint("the quick brown fox jumped over the lazy dog");
+/// assert!(a.jaccard(&b) > 0.7);
+///
+/// let mut stream = fingerprinter.stream();
+/// stream.update("the quick brown fox ");
+/// stream.update("jumps over the lazy dog");
+/// assert_eq!(a, stream.finish());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fingerprinter {
+    k: usize,
+    sampling: u64,
+}
+
+impl Fingerprinter {
+    /// Creates a fingerprinter over k-grams of `k` characters that keeps one
+    /// in `sampling` hashes on average. A `sampling` of 1 keeps every hash.
+    ///
+    /// Panics if `k` or `sampling` is 0.
+    pub fn new(k: usize, sampling: u64) -> Self {
+        assert!(k > 0, "k-grams must contain at least one character");
+        assert!(sampling > 0, "sampling must be at least 1");
+        Fingerprinter { k, sampling }
+    }
+
+    /// Returns the fingerprint of a whole document.
+    pub fn fingerprint(&self, s: &str) -> Fingerprint {
+        let mut stream = self.stream();
+        stream.update(s);
+        stream.finish()
+    }
+
+    /// Returns a stream that builds a fingerprint from a document fed in
+    /// chunks, without holding the whole document in memory.
+    pub fn stream(&self) -> FingerprintStream {
+        FingerprintStream {
+            fingerprinter: *self,
+            hash: RollingHash::new(self.k),
+            fingerprint: Fingerprint::default(),
+        }
+    }
+}
+
+impl Default for Fingerprinter {
+    /// Fingerprints over 8-grams, keeping one in 4 hashes.
+    fn default() -> Self {
+        Fingerprinter::new(8, 4)
+    }
+}
+
+impl SimilarityMetric for Fingerprinter {
+    /// Compares the fingerprints of both strings. Strings too short to have a
+    /// fingerprint, e.g. shorter than `k` characters, are compared exactly
+    /// instead.
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        let (a_fingerprint, b_fingerprint) = (self.fingerprint(a), self.fingerprint(b));
+        Similarity::Float(if a_fingerprint.is_empty() || b_fingerprint.is_empty() {
+            f64::from(u8::from(a == b))
+        } else {
+            a_fingerprint.jaccard(&b_fingerprint)
+        })
+    }
+}
+
+/// Builds a [`Fingerprint`] from a document fed in chunks.
+#[derive(Debug, Clone)]
+pub struct FingerprintStream {
+    fingerprinter: Fingerprinter,
+    hash: RollingHash,
+    fingerprint: Fingerprint,
+}
+
+impl FingerprintStream {
+    /// Feeds the next chunk of the document. Chunks don't need to end on word
+    /// boundaries.
+    pub fn update(&mut self, chunk: &str) {
+        for c in chunk.chars() {
+            if let Some(hash) = self.hash.push(c) {
+                if hash % self.fingerprinter.sampling == 0 {
+                    self.fingerprint.hashes.insert(hash);
+                }
+            }
+        }
+    }
+
+    /// Returns the fingerprint of everything fed so far.
+    pub fn finish(self) -> Fingerprint {
+        self.fingerprint
+    }
+}
+
+/// The set of sampled k-gram hashes of a document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fingerprint {
+    hashes: HashSet<u64>,
+}
+
+impl Fingerprint {
+    /// Returns the number of distinct hashes in the fingerprint.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns `true` if the fingerprint contains no hashes, e.g. because the
+    /// document is shorter than `k` characters.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Calculates the Jaccard index between the hashes of both fingerprints.
+    /// Empty fingerprints share no hash, so they score 0.0 even against each
+    /// other.
+    pub fn jaccard(&self, other: &Fingerprint) -> f64 {
+        let intersection = self.hashes.intersection(&other.hashes).count();
+        let union = self.hashes.len() + other.hashes.len() - intersection;
+        if union == 0 {
+            return 0.0;
+        }
+        intersection as f64 / union as f64
+    }
+}
+
+/// Estimates the similarity of two long documents as the Jaccard index of
+/// their fingerprints, using the default `Fingerprinter`. Strings shorter
+/// than 8 characters are compared exactly.
+///
+/// ```
+/// use fuzzt::algorithms::fingerprint_similarity;
+///
+/// assert_eq!(1.0, fingerprint_similarity("a rather long document", "a rather long document"));
+/// assert_eq!(0.0, fingerprint_similarity("a rather long document", "something else entirely"));
+/// ```
+pub fn fingerprint_similarity(a: &str, b: &str) -> f64 {
+    Fingerprinter::default().compute_metric(a, b).as_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_rolling_hash_matches_direct_hash() {
+        let fingerprinter = Fingerprinter::new(3, 1);
+        let rolled = fingerprinter.fingerprint("abcabc");
+        let direct = fingerprinter.fingerprint("abc");
+        assert_eq!(3, rolled.len());
+        assert!(direct.hashes.is_subset(&rolled.hashes));
+    }
+
+    #[test]
+    fn fingerprint_short_documents() {
+        let fingerprinter = Fingerprinter::new(4, 1);
+        assert!(fingerprinter.fingerprint("abc").is_empty());
+        assert!(fingerprinter.fingerprint("").is_empty());
+        assert_eq!(1, fingerprinter.fingerprint("abcd").len());
+    }
+
+    #[test]
+    fn fingerprint_stream_multibyte_chunks() {
+        let fingerprinter = Fingerprinter::new(2, 1);
+        let mut stream = fingerprinter.stream();
+        for chunk in ["ö", "ঙ香", "", "ö"] {
+            stream.update(chunk);
+        }
+        assert_eq!(fingerprinter.fingerprint("öঙ香ö"), stream.finish());
+    }
+
+    #[test]
+    fn fingerprint_sampling_keeps_subset() {
+        let text = "It was the best of times, it was the worst of times, it was the age of wisdom";
+        let all = Fingerprinter::new(5, 1).fingerprint(text);
+        let sampled = Fingerprinter::new(5, 4).fingerprint(text);
+        assert!(sampled.len() < all.len());
+        assert!(sampled.hashes.is_subset(&all.hashes));
+    }
+
+    #[test]
+    fn fingerprint_jaccard() {
+        let fingerprinter = Fingerprinter::new(3, 1);
+        let a = fingerprinter.fingerprint("abcde");
+        let b = fingerprinter.fingerprint("abcdf");
+        assert_delta!(0.5, a.jaccard(&b));
+        assert_delta!(0.0, Fingerprint::default().jaccard(&Fingerprint::default()));
+    }
+
+    #[test]
+    fn fingerprint_metric_short_strings() {
+        let fingerprinter = Fingerprinter::default();
+        assert_eq!(
+            Similarity::Float(0.0),
+            fingerprinter.compute_metric("hello", "world")
+        );
+        assert_eq!(
+            Similarity::Float(1.0),
+            fingerprinter.compute_metric("hello", "hello")
+        );
+        assert_eq!(Similarity::Float(1.0), fingerprinter.compute_metric("", ""));
+        assert_delta!(
+            0.0,
+            fingerprint_similarity("hello", "a rather long document")
+        );
+    }
+
+    #[test]
+    fn fingerprint_long_documents() {
+        let a = "lorem ipsum dolor sit amet ".repeat(2000);
+        let b = format!("{a}elit");
+        assert!(fingerprint_similarity(&a, &b) > 0.5);
+    }
+}
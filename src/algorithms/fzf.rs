@@ -0,0 +1,293 @@
+use crate::algorithms::{Similarity, SimilarityMetric};
+
+// the scores of the fzf algorithm, see
+// <https://github.com/junegunn/fzf/blob/master/src/algo/algo.go>
+const SCORE_MATCH: i32 = 16;
+const SCORE_GAP_START: i32 = -3;
+const SCORE_GAP_EXTENSION: i32 = -1;
+// matching the first character of a word
+const BONUS_BOUNDARY: i32 = SCORE_MATCH / 2;
+const BONUS_BOUNDARY_WHITE: i32 = BONUS_BOUNDARY + 2;
+const BONUS_BOUNDARY_DELIMITER: i32 = BONUS_BOUNDARY + 1;
+// matching punctuation, which is rarely typed by accident
+const BONUS_NON_WORD: i32 = SCORE_MATCH / 2;
+// matching an uppercase letter after a lowercase one, or a digit after a
+// non-digit
+const BONUS_CAMEL_123: i32 = BONUS_BOUNDARY + SCORE_GAP_EXTENSION;
+// the minimum bonus of a character matched right after the previous one,
+// outweighing the penalty of the smallest gap
+const BONUS_CONSECUTIVE: i32 = -(SCORE_GAP_START + SCORE_GAP_EXTENSION);
+const BONUS_FIRST_CHAR_MULTIPLIER: i32 = 2;
+
+// the order matters: classes after `NonWord` start a word
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum CharClass {
+    White,
+    NonWord,
+    Delimiter,
+    Lower,
+    Upper,
+    Letter,
+    Number,
+}
+
+fn char_class(c: char) -> CharClass {
+    if c.is_whitespace() {
+        CharClass::White
+    } else if matches!(c, '/' | ',' | ':' | ';' | '|') {
+        CharClass::Delimiter
+    } else if c.is_lowercase() {
+        CharClass::Lower
+    } else if c.is_uppercase() {
+        CharClass::Upper
+    } else if c.is_alphabetic() {
+        CharClass::Letter
+    } else if c.is_numeric() {
+        CharClass::Number
+    } else {
+        CharClass::NonWord
+    }
+}
+
+// the bonus of matching a character of class `class` following one of class
+// `prev`
+fn bonus(prev: CharClass, class: CharClass) -> i32 {
+    if class > CharClass::NonWord {
+        match prev {
+            CharClass::White => return BONUS_BOUNDARY_WHITE,
+            CharClass::Delimiter => return BONUS_BOUNDARY_DELIMITER,
+            CharClass::NonWord => return BONUS_BOUNDARY,
+            _ => {}
+        }
+    }
+    if (prev == CharClass::Lower && class == CharClass::Upper)
+        || (prev != CharClass::Number && class == CharClass::Number)
+    {
+        return BONUS_CAMEL_123;
+    }
+    match class {
+        CharClass::NonWord | CharClass::Delimiter => BONUS_NON_WORD,
+        CharClass::White => BONUS_BOUNDARY_WHITE,
+        _ => 0,
+    }
+}
+
+/// The result of matching a query against a text with [`fzf_match`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FzfMatch {
+    /// The score of the match. Higher is better, and it only makes sense
+    /// to compare the scores of the same query.
+    pub score: i32,
+    /// The indices of the characters of the text matched by the characters
+    /// of the query, in increasing order.
+    pub positions: Vec<usize>,
+}
+
+/// Matches `query` against `text` like the fuzzy finder fzf, returning the
+/// score of the best match along with the matched positions, or `None` if
+/// `query` isn't a subsequence of `text`.
+///
+/// Each matched character scores a fixed amount, plus a bonus if it starts a
+/// word, e.g. after a space, a `/` or a `_`, if it is an uppercase letter
+/// following a lowercase one, or if it follows the previous matched
+/// character. The bonus of the first character of the query counts double,
+/// and gaps between matched characters are penalised. Characters are compared
+/// case-insensitively unless the query contains an uppercase letter.
+///
+/// An empty query matches any text with a score of 0.
+///
+/// ```
+/// use fuzzt::algorithms::fzf_match;
+///
+/// let found = fzf_match("smain", "src/main.rs").unwrap();
+/// assert_eq!(found.positions, [0, 4, 5, 6, 7]);
+/// assert!(found.score > fzf_match("smain", "tests/fixtures/some_main.txt").unwrap().score);
+/// assert_eq!(None, fzf_match("smain", "src/lib.rs"));
+/// ```
+pub fn fzf_match(query: &str, text: &str) -> Option<FzfMatch> {
+    let case_sensitive = query.chars().any(char::is_uppercase);
+    let fold = |c: char| {
+        if case_sensitive {
+            c
+        } else {
+            c.to_lowercase().next().unwrap_or(c)
+        }
+    };
+    let pattern: Vec<char> = query.chars().map(fold).collect();
+    let text: Vec<char> = text.chars().collect();
+    let folded: Vec<char> = text.iter().map(|&c| fold(c)).collect();
+    if pattern.is_empty() {
+        return Some(FzfMatch {
+            score: 0,
+            positions: vec![],
+        });
+    }
+
+    // the first position at which each character of the pattern can be
+    // matched, which also checks that the pattern is a subsequence
+    let mut first = Vec::with_capacity(pattern.len());
+    let mut start = 0;
+    for &p in &pattern {
+        let offset = folded[start..].iter().position(|&c| c == p)?;
+        first.push(start + offset);
+        start += offset + 1;
+    }
+
+    let mut bonuses = Vec::with_capacity(text.len());
+    let mut prev = CharClass::White;
+    for &c in &text {
+        let class = char_class(c);
+        bonuses.push(bonus(prev, class));
+        prev = class;
+    }
+
+    // `scores[i][j]` is the score of the best match of the first `i + 1`
+    // characters of the pattern within the first `j + 1` characters of the
+    // text, `matched[i][j]` whether it matches character `i` at `j`, and
+    // `consecutive[i][j]` the length of the run of consecutive matches it
+    // ends with
+    let width = text.len();
+    let mut scores = vec![0; pattern.len() * width];
+    let mut matched = vec![false; pattern.len() * width];
+    let mut consecutive = vec![0usize; pattern.len() * width];
+    for (i, &p) in pattern.iter().enumerate() {
+        let row = i * width;
+        let mut in_gap = false;
+        let mut left = 0;
+        for j in first[i]..width {
+            let gap = left
+                + if in_gap {
+                    SCORE_GAP_EXTENSION
+                } else {
+                    SCORE_GAP_START
+                };
+            let mut score = gap;
+            let mut run = 0;
+            if folded[j] == p {
+                if i == 0 {
+                    score = SCORE_MATCH + bonuses[j] * BONUS_FIRST_CHAR_MULTIPLIER;
+                    run = 1;
+                } else {
+                    let diagonal = row - width + j - 1;
+                    let mut b = bonuses[j];
+                    run = consecutive[diagonal] + 1;
+                    if run > 1 {
+                        // a run of consecutive matches gets the bonus of its
+                        // first character, unless this one starts a word
+                        let first_bonus = bonuses[j + 1 - run];
+                        if b >= BONUS_BOUNDARY && b > first_bonus {
+                            run = 1;
+                        } else {
+                            b = b.max(BONUS_CONSECUTIVE).max(first_bonus);
+                        }
+                    }
+                    let with_match = scores[diagonal] + SCORE_MATCH;
+                    if with_match + b < gap {
+                        score = with_match + bonuses[j];
+                        run = 0;
+                    } else {
+                        score = with_match + b;
+                    }
+                }
+                matched[row + j] = score >= gap;
+            }
+            if !matched[row + j] {
+                run = 0;
+            }
+            in_gap = !matched[row + j];
+            consecutive[row + j] = run;
+            scores[row + j] = score.max(gap).max(0);
+            left = scores[row + j];
+        }
+    }
+
+    // the best match ends with the last character of the pattern, at the
+    // earliest position in case of a tie
+    let last = (pattern.len() - 1) * width;
+    let mut j = (first[pattern.len() - 1]..width)
+        .filter(|&j| matched[last + j])
+        .max_by_key(|&j| (scores[last + j], std::cmp::Reverse(j)))?;
+    let score = scores[last + j];
+    let mut positions = vec![0; pattern.len()];
+    for i in (0..pattern.len()).rev() {
+        while !matched[i * width + j] {
+            j -= 1;
+        }
+        positions[i] = j;
+        j = j.saturating_sub(1);
+    }
+    Some(FzfMatch { score, positions })
+}
+
+/// Scores texts like the fuzzy finder fzf, for interactive search in file
+/// pickers and the like, see [`fzf_match`]. The first string is the query.
+///
+/// Scores are divided by the highest score a query of the same length can
+/// reach, e.g. against a text starting with the query, so they are between
+/// 0.0 and 1.0. Texts the query isn't a subsequence of score 0.0.
+///
+/// ```
+/// use fuzzt::algorithms::Fzf;
+/// use fuzzt::get_top_n;
+///
+/// let files = ["src/domain.rs", "src/main.rs", "examples/simple_main.rs"];
+/// let matches = get_top_n("smain", &files, Some(0.1), None, None, Some(&Fzf));
+/// assert_eq!(matches[0], "src/main.rs");
+/// ```
+pub struct Fzf;
+
+impl SimilarityMetric for Fzf {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        let len = a.chars().count() as i32;
+        let best = len * (SCORE_MATCH + BONUS_BOUNDARY_WHITE)
+            + BONUS_BOUNDARY_WHITE * (BONUS_FIRST_CHAR_MULTIPLIER - 1);
+        Similarity::Float(match fzf_match(a, b) {
+            Some(found) if len > 0 => f64::from(found.score) / f64::from(best),
+            _ => 0.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fzf_positions() {
+        let cases = [
+            ("smain", "src/main.rs", vec![0, 4, 5, 6, 7]),
+            ("fb", "foo_bar", vec![0, 4]),
+            ("fb", "FooBar", vec![0, 3]),
+            ("abc", "a__abc", vec![3, 4, 5]),
+            ("ooo", "oxooxo", vec![0, 2, 3]),
+            ("香", "öঙ香", vec![2]),
+        ];
+        for (query, text, positions) in cases {
+            let found = fzf_match(query, text).unwrap();
+            assert_eq!(positions, found.positions, "{query} {text}");
+        }
+    }
+
+    #[test]
+    fn fzf_ranking() {
+        let score = |query, text| fzf_match(query, text).map(|found| found.score);
+        // word boundaries and camel case beat matches inside words
+        assert!(score("fb", "foo_bar") > score("fb", "fabric"));
+        assert!(score("fb", "FooBar") > score("fb", "fooba"));
+        // consecutive matches beat scattered ones
+        assert!(score("main", "main.rs") > score("main", "m_a_i_n"));
+        // uppercase letters in the query make it case-sensitive
+        assert_eq!(None, score("Main", "main.rs"));
+        assert!(score("main", "Main.rs").is_some());
+        assert_eq!(Some(0), score("", "abc"));
+        assert_eq!(None, score("abc", ""));
+    }
+
+    #[test]
+    fn fzf_metric() {
+        assert_eq!(Similarity::Float(1.0), Fzf.compute_metric("ab", "ab c"));
+        assert!(Fzf.compute_metric("ab", "a b").as_f64() < 1.0);
+        assert_eq!(Similarity::Float(0.0), Fzf.compute_metric("ab", "ba"));
+        assert_eq!(Similarity::Float(0.0), Fzf.compute_metric("", "ab"));
+    }
+}
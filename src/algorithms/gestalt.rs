@@ -4,10 +4,15 @@ use std::collections::HashMap;
 
 /// Compares two strings `s1` and `s2` and returns a measure of their similarity as a float in the range [0, 1].
 ///
-/// The returned measure is computed as follows:
+/// This is the Ratcliff/Obershelp ratio computed by `ratio` in Python's
+/// difflib, and returns the same values:
 /// 1. If the total length of the two strings is 0, the function returns 1.0.
-/// 2. Otherwise, it computes the intersection of the character counts of the two strings,
-///    sums up the counts in the intersection, and returns the ratio of twice the sum of the counts to the total length.
+/// 2. Otherwise, it finds the longest block of characters common to both strings, then
+///    recursively the longest blocks to the left and to the right of it, and returns the
+///    ratio of twice the number of matched characters to the total length.
+///
+/// Like difflib, characters making up more than 1% of `s2` are ignored when
+/// looking for blocks if `s2` has at least 200 characters.
 ///
 /// # Arguments
 ///
@@ -23,12 +28,118 @@ use std::collections::HashMap;
 /// assert_eq!(sequence_matcher("test", "test"), 1.0);
 /// assert_eq!(sequence_matcher("test", "tent"), 0.75);
 /// assert_eq!(sequence_matcher("kitten", "sitting"), 0.6153846153846154);
+/// assert_eq!(sequence_matcher("abcd", "dcba"), 0.25);
 /// assert_eq!(sequence_matcher("", ""), 1.0);
 /// assert_eq!(sequence_matcher("test", ""), 0.0);
 /// assert_eq!(sequence_matcher("", "test"), 0.0);
 /// ```
 pub fn sequence_matcher(s1: &str, s2: &str) -> f64 {
-    let length = s1.len() + s2.len();
+    let a: Vec<char> = s1.chars().collect();
+    let b: Vec<char> = s2.chars().collect();
+    let length = a.len() + b.len();
+
+    if length == 0 {
+        return 1.0;
+    }
+
+    let matches = matching_characters(&a, &b);
+    2.0 * (matches as f64) / (length as f64)
+}
+
+/// Returns the positions of every character of `b`, leaving out the popular
+/// characters like difflib's autojunk heuristic.
+fn positions(b: &[char]) -> HashMap<char, Vec<usize>> {
+    let mut b2j: HashMap<char, Vec<usize>> = HashMap::new();
+    for (j, &c) in b.iter().enumerate() {
+        b2j.entry(c).or_default().push(j);
+    }
+    if b.len() >= 200 {
+        let popular = b.len() / 100 + 1;
+        b2j.retain(|_, positions| positions.len() <= popular);
+    }
+    b2j
+}
+
+/// Finds the longest block `a[i..i + size] == b[j..j + size]` within the
+/// given ranges, returning `(i, j, size)`. Among the longest blocks, the one
+/// starting first in `a`, then in `b`, is returned.
+fn longest_match(
+    a: &[char],
+    b: &[char],
+    b2j: &HashMap<char, Vec<usize>>,
+    (a_lo, a_hi): (usize, usize),
+    (b_lo, b_hi): (usize, usize),
+) -> (usize, usize, usize) {
+    let (mut best_i, mut best_j, mut best_size) = (a_lo, b_lo, 0);
+    // length of the longest block ending at each position of b, for the
+    // previous character of a
+    let mut j2len: HashMap<usize, usize> = HashMap::new();
+    for (i, c) in a.iter().enumerate().take(a_hi).skip(a_lo) {
+        let mut new_j2len = HashMap::new();
+        for &j in b2j.get(c).into_iter().flatten() {
+            if j < b_lo {
+                continue;
+            }
+            if j >= b_hi {
+                break;
+            }
+            let k = j.checked_sub(1).and_then(|j| j2len.get(&j)).unwrap_or(&0) + 1;
+            new_j2len.insert(j, k);
+            if k > best_size {
+                (best_i, best_j, best_size) = (i + 1 - k, j + 1 - k, k);
+            }
+        }
+        j2len = new_j2len;
+    }
+
+    // popular characters were left out of b2j, extend the block over them
+    while best_i > a_lo && best_j > b_lo && a[best_i - 1] == b[best_j - 1] {
+        best_i -= 1;
+        best_j -= 1;
+        best_size += 1;
+    }
+    while best_i + best_size < a_hi
+        && best_j + best_size < b_hi
+        && a[best_i + best_size] == b[best_j + best_size]
+    {
+        best_size += 1;
+    }
+    (best_i, best_j, best_size)
+}
+
+/// Returns the number of characters in the matching blocks of both strings.
+fn matching_characters(a: &[char], b: &[char]) -> usize {
+    let b2j = positions(b);
+    let mut matches = 0;
+    let mut queue = vec![((0, a.len()), (0, b.len()))];
+    while let Some(((a_lo, a_hi), (b_lo, b_hi))) = queue.pop() {
+        let (i, j, size) = longest_match(a, b, &b2j, (a_lo, a_hi), (b_lo, b_hi));
+        if size == 0 {
+            continue;
+        }
+        matches += size;
+        if a_lo < i && b_lo < j {
+            queue.push(((a_lo, i), (b_lo, j)));
+        }
+        if i + size < a_hi && j + size < b_hi {
+            queue.push(((i + size, a_hi), (j + size, b_hi)));
+        }
+    }
+    matches
+}
+
+/// Returns an upper bound on the ratio of `sequence_matcher`, computed from
+/// the characters both strings have in common regardless of their order. Like
+/// `quick_ratio` in Python's difflib.
+///
+/// ```
+/// use fuzzt::algorithms::quick_ratio;
+///
+/// assert_eq!(quick_ratio("abcd", "dcba"), 1.0);
+/// assert_eq!(quick_ratio("kitten", "sitting"), 0.6153846153846154);
+/// ```
+pub fn quick_ratio(s1: &str, s2: &str) -> f64 {
+    let length = s1.chars().count() + s2.chars().count();
 
     if length == 0 {
         return 1.0;
@@ -38,6 +149,27 @@ pub fn sequence_matcher(s1: &str, s2: &str) -> f64 {
     let matches: usize = intersect.values().sum();
     2.0 * (matches as f64) / (length as f64)
 }
+
+/// Returns an upper bound on `quick_ratio` (and therefore on
+/// `sequence_matcher`) computed only from the lengths of the strings. Like
+/// `real_quick_ratio` in Python's difflib.
+///
+/// ```
+/// use fuzzt::algorithms::real_quick_ratio;
+///
+/// assert_eq!(real_quick_ratio("kitten", "sitting"), 0.9230769230769231);
+/// ```
+pub fn real_quick_ratio(s1: &str, s2: &str) -> f64 {
+    let len1 = s1.chars().count();
+    let len2 = s2.chars().count();
+
+    if len1 + len2 == 0 {
+        return 1.0;
+    }
+
+    2.0 * (len1.min(len2) as f64) / ((len1 + len2) as f64)
+}
+
 fn counter(s: &str) -> HashMap<char, usize> {
     let mut count = HashMap::new();
     for c in s.chars() {
@@ -77,4 +209,42 @@ mod tests {
         assert_eq!(sequence_matcher("test", ""), 0.0);
         assert_eq!(sequence_matcher("", "test"), 0.0);
     }
+
+    #[test]
+    fn test_sequence_matcher_reordered() {
+        assert_eq!(sequence_matcher("tide", "diet"), 0.25);
+        assert_eq!(
+            sequence_matcher("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear"),
+            0.9090909090909091
+        );
+        assert_eq!(sequence_matcher("öঙ香", "香ö"), 0.4);
+        assert_eq!(sequence_matcher("martha", "marhta"), 0.8333333333333334);
+    }
+
+    #[test]
+    fn test_sequence_matcher_autojunk() {
+        let a = format!("{}xyz", "ab".repeat(150));
+        let b = format!("{}xyz{}", "ba".repeat(140), "ab".repeat(10));
+        assert_eq!(sequence_matcher(&a, &b), 0.009900990099009901);
+    }
+
+    #[test]
+    fn test_quick_ratio_bounds() {
+        let pairs = [
+            ("abcd", "dcba"),
+            ("kitten", "sitting"),
+            ("", "abc"),
+            ("öঙ香", "香ö"),
+        ];
+        for (a, b) in pairs {
+            assert!(sequence_matcher(a, b) <= quick_ratio(a, b));
+            assert!(quick_ratio(a, b) <= real_quick_ratio(a, b));
+        }
+    }
+
+    #[test]
+    fn test_quick_ratio_multibyte() {
+        assert_eq!(quick_ratio("öঙ香", "öঙ香"), 1.0);
+        assert_eq!(real_quick_ratio("öঙ香", "ö"), 0.5);
+    }
 }
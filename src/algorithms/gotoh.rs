@@ -0,0 +1,161 @@
+use std::cmp::min;
+
+use crate::algorithms::{Similarity, SimilarityMetric};
+use crate::utils::with_scratch;
+
+// costs are added with saturating arithmetic, so huge penalties can't
+// overflow and unreachable states stay unreachable
+const UNREACHABLE: usize = usize::MAX;
+
+/// Calculates the edit distance between two strings with affine gap costs,
+/// using Gotoh's algorithm.
+/// See <https://en.wikipedia.org/wiki/Gap_penalty#Affine>.
+///
+/// A gap of `k` consecutive insertions or deletions costs
+/// `gap_open + (k - 1) * gap_extend`, so a single long insertion (e.g. a
+/// middle name) costs much less than the same number of scattered edits.
+/// With all costs set to 1 this is the Levenshtein distance.
+///
+/// ```
+/// use fuzzt::algorithms::Gotoh;
+///
+/// let gotoh = Gotoh::new(2, 3, 1);
+/// assert_eq!(5, gotoh.distance("abcdef", "abcXYZdef"));
+/// assert_eq!(9, gotoh.distance("abcdef", "aXbcYdeZf"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gotoh {
+    substitution: usize,
+    gap_open: usize,
+    gap_extend: usize,
+}
+
+impl Gotoh {
+    /// Creates a metric with the cost of substituting a character, of opening
+    /// a gap and of extending a gap by one more character.
+    pub fn new(substitution: usize, gap_open: usize, gap_extend: usize) -> Self {
+        Gotoh {
+            substitution,
+            gap_open,
+            gap_extend,
+        }
+    }
+
+    fn gap(&self, len: usize) -> usize {
+        self.gap_open
+            .saturating_add((len - 1).saturating_mul(self.gap_extend))
+    }
+
+    /// Calculates the cost of the cheapest alignment of both strings.
+    pub fn distance(&self, a: &str, b: &str) -> usize {
+        let b_chars: Vec<char> = b.chars().collect();
+        let width = b_chars.len() + 1;
+
+        with_scratch(4 * width, |buffer| {
+            // `distances` hold the cost of the best alignment of the prefixes,
+            // `deletions` the cost of the best one ending with a gap in `b`
+            let (prev_distances, rest) = buffer.split_at_mut(width);
+            let (prev_deletions, rest) = rest.split_at_mut(width);
+            let (curr_distances, curr_deletions) = rest.split_at_mut(width);
+
+            prev_distances[0] = 0;
+            prev_deletions[0] = UNREACHABLE;
+            for j in 1..width {
+                prev_distances[j] = self.gap(j);
+                prev_deletions[j] = UNREACHABLE;
+            }
+
+            for (i, a_char) in a.chars().enumerate() {
+                curr_distances[0] = self.gap(i + 1);
+                curr_deletions[0] = curr_distances[0];
+                // the cost of the best alignment ending with a gap in `a`
+                let mut insertion = UNREACHABLE;
+
+                for (j, &b_char) in b_chars.iter().enumerate() {
+                    curr_deletions[j + 1] = min(
+                        prev_distances[j + 1].saturating_add(self.gap_open),
+                        prev_deletions[j + 1].saturating_add(self.gap_extend),
+                    );
+                    insertion = min(
+                        curr_distances[j].saturating_add(self.gap_open),
+                        insertion.saturating_add(self.gap_extend),
+                    );
+                    let cost = if a_char == b_char {
+                        0
+                    } else {
+                        self.substitution
+                    };
+                    curr_distances[j + 1] = min(
+                        prev_distances[j].saturating_add(cost),
+                        min(curr_deletions[j + 1], insertion),
+                    );
+                }
+
+                prev_distances.copy_from_slice(curr_distances);
+                prev_deletions.copy_from_slice(curr_deletions);
+            }
+
+            prev_distances[width - 1]
+        })
+    }
+}
+
+impl Default for Gotoh {
+    /// Substitutions cost 2, opening a gap 3 and extending it 1.
+    fn default() -> Self {
+        Gotoh::new(2, 3, 1)
+    }
+}
+
+impl SimilarityMetric for Gotoh {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Usize(self.distance(a, b))
+    }
+}
+
+/// Calculates the edit distance between two strings with the default affine
+/// gap costs of [`Gotoh`].
+///
+/// ```
+/// use fuzzt::algorithms::gotoh;
+///
+/// assert_eq!(8, gotoh("john smith", "john david smith"));
+/// ```
+pub fn gotoh(a: &str, b: &str) -> usize {
+    Gotoh::default().distance(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gotoh_empty() {
+        assert_eq!(0, gotoh("", ""));
+        assert_eq!(5, gotoh("", "abc"));
+        assert_eq!(5, gotoh("abc", ""));
+    }
+
+    #[test]
+    fn gotoh_unit_costs_match_levenshtein() {
+        let unit = Gotoh::new(1, 1, 1);
+        assert_eq!(3, unit.distance("kitten", "sitting"));
+        assert_eq!(6, unit.distance("john smith", "john david smith"));
+        assert_eq!(3, unit.distance("öঙ香", "abc"));
+        assert_eq!(4, unit.distance("abcdef", "af"));
+    }
+
+    #[test]
+    fn gotoh_prefers_long_gaps() {
+        assert_eq!(6, gotoh("abcdef", "af"));
+        assert_eq!(4, gotoh("abcdef", "axcyef"));
+        assert_eq!(7, gotoh("kitten", "sitting"));
+    }
+
+    #[test]
+    fn gotoh_huge_costs_saturate() {
+        let gotoh = Gotoh::new(usize::MAX, usize::MAX, usize::MAX);
+        assert_eq!(usize::MAX, gotoh.distance("abc", "xyz"));
+        assert_eq!(0, gotoh.distance("abc", "abc"));
+    }
+}
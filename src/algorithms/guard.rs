@@ -0,0 +1,245 @@
+use crate::algorithms::{Similarity, SimilarityMetric};
+use crate::utils::FuzztError;
+
+/// What a [`LengthGuard`] does with inputs longer than its limit.
+pub enum OversizedInput<'m> {
+    /// Only compare the first `max_len` characters of each string.
+    Truncate,
+    /// Compare the strings with a cheaper metric instead, such as
+    /// `SorensenDice`. The fallback must return the same kind of result as
+    /// the guarded metric, distances or similarities, so that all scores can
+    /// be ranked together.
+    Fallback(&'m dyn SimilarityMetric),
+    /// Refuse to compare the strings. [`LengthGuard::try_compute_metric`]
+    /// returns `FuzztError::InputTooLong`, and
+    /// [`SimilarityMetric::compute_metric`] scores the pair as the worst
+    /// result of the guarded metric: `Similarity::Usize(usize::MAX)` for
+    /// distances and `Similarity::Float(0.0)` for similarities.
+    Reject,
+}
+
+/// Wraps a metric and limits the length of the strings it compares.
+///
+/// Most edit distances run in `O(N*M)`, so a single pathological input of a
+/// few megabytes can take minutes to score. Strings longer than `max_len`
+/// characters are handled according to the [`OversizedInput`] policy instead.
+///
+/// ```
+/// use fuzzt::algorithms::{Levenshtein, LengthGuard, OversizedInput, Similarity, SimilarityMetric};
+/// use fuzzt::FuzztError;
+///
+/// let guard = LengthGuard::new(&Levenshtein, 8, OversizedInput::Truncate);
+/// assert!(matches!(guard.compute_metric("kitten", "sitting"), Similarity::Usize(3)));
+/// assert!(matches!(guard.compute_metric("abcdefgh-1", "abcdefgh-2"), Similarity::Usize(0)));
+///
+/// let guard = LengthGuard::new(&Levenshtein, 8, OversizedInput::Reject);
+/// assert!(matches!(
+///     guard.try_compute_metric("abcdefgh-1", "abcdefgh-2"),
+///     Err(FuzztError::InputTooLong)
+/// ));
+/// ```
+pub struct LengthGuard<'m> {
+    metric: &'m dyn SimilarityMetric,
+    max_len: usize,
+    policy: OversizedInput<'m>,
+}
+
+impl<'m> LengthGuard<'m> {
+    /// Creates a guard that applies `policy` to strings longer than `max_len`
+    /// characters and compares all other strings with `metric`.
+    ///
+    /// Panics if the policy falls back to a metric returning distances while
+    /// `metric` returns similarities, or the other way around.
+    pub fn new(
+        metric: &'m dyn SimilarityMetric,
+        max_len: usize,
+        policy: OversizedInput<'m>,
+    ) -> Self {
+        if let OversizedInput::Fallback(fallback) = policy {
+            assert_eq!(
+                returns_distances(metric),
+                returns_distances(fallback),
+                "the fallback must return the same kind of result as the metric"
+            );
+        }
+        LengthGuard {
+            metric,
+            max_len,
+            policy,
+        }
+    }
+
+    // the worst result of the guarded metric, given to rejected pairs
+    fn worst(&self) -> Similarity {
+        if returns_distances(self.metric) {
+            Similarity::Usize(usize::MAX)
+        } else {
+            Similarity::Float(0.0)
+        }
+    }
+
+    fn exceeds(&self, s: &str) -> bool {
+        // avoid counting all characters of huge strings
+        s.len() > self.max_len && s.chars().nth(self.max_len).is_some()
+    }
+
+    fn truncate<'a>(&self, s: &'a str) -> &'a str {
+        match s.char_indices().nth(self.max_len) {
+            Some((index, _)) => &s[..index],
+            None => s,
+        }
+    }
+
+    /// Compares the strings like `compute_metric`, but returns
+    /// `FuzztError::InputTooLong` if the policy is `OversizedInput::Reject`
+    /// and one of the strings exceeds the limit.
+    pub fn try_compute_metric(&self, a: &str, b: &str) -> Result<Similarity, FuzztError> {
+        if !self.exceeds(a) && !self.exceeds(b) {
+            return Ok(self.metric.compute_metric(a, b));
+        }
+
+        match self.policy {
+            OversizedInput::Truncate => Ok(self
+                .metric
+                .compute_metric(self.truncate(a), self.truncate(b))),
+            OversizedInput::Fallback(fallback) => Ok(fallback.compute_metric(a, b)),
+            OversizedInput::Reject => Err(FuzztError::InputTooLong),
+        }
+    }
+}
+
+impl SimilarityMetric for LengthGuard<'_> {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        self.try_compute_metric(a, b)
+            .unwrap_or_else(|_| self.worst())
+    }
+}
+
+// the kind of result of a metric, found by comparing empty strings
+fn returns_distances(metric: &dyn SimilarityMetric) -> bool {
+    metric.compute_metric("", "").is_distance()
+}
+
+/// Wraps a metric and dampens its scores for short strings, which share
+/// characters by chance far more often than long ones.
+///
+/// Scores are normalized to similarities and multiplied by the length of the
+/// shorter string divided by `min_len`, so only strings shorter than `min_len`
+/// characters are affected. Identical strings keep their score. Since the
+/// result is a similarity, the wrapper can be passed as the scorer of the
+/// matching functions.
+///
+/// ```
+/// use fuzzt::algorithms::{LengthDamping, NormalizedLevenshtein, SimilarityMetric};
+/// use fuzzt::get_top_n;
+///
+/// let damped = LengthDamping::new(&NormalizedLevenshtein, 4);
+/// assert_eq!(0.25, damped.compute_metric("ab", "ac").as_f64());
+/// assert_eq!(1.0, damped.compute_metric("ab", "ab").as_f64());
+///
+/// let choices = ["ac", "abxy"];
+/// let matches = get_top_n("abx", &choices, Some(0.4), None, None, Some(&damped));
+/// assert_eq!(matches, ["abxy"]);
+/// ```
+pub struct LengthDamping<'m> {
+    metric: &'m dyn SimilarityMetric,
+    min_len: usize,
+}
+
+impl<'m> LengthDamping<'m> {
+    /// Creates a wrapper dampening the scores of `metric` for strings shorter
+    /// than `min_len` characters.
+    pub fn new(metric: &'m dyn SimilarityMetric, min_len: usize) -> Self {
+        LengthDamping { metric, min_len }
+    }
+
+    /// Returns the factor scores of the two strings are multiplied by.
+    pub fn damping(&self, a: &str, b: &str) -> f64 {
+        if self.min_len == 0 {
+            return 1.0;
+        }
+        let shorter = a.chars().count().min(b.chars().count());
+        (shorter as f64 / self.min_len as f64).min(1.0)
+    }
+}
+
+impl SimilarityMetric for LengthDamping<'_> {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        let max_len = a.chars().count().max(b.chars().count());
+        let score = self.metric.compute_metric(a, b).normalize(max_len);
+        if a == b {
+            return Similarity::Float(score);
+        }
+        Similarity::Float(score * self.damping(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{Levenshtein, NormalizedLevenshtein};
+
+    #[test]
+    fn length_guard_within_limit() {
+        let guard = LengthGuard::new(&NormalizedLevenshtein, 7, OversizedInput::Reject);
+        assert_delta!(0.57142, guard.compute_metric("kitten", "sitting").as_f64());
+    }
+
+    #[test]
+    fn length_guard_truncate_multibyte() {
+        let guard = LengthGuard::new(&NormalizedLevenshtein, 3, OversizedInput::Truncate);
+        assert_delta!(1.0, guard.compute_metric("öঙ香a", "öঙ香b").as_f64());
+    }
+
+    #[test]
+    fn length_guard_fallback() {
+        let damped = LengthDamping::new(&NormalizedLevenshtein, 8);
+        let guard = LengthGuard::new(&NormalizedLevenshtein, 4, OversizedInput::Fallback(&damped));
+        assert_delta!(0.375, guard.compute_metric("abcde", "abced").as_f64());
+        assert_delta!(0.75, guard.compute_metric("abcd", "abce").as_f64());
+    }
+
+    #[test]
+    #[should_panic]
+    fn length_guard_fallback_of_other_kind() {
+        LengthGuard::new(
+            &NormalizedLevenshtein,
+            4,
+            OversizedInput::Fallback(&Levenshtein),
+        );
+    }
+
+    #[test]
+    fn length_damping_short_strings() {
+        let damped = LengthDamping::new(&Levenshtein, 4);
+        assert_delta!(0.5, damped.damping("ab", "abcdef"));
+        assert_delta!(1.0, damped.damping("abcd", "abce"));
+        assert_delta!(0.0, damped.compute_metric("", "ab").as_f64());
+        assert_delta!(1.0, damped.compute_metric("", "").as_f64());
+        assert_delta!(0.75, damped.compute_metric("abcd", "abce").as_f64());
+        assert_delta!(0.25, damped.compute_metric("öঙ", "öa").as_f64());
+        assert_delta!(
+            0.5,
+            LengthDamping::new(&NormalizedLevenshtein, 0)
+                .compute_metric("ab", "ac")
+                .as_f64()
+        );
+    }
+
+    #[test]
+    fn length_guard_reject() {
+        let guard = LengthGuard::new(&NormalizedLevenshtein, 4, OversizedInput::Reject);
+        assert!(matches!(
+            guard.try_compute_metric("abcd", "abcde"),
+            Err(FuzztError::InputTooLong)
+        ));
+        assert_delta!(0.0, guard.compute_metric("abcde", "abcde").as_f64());
+
+        // rejected pairs rank last for distances too
+        let guard = LengthGuard::new(&Levenshtein, 4, OversizedInput::Reject);
+        assert!(matches!(
+            guard.compute_metric("abcde", "abcde"),
+            Similarity::Usize(usize::MAX)
+        ));
+    }
+}
@@ -41,11 +41,203 @@ pub fn hamming(a: &str, b: &str) -> HammingResult {
     generic_hamming(a.chars(), b.chars())
 }
 
+/// Like [`hamming`], but compares two sequences of any elements, e.g. the
+/// characters produced by a decoder, without collecting them first.
+///
+/// ```
+/// use fuzzt::algorithms::hamming_iter;
+///
+/// let decoded = "HAMMING".chars().map(|c| c.to_ascii_lowercase());
+/// assert_eq!(Ok(3), hamming_iter(decoded, "hammers".chars()));
+/// ```
+pub fn hamming_iter<Iter1, Iter2>(a: Iter1, b: Iter2) -> HammingResult
+where
+    Iter1: IntoIterator,
+    Iter2: IntoIterator,
+    Iter1::Item: PartialEq<Iter2::Item>,
+{
+    generic_hamming(a, b)
+}
+
+/// How the Hamming distance handles strings of different lengths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LengthPolicy {
+    /// Return `FuzztError::DifferentLengthArgs`, like [`hamming`].
+    Reject,
+    /// Pad the shorter string, so every character past its end counts as a
+    /// difference.
+    #[default]
+    Pad,
+    /// Only compare the characters of the longer string up to the length of
+    /// the shorter one.
+    Truncate,
+}
+
+/// Like [`hamming`], but handles strings of different lengths according to
+/// `policy`.
+///
+/// ```
+/// use fuzzt::algorithms::{hamming_with_policy, LengthPolicy};
+/// use fuzzt::FuzztError;
+///
+/// assert_eq!(Ok(4), hamming_with_policy("hamming", "ham", LengthPolicy::Pad));
+/// assert_eq!(Ok(0), hamming_with_policy("hamming", "ham", LengthPolicy::Truncate));
+/// assert_eq!(
+///     Err(FuzztError::DifferentLengthArgs),
+///     hamming_with_policy("hamming", "ham", LengthPolicy::Reject)
+/// );
+/// ```
+pub fn hamming_with_policy(a: &str, b: &str, policy: LengthPolicy) -> HammingResult {
+    if policy == LengthPolicy::Reject {
+        return hamming(a, b);
+    }
+    let (mut ita, mut itb) = (a.chars(), b.chars());
+    let mut count = 0;
+    loop {
+        match (ita.next(), itb.next()) {
+            (None, None) => return Ok(count),
+            (Some(x), Some(y)) => count += usize::from(x != y),
+            _ if policy == LengthPolicy::Truncate => return Ok(count),
+            _ => count += 1,
+        }
+    }
+}
+
+/// Calculates the fraction of the positions where the characters of the
+/// strings are equal, between 0.0 and 1.0. Returns an error if the strings
+/// have different lengths.
+///
+/// ```
+/// use fuzzt::algorithms::normalized_hamming;
+/// use fuzzt::FuzztError;
+///
+/// assert_eq!(Ok(0.75), normalized_hamming("rust", "bust"));
+/// assert_eq!(Ok(1.0), normalized_hamming("", ""));
+/// assert_eq!(Err(FuzztError::DifferentLengthArgs), normalized_hamming("rust", "rusty"));
+/// ```
+pub fn normalized_hamming(a: &str, b: &str) -> Result<f64, FuzztError> {
+    normalized_hamming_with_policy(a, b, LengthPolicy::Reject)
+}
+
+/// Like [`normalized_hamming`], but handles strings of different lengths
+/// according to `policy`. Padded distances are divided by the length of the
+/// longer string, and truncated ones by the length of the shorter string. An
+/// empty string only scores 1.0 against another empty string, including when
+/// truncating.
+///
+/// ```
+/// use fuzzt::algorithms::{normalized_hamming_with_policy, LengthPolicy};
+///
+/// assert_eq!(Ok(0.8), normalized_hamming_with_policy("rust", "rusty", LengthPolicy::Pad));
+/// assert_eq!(Ok(1.0), normalized_hamming_with_policy("rust", "rusty", LengthPolicy::Truncate));
+/// assert_eq!(Ok(0.0), normalized_hamming_with_policy("", "rust", LengthPolicy::Truncate));
+/// ```
+pub fn normalized_hamming_with_policy(
+    a: &str,
+    b: &str,
+    policy: LengthPolicy,
+) -> Result<f64, FuzztError> {
+    let distance = hamming_with_policy(a, b, policy)?;
+    let a_len = a.chars().count();
+    let b_len = b.chars().count();
+    let len = match policy {
+        LengthPolicy::Truncate => a_len.min(b_len),
+        LengthPolicy::Reject | LengthPolicy::Pad => a_len.max(b_len),
+    };
+    if len == 0 {
+        return Ok(if a_len == b_len { 1.0 } else { 0.0 });
+    }
+    Ok(1.0 - distance as f64 / len as f64)
+}
+
+/// Like [`hamming`], but compares bytes and can be evaluated at compile time,
+/// e.g. to build static tables of distances between ASCII literals.
+///
+/// ```
+/// use fuzzt::algorithms::const_hamming;
+/// use fuzzt::FuzztError;
+///
+/// const DISTANCE: usize = match const_hamming(b"hamming", b"hammers") {
+///     Ok(distance) => distance,
+///     Err(_) => panic!("literals have different lengths"),
+/// };
+/// assert_eq!(3, DISTANCE);
+/// assert_eq!(Err(FuzztError::DifferentLengthArgs), const_hamming(b"hamming", b"ham"));
+/// ```
+pub const fn const_hamming(a: &[u8], b: &[u8]) -> HammingResult {
+    if a.len() != b.len() {
+        return Err(FuzztError::DifferentLengthArgs);
+    }
+    let mut count = 0;
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            count += 1;
+        }
+        i += 1;
+    }
+    Ok(count)
+}
+
+/// Scores strings with their Hamming distance.
+///
+/// The distance isn't defined for strings of different lengths, which are
+/// scored as if the shorter one was padded: every character past its end
+/// counts as a difference.
+///
+/// ```
+/// use fuzzt::algorithms::{Hamming, Similarity, SimilarityMetric};
+///
+/// assert_eq!(Similarity::Usize(3), Hamming.compute_metric("hamming", "hammers"));
+/// assert_eq!(Similarity::Usize(4), Hamming.compute_metric("hamming", "ham"));
+/// ```
 pub struct Hamming;
 
 impl SimilarityMetric for Hamming {
     fn compute_metric(&self, a: &str, b: &str) -> Similarity {
-        Similarity::Usize(hamming(a, b).unwrap())
+        // padding never fails
+        let distance = hamming_with_policy(a, b, LengthPolicy::Pad).unwrap_or_default();
+        Similarity::Usize(distance)
+    }
+}
+
+/// Scores strings with their normalized Hamming similarity, handling strings
+/// of different lengths according to a [`LengthPolicy`].
+///
+/// Unlike [`Hamming`], the scores are similarities between 0.0 and 1.0, so
+/// they can be used with the cutoffs of `get_top_n`. With
+/// `LengthPolicy::Reject`, strings of different lengths score 0.0.
+///
+/// ```
+/// use fuzzt::{algorithms::NormalizedHamming, get_top_n};
+///
+/// let codes = ["AB-1234", "AB-1243", "AB-12345", "XY-1234"];
+/// let matches = get_top_n("AB-1234", &codes, Some(0.7), None, None, Some(&NormalizedHamming::default()));
+/// assert_eq!(matches, ["AB-1234", "AB-12345", "AB-1243"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizedHamming {
+    policy: LengthPolicy,
+}
+
+impl NormalizedHamming {
+    /// Creates a scorer handling strings of different lengths according to
+    /// `policy`.
+    pub fn new(policy: LengthPolicy) -> Self {
+        NormalizedHamming { policy }
+    }
+}
+
+impl Default for NormalizedHamming {
+    /// Pads the shorter string.
+    fn default() -> Self {
+        NormalizedHamming::new(LengthPolicy::Pad)
+    }
+}
+
+impl SimilarityMetric for NormalizedHamming {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(normalized_hamming_with_policy(a, b, self.policy).unwrap_or(0.0))
     }
 }
 
@@ -67,6 +259,17 @@ mod tests {
         assert_hamming_dist(0, "hamming", "hamming")
     }
 
+    #[test]
+    fn const_hamming_matches_hamming() {
+        const DISTANCE: HammingResult = const_hamming(b"karolin", b"kathrin");
+        assert_eq!(hamming("karolin", "kathrin"), DISTANCE);
+        assert_eq!(Ok(0), const_hamming(b"", b""));
+        assert_eq!(
+            Err(FuzztError::DifferentLengthArgs),
+            const_hamming(b"a", b"")
+        );
+    }
+
     #[test]
     fn hamming_numbers() {
         assert_eq!(Ok(1), generic_hamming(&[1, 2, 4], &[1, 2, 3]));
@@ -82,6 +285,17 @@ mod tests {
         assert_hamming_dist(2, "hamming", "h香mmüng");
     }
 
+    #[test]
+    fn hamming_iterators() {
+        assert_eq!(Ok(0), hamming_iter("".chars(), "".chars()));
+        assert_eq!(Ok(1), hamming_iter("öঙ香".chars(), "öঙa".chars()));
+        assert_eq!(Ok(2), hamming_iter([1, 2, 3], vec![1, 4, 5]));
+        assert_eq!(
+            Err(FuzztError::DifferentLengthArgs),
+            hamming_iter("ab".chars(), "a".chars())
+        );
+    }
+
     #[test]
     fn hamming_unequal_length() {
         assert_eq!(
@@ -90,6 +304,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hamming_length_policies() {
+        assert_eq!(Ok(0), hamming_with_policy("", "", LengthPolicy::Reject));
+        assert_eq!(Ok(3), hamming_with_policy("", "öঙ香", LengthPolicy::Pad));
+        assert_eq!(
+            Ok(0),
+            hamming_with_policy("öঙ香", "", LengthPolicy::Truncate)
+        );
+        assert_eq!(
+            Ok(1),
+            hamming_with_policy("ham", "hxmmer", LengthPolicy::Truncate)
+        );
+        assert_eq!(
+            Ok(4),
+            hamming_with_policy("ham", "hxmmer", LengthPolicy::Pad)
+        );
+    }
+
+    #[test]
+    fn normalized_hamming_scores() {
+        assert_delta!(1.0, normalized_hamming("", "").unwrap());
+        assert_delta!(0.0, normalized_hamming("ab", "ba").unwrap());
+        assert_delta!(
+            0.5,
+            normalized_hamming_with_policy("ab", "abcd", LengthPolicy::Pad).unwrap()
+        );
+        // an empty string isn't a truncated copy of every string
+        assert_delta!(
+            0.0,
+            normalized_hamming_with_policy("", "abcd", LengthPolicy::Truncate).unwrap()
+        );
+        assert_delta!(
+            1.0,
+            normalized_hamming_with_policy("", "", LengthPolicy::Truncate).unwrap()
+        );
+        let reject = NormalizedHamming::new(LengthPolicy::Reject);
+        assert_eq!(Similarity::Float(0.0), reject.compute_metric("ab", "abc"));
+        assert_eq!(Similarity::Float(0.5), reject.compute_metric("ab", "ac"));
+    }
+
     #[test]
     fn hamming_names() {
         assert_hamming_dist(14, "Friedrich Nietzs", "Jean-Paul Sartre")
@@ -0,0 +1,266 @@
+use crate::algorithms::{NGramProfile, Similarity, SimilarityMetric};
+use crate::processors::{Tokenizer, WhitespaceTokenizer};
+use std::collections::HashSet;
+
+/// Calculates the Jaccard index between the character bigrams of two strings,
+/// counting repetitions.
+/// See <https://en.wikipedia.org/wiki/Jaccard_index>.
+///
+/// ```
+/// use fuzzt::algorithms::jaccard;
+///
+/// assert_eq!(1.0, jaccard("", ""));
+/// assert_eq!(0.0, jaccard("", "a"));
+/// assert_eq!(0.0, jaccard("french", "quebec"));
+/// assert_eq!(0.8, jaccard("feris", "ferris"));
+/// ```
+pub fn jaccard(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+
+    let a_bigrams = NGramProfile::new(a, 2);
+    let b_bigrams = NGramProfile::new(b, 2);
+
+    if a_bigrams.is_empty() || b_bigrams.is_empty() {
+        return 0.0;
+    }
+
+    a_bigrams.jaccard(&b_bigrams)
+}
+
+/// Like [`jaccard`], but pads both strings with `pad` before splitting them
+/// into bigrams, so differences in the first and last characters weigh as much
+/// as the others.
+///
+/// ```
+/// use fuzzt::algorithms::jaccard_padded;
+///
+/// assert_eq!(0.2, jaccard_padded("ab", "ac", '#'));
+/// assert_eq!(6.0 / 7.0, jaccard_padded("feris", "ferris", '#'));
+/// ```
+pub fn jaccard_padded(a: &str, b: &str, pad: char) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+
+    let a_bigrams = NGramProfile::padded(a, 2, pad);
+    let b_bigrams = NGramProfile::padded(b, 2, pad);
+
+    if a_bigrams.is_empty() || b_bigrams.is_empty() {
+        return 0.0;
+    }
+
+    a_bigrams.jaccard(&b_bigrams)
+}
+
+/// Calculates the Jaccard index between the sets of whitespace-separated
+/// tokens of two strings, ignoring their order and repetitions.
+///
+/// ```
+/// use fuzzt::algorithms::token_jaccard;
+///
+/// assert_eq!(1.0, token_jaccard("new york city", "city new york"));
+/// assert_eq!(2.0 / 3.0, token_jaccard("new york city", "new york"));
+/// ```
+pub fn token_jaccard(a: &str, b: &str) -> f64 {
+    TokenJaccard::default().similarity(a, b)
+}
+
+pub struct Jaccard;
+
+impl SimilarityMetric for Jaccard {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(jaccard(a, b))
+    }
+}
+
+/// Scores strings with [`jaccard_padded`].
+pub struct PaddedJaccard {
+    pad: char,
+}
+
+impl PaddedJaccard {
+    /// Creates a metric padding strings with `pad`.
+    pub fn new(pad: char) -> Self {
+        PaddedJaccard { pad }
+    }
+}
+
+impl Default for PaddedJaccard {
+    /// Pads strings with `#`.
+    fn default() -> Self {
+        PaddedJaccard::new('#')
+    }
+}
+
+impl SimilarityMetric for PaddedJaccard {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(jaccard_padded(a, b, self.pad))
+    }
+}
+
+/// Scores strings with the Jaccard index between their sets of tokens.
+///
+/// ```
+/// use fuzzt::algorithms::TokenJaccard;
+/// use fuzzt::processors::WordTokenizer;
+///
+/// let scorer = TokenJaccard::new(&WordTokenizer);
+/// assert_eq!(1.0, scorer.similarity("Smith, John", "John Smith"));
+/// ```
+pub struct TokenJaccard<'t> {
+    tokenizer: &'t dyn Tokenizer,
+}
+
+impl<'t> TokenJaccard<'t> {
+    /// Creates a scorer comparing the tokens produced by `tokenizer`.
+    pub fn new(tokenizer: &'t dyn Tokenizer) -> Self {
+        TokenJaccard { tokenizer }
+    }
+
+    /// Calculates the Jaccard index between the token sets of both strings.
+    /// Strings without any token are only similar to each other.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        let a: HashSet<&str> = self.tokenizer.tokenize(a).into_iter().collect();
+        let b: HashSet<&str> = self.tokenizer.tokenize(b).into_iter().collect();
+        if a.is_empty() && b.is_empty() {
+            return 1.0;
+        }
+
+        let shared = a.intersection(&b).count();
+        shared as f64 / (a.len() + b.len() - shared) as f64
+    }
+}
+
+impl Default for TokenJaccard<'static> {
+    /// Splits tokens on whitespace.
+    fn default() -> Self {
+        TokenJaccard::new(&WhitespaceTokenizer)
+    }
+}
+
+impl SimilarityMetric for TokenJaccard<'_> {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.similarity(a, b))
+    }
+}
+
+/// Scores strings with the Jaccard index between their sets of tokens, where
+/// every token counts as much as its weight.
+///
+/// The score is the total weight of the shared tokens divided by the total
+/// weight of all distinct tokens, so important tokens (e.g. brands, or rare
+/// tokens weighted by their inverse document frequency) dominate the score.
+/// With uniform weights it's the same as `TokenJaccard`. Weights are expected
+/// to be non-negative; if every token weighs nothing, strings are only similar
+/// if they have the same tokens.
+///
+/// ```
+/// use fuzzt::algorithms::WeightedTokenJaccard;
+/// use fuzzt::processors::WhitespaceTokenizer;
+///
+/// let brand = |token: &str| if token == "apple" { 5.0 } else { 1.0 };
+/// let scorer = WeightedTokenJaccard::new(&WhitespaceTokenizer, &brand);
+/// assert_eq!(0.75, scorer.similarity("apple iphone case", "apple phone case"));
+/// assert_eq!(0.25, scorer.similarity("apple iphone case", "samsung iphone case"));
+/// ```
+pub struct WeightedTokenJaccard<'t> {
+    tokenizer: &'t dyn Tokenizer,
+    weight: &'t dyn Fn(&str) -> f64,
+}
+
+impl<'t> WeightedTokenJaccard<'t> {
+    /// Creates a scorer comparing the tokens produced by `tokenizer`, each
+    /// weighted by `weight`.
+    pub fn new(tokenizer: &'t dyn Tokenizer, weight: &'t dyn Fn(&str) -> f64) -> Self {
+        WeightedTokenJaccard { tokenizer, weight }
+    }
+
+    /// Calculates the weighted Jaccard index between the token sets of both
+    /// strings. Strings without any token are only similar to each other.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        let a: HashSet<&str> = self.tokenizer.tokenize(a).into_iter().collect();
+        let b: HashSet<&str> = self.tokenizer.tokenize(b).into_iter().collect();
+        if a == b {
+            return 1.0;
+        }
+
+        let shared: f64 = a.intersection(&b).map(|token| (self.weight)(token)).sum();
+        let total: f64 = a.union(&b).map(|token| (self.weight)(token)).sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+        shared / total
+    }
+}
+
+impl SimilarityMetric for WeightedTokenJaccard<'_> {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.similarity(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jaccard_counts_repetitions() {
+        // "aaa" has two "aa" bigrams, "aa" only one
+        assert_delta!(0.5, jaccard("aaa", "aa"));
+    }
+
+    #[test]
+    fn jaccard_multibyte() {
+        assert_delta!(1.0 / 3.0, jaccard("öঙ香", "öঙa"));
+    }
+
+    #[test]
+    fn jaccard_single_chars() {
+        assert_delta!(1.0, jaccard("a", "a"));
+        assert_delta!(0.0, jaccard("a", "b"));
+    }
+
+    #[test]
+    fn jaccard_padded_short_strings() {
+        assert_delta!(0.0, jaccard_padded("a", "b", '#'));
+        assert_delta!(0.25, jaccard_padded("a", "ab", '#'));
+        assert_delta!(0.4, jaccard_padded("ab", "abc", '香'));
+        assert_delta!(0.0, jaccard_padded("", "a", '#'));
+    }
+
+    #[test]
+    fn token_jaccard_empty() {
+        assert_delta!(1.0, token_jaccard("", "  "));
+        assert_delta!(0.0, token_jaccard("", "word"));
+    }
+
+    #[test]
+    fn token_jaccard_ignores_repetitions() {
+        assert_delta!(1.0, token_jaccard("la la land", "land la"));
+        assert_delta!(
+            1.0 / 3.0,
+            TokenJaccard::default()
+                .compute_metric("a b", "b c")
+                .as_f64()
+        );
+    }
+
+    #[test]
+    fn weighted_token_jaccard() {
+        let uniform = |_: &str| 2.0;
+        let scorer = WeightedTokenJaccard::new(&WhitespaceTokenizer, &uniform);
+        assert_delta!(1.0, scorer.similarity("", ""));
+        assert_delta!(0.0, scorer.similarity("", "word"));
+        assert_delta!(
+            token_jaccard("new york city", "new york"),
+            scorer.similarity("new york city", "new york")
+        );
+
+        let nothing = |_: &str| 0.0;
+        let scorer = WeightedTokenJaccard::new(&WhitespaceTokenizer, &nothing);
+        assert_delta!(1.0, scorer.similarity("b a", "a b"));
+        assert_delta!(0.0, scorer.similarity("a b", "a c"));
+    }
+}
@@ -1,10 +1,53 @@
 use crate::algorithms::{Similarity, SimilarityMetric};
-use crate::utils::StringWrapper;
+use crate::utils::{LowercaseStringWrapper, StringWrapper};
 use std::cmp::{max, min};
+use std::collections::HashSet;
+
+/// How far apart two equal characters may be for Jaro to consider them a
+/// match.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SearchRange {
+    /// Half the length of the longer string minus one, as defined by Jaro.
+    #[default]
+    Standard,
+    /// A fixed number of positions, regardless of the lengths of the strings.
+    /// `Fixed(0)` only matches characters at the same position.
+    Fixed(usize),
+    /// The length of the longer string multiplied by a factor. The standard
+    /// range is close to `Proportional(0.5)`.
+    Proportional(f64),
+}
+
+impl SearchRange {
+    fn window(self, a_len: usize, b_len: usize) -> usize {
+        let len = max(a_len, b_len);
+        let window = match self {
+            SearchRange::Standard => (len / 2).saturating_sub(1),
+            SearchRange::Fixed(window) => window,
+            SearchRange::Proportional(factor) => (len as f64 * factor) as usize,
+        };
+        // a wider window matches the same characters
+        min(window, len)
+    }
+}
 
 /// Calculates the Jaro similarity between two sequences. The returned value
 /// is between 0.0 and 1.0 (higher value means more similar).
 fn generic_jaro<'a, 'b, Iter1, Iter2, Elem1, Elem2>(a: &'a Iter1, b: &'b Iter2) -> f64
+where
+    &'a Iter1: IntoIterator<Item = Elem1>,
+    &'b Iter2: IntoIterator<Item = Elem2>,
+    Elem1: PartialEq<Elem2>,
+{
+    generic_jaro_with_range(a, b, SearchRange::Standard)
+}
+
+/// Like `generic_jaro`, but characters are matched within `range`.
+fn generic_jaro_with_range<'a, 'b, Iter1, Iter2, Elem1, Elem2>(
+    a: &'a Iter1,
+    b: &'b Iter2,
+    range: SearchRange,
+) -> f64
 where
     &'a Iter1: IntoIterator<Item = Elem1>,
     &'b Iter2: IntoIterator<Item = Elem2>,
@@ -19,8 +62,7 @@ where
         return 0.0;
     }
 
-    let mut search_range = max(a_len, b_len) / 2;
-    search_range = search_range.saturating_sub(1);
+    let search_range = range.window(a_len, b_len);
 
     // combine memory allocations to reduce runtime
     let mut flags_memory = vec![false; a_len + b_len];
@@ -30,11 +72,7 @@ where
 
     for (i, a_elem) in a.into_iter().enumerate() {
         // prevent integer wrapping
-        let min_bound = if i > search_range {
-            i - search_range
-        } else {
-            0
-        };
+        let min_bound = i.saturating_sub(search_range);
 
         let max_bound = min(b_len, i + search_range + 1);
 
@@ -87,17 +125,32 @@ where
     &'b Iter2: IntoIterator<Item = Elem2>,
     Elem1: PartialEq<Elem2>,
 {
-    let sim = generic_jaro(a, b);
+    generic_jaro_winkler_with_config(a, b, &JaroWinklerConfig::new())
+}
 
-    if sim > 0.7 {
+/// Like `generic_jaro_winkler`, but with the boost and search range of
+/// `config`.
+fn generic_jaro_winkler_with_config<'a, 'b, Iter1, Iter2, Elem1, Elem2>(
+    a: &'a Iter1,
+    b: &'b Iter2,
+    config: &JaroWinklerConfig,
+) -> f64
+where
+    &'a Iter1: IntoIterator<Item = Elem1>,
+    &'b Iter2: IntoIterator<Item = Elem2>,
+    Elem1: PartialEq<Elem2>,
+{
+    let sim = generic_jaro_with_range(a, b, config.range);
+
+    if sim > config.boost_threshold {
         let prefix_length = a
             .into_iter()
-            .take(4)
+            .take(config.max_prefix)
             .zip(b)
             .take_while(|(a_elem, b_elem)| a_elem == b_elem)
             .count();
 
-        sim + 0.1 * prefix_length as f64 * (1.0 - sim)
+        (sim + config.prefix_weight * prefix_length as f64 * (1.0 - sim)).min(1.0)
     } else {
         sim
     }
@@ -128,8 +181,210 @@ pub fn jaro_winkler(a: &str, b: &str) -> f64 {
     generic_jaro_winkler(&StringWrapper(a), &StringWrapper(b))
 }
 
+/// Like [`jaro`], but takes the sequences by value, e.g. the output of a
+/// decoder or normalizer producing characters on the fly.
+///
+/// Unlike `levenshtein_iter`, this collects both sequences into vectors: the
+/// matching window depends on the length of both sequences, and characters
+/// are looked up by position within it. This still avoids building
+/// intermediate strings.
+///
+/// ```
+/// use fuzzt::algorithms::{jaro, jaro_iter};
+///
+/// let decoded = "MARTHA".chars().map(|c| c.to_ascii_lowercase());
+/// assert_eq!(jaro("martha", "marhta"), jaro_iter(decoded, "marhta".chars()));
+/// ```
+pub fn jaro_iter<Iter1, Iter2>(a: Iter1, b: Iter2) -> f64
+where
+    Iter1: IntoIterator,
+    Iter2: IntoIterator,
+    Iter1::Item: PartialEq<Iter2::Item>,
+{
+    let a: Vec<Iter1::Item> = a.into_iter().collect();
+    let b: Vec<Iter2::Item> = b.into_iter().collect();
+    generic_jaro(&a, &b)
+}
+
+/// Like [`jaro_winkler`], but takes the sequences by value. See [`jaro_iter`].
+///
+/// ```
+/// use fuzzt::algorithms::{jaro_winkler, jaro_winkler_iter};
+///
+/// let decoded = "MARTHA".chars().map(|c| c.to_ascii_lowercase());
+/// assert_eq!(
+///     jaro_winkler("martha", "marhta"),
+///     jaro_winkler_iter(decoded, "marhta".chars())
+/// );
+/// ```
+pub fn jaro_winkler_iter<Iter1, Iter2>(a: Iter1, b: Iter2) -> f64
+where
+    Iter1: IntoIterator,
+    Iter2: IntoIterator,
+    Iter1::Item: PartialEq<Iter2::Item>,
+{
+    let a: Vec<Iter1::Item> = a.into_iter().collect();
+    let b: Vec<Iter2::Item> = b.into_iter().collect();
+    generic_jaro_winkler(&a, &b)
+}
+
+/// Like `jaro`, but characters are compared case-insensitively. The strings
+/// are lowercased while they are iterated, without allocating copies.
+///
+/// ```
+/// use fuzzt::algorithms::jaro_ignore_case;
+///
+/// assert_eq!(1.0, jaro_ignore_case("MARTHA", "martha"));
+/// ```
+pub fn jaro_ignore_case(a: &str, b: &str) -> f64 {
+    generic_jaro(&LowercaseStringWrapper(a), &LowercaseStringWrapper(b))
+}
+
+/// Like `jaro_winkler`, but characters are compared case-insensitively.
+///
+/// ```
+/// use fuzzt::algorithms::jaro_winkler_ignore_case;
+///
+/// assert!((0.961 - jaro_winkler_ignore_case("MARTHA", "marhta")).abs() < 0.001);
+/// ```
+pub fn jaro_winkler_ignore_case(a: &str, b: &str) -> f64 {
+    generic_jaro_winkler(&LowercaseStringWrapper(a), &LowercaseStringWrapper(b))
+}
+
 pub struct Jaro;
 pub struct JaroWinkler;
+pub struct JaroIgnoreCase;
+pub struct JaroWinklerIgnoreCase;
+
+impl Jaro {
+    /// Returns a Jaro metric that matches characters within `range` instead
+    /// of the standard search range.
+    ///
+    /// A tighter range suits short codes, where the standard range lets
+    /// characters match across most of the string.
+    ///
+    /// ```
+    /// use fuzzt::algorithms::{jaro, Jaro, SearchRange};
+    ///
+    /// let strict = Jaro::with_search_range(SearchRange::Fixed(0));
+    /// assert!((jaro("1234", "1243") - 0.917).abs() < 0.001);
+    /// assert!((strict.jaro("1234", "1243") - 0.667).abs() < 0.001);
+    /// ```
+    pub fn with_search_range(range: SearchRange) -> JaroConfig {
+        JaroConfig { range }
+    }
+}
+
+/// A Jaro metric with a custom search range, created with
+/// [`Jaro::with_search_range`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct JaroConfig {
+    range: SearchRange,
+}
+
+impl JaroConfig {
+    /// Calculates the Jaro similarity between two strings within the
+    /// configured search range.
+    pub fn jaro(&self, a: &str, b: &str) -> f64 {
+        generic_jaro_with_range(&StringWrapper(a), &StringWrapper(b), self.range)
+    }
+}
+
+impl JaroWinkler {
+    /// Returns a Jaro-Winkler metric that boosts common prefixes of up to
+    /// `max_prefix` characters by `prefix_weight` per character, instead of
+    /// 0.1 per character for up to 4 characters.
+    ///
+    /// ```
+    /// use fuzzt::algorithms::{jaro_winkler, JaroWinkler};
+    ///
+    /// let sku = JaroWinkler::with_prefix(0.15, 6);
+    /// assert!(sku.jaro_winkler("AB-1234-X", "AB-1234-Y") > jaro_winkler("AB-1234-X", "AB-1234-Y"));
+    /// ```
+    pub fn with_prefix(prefix_weight: f64, max_prefix: usize) -> JaroWinklerConfig {
+        JaroWinklerConfig::new()
+            .with_prefix_weight(prefix_weight)
+            .with_max_prefix(max_prefix)
+    }
+}
+
+/// A Jaro-Winkler metric with custom parameters.
+///
+/// By default, prefixes of up to 4 characters are boosted by 0.1 per
+/// character when the Jaro similarity is above 0.7, as defined by Winkler.
+/// When `prefix_weight * max_prefix` is greater than 1.0, different strings
+/// with a long common prefix can score 1.0, since scores are capped at 1.0.
+///
+/// ```
+/// use fuzzt::algorithms::{jaro_winkler, JaroWinklerConfig};
+///
+/// let config = JaroWinklerConfig::new().with_prefix_weight(0.2).with_max_prefix(6);
+/// assert!((0.978 - config.jaro_winkler("martha", "marhta")).abs() < 0.001);
+/// assert_eq!(jaro_winkler("dwayne", "duane"), JaroWinklerConfig::default().jaro_winkler("dwayne", "duane"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JaroWinklerConfig {
+    range: SearchRange,
+    prefix_weight: f64,
+    max_prefix: usize,
+    boost_threshold: f64,
+}
+
+impl JaroWinklerConfig {
+    /// Creates a metric with the standard parameters.
+    pub fn new() -> Self {
+        JaroWinklerConfig {
+            range: SearchRange::Standard,
+            prefix_weight: 0.1,
+            max_prefix: 4,
+            boost_threshold: 0.7,
+        }
+    }
+
+    /// Sets how much each character of the common prefix boosts the score.
+    ///
+    /// Panics if `prefix_weight` is negative.
+    pub fn with_prefix_weight(mut self, prefix_weight: f64) -> Self {
+        assert!(
+            prefix_weight >= 0.0,
+            "the prefix weight must be non-negative"
+        );
+        self.prefix_weight = prefix_weight;
+        self
+    }
+
+    /// Sets the number of characters of the common prefix taken into
+    /// account.
+    pub fn with_max_prefix(mut self, max_prefix: usize) -> Self {
+        self.max_prefix = max_prefix;
+        self
+    }
+
+    /// Sets the Jaro similarity above which common prefixes are boosted.
+    pub fn with_boost_threshold(mut self, boost_threshold: f64) -> Self {
+        self.boost_threshold = boost_threshold;
+        self
+    }
+
+    /// Sets the range within which characters are matched.
+    pub fn with_search_range(mut self, range: SearchRange) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Calculates the Jaro-Winkler similarity between two strings with the
+    /// configured parameters.
+    pub fn jaro_winkler(&self, a: &str, b: &str) -> f64 {
+        generic_jaro_winkler_with_config(&StringWrapper(a), &StringWrapper(b), self)
+    }
+}
+
+impl Default for JaroWinklerConfig {
+    /// Uses the standard parameters.
+    fn default() -> Self {
+        JaroWinklerConfig::new()
+    }
+}
 
 impl SimilarityMetric for Jaro {
     fn compute_metric(&self, a: &str, b: &str) -> Similarity {
@@ -137,16 +392,389 @@ impl SimilarityMetric for Jaro {
     }
 }
 
+impl SimilarityMetric for JaroConfig {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.jaro(a, b))
+    }
+}
+
+impl SimilarityMetric for JaroWinklerConfig {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.jaro_winkler(a, b))
+    }
+}
+
 impl SimilarityMetric for JaroWinkler {
     fn compute_metric(&self, a: &str, b: &str) -> Similarity {
         Similarity::Float(jaro_winkler(a, b))
     }
 }
 
+impl SimilarityMetric for JaroIgnoreCase {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(jaro_ignore_case(a, b))
+    }
+}
+
+impl SimilarityMetric for JaroWinklerIgnoreCase {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(jaro_winkler_ignore_case(a, b))
+    }
+}
+
+// pairs of characters strcmp95 gives partial credit for, as they are often
+// confused in handwritten or spelled out records
+const WINKLER_SIMILAR_CHARACTERS: [(char, char); 39] = [
+    ('A', 'E'),
+    ('A', 'I'),
+    ('A', 'O'),
+    ('A', 'U'),
+    ('B', 'V'),
+    ('E', 'I'),
+    ('E', 'O'),
+    ('E', 'U'),
+    ('I', 'O'),
+    ('I', 'U'),
+    ('O', 'U'),
+    ('I', 'Y'),
+    ('E', 'Y'),
+    ('C', 'G'),
+    ('E', 'F'),
+    ('W', 'U'),
+    ('W', 'V'),
+    ('X', 'K'),
+    ('S', 'Z'),
+    ('X', 'S'),
+    ('Q', 'C'),
+    ('U', 'V'),
+    ('M', 'N'),
+    ('L', 'I'),
+    ('Q', 'O'),
+    ('P', 'R'),
+    ('I', 'J'),
+    ('2', 'Z'),
+    ('5', 'S'),
+    ('8', 'B'),
+    ('1', 'I'),
+    ('1', 'L'),
+    ('0', 'O'),
+    ('0', 'Q'),
+    ('C', 'K'),
+    ('G', 'J'),
+    ('E', ' '),
+    ('Y', ' '),
+    ('S', ' '),
+];
+
+/// Pairs of characters that [`Strcmp95`] gives partial credit for when they
+/// don't match exactly, such as `I` and `Y` or `C` and `K`.
+///
+/// Pairs are symmetric. Since `Strcmp95` compares strings in upper case by
+/// default, letters should usually be inserted in upper case.
+///
+/// ```
+/// use fuzzt::algorithms::SimilarCharacters;
+///
+/// let mut similar = SimilarCharacters::winkler();
+/// assert!(similar.contains('K', 'C'));
+/// assert!(!similar.contains('Ö', 'O'));
+/// similar.insert('Ö', 'O');
+/// assert!(similar.contains('O', 'Ö'));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimilarCharacters {
+    pairs: HashSet<(char, char)>,
+}
+
+impl SimilarCharacters {
+    /// Creates a table without pairs.
+    pub fn new() -> Self {
+        SimilarCharacters {
+            pairs: HashSet::new(),
+        }
+    }
+
+    /// The 39 pairs of Winkler's strcmp95, such as vowels, `M` and `N`, or
+    /// `0` and `O`.
+    pub fn winkler() -> Self {
+        let mut similar = SimilarCharacters::new();
+        for (a, b) in WINKLER_SIMILAR_CHARACTERS {
+            similar.insert(a, b);
+        }
+        similar
+    }
+
+    /// Gives partial credit to `a` and `b` when they are compared.
+    pub fn insert(&mut self, a: char, b: char) {
+        self.pairs.insert((a.min(b), a.max(b)));
+    }
+
+    /// Returns `true` if `a` and `b` are a pair of the table.
+    pub fn contains(&self, a: char, b: char) -> bool {
+        self.pairs.contains(&(a.min(b), a.max(b)))
+    }
+
+    /// Returns the number of pairs of the table.
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Returns `true` if the table has no pairs.
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+impl Default for SimilarCharacters {
+    /// The pairs of [`SimilarCharacters::winkler`].
+    fn default() -> Self {
+        SimilarCharacters::winkler()
+    }
+}
+
+/// The strcmp95 variant of Jaro-Winkler used by the U.S. Census Bureau for
+/// record linkage.
+/// See <https://www.census.gov/srd/papers/pdf/rr94-5.pdf>.
+///
+/// On top of the prefix boost of `jaro_winkler`, strcmp95 ignores leading and
+/// trailing spaces, compares characters in upper case, gives partial credit
+/// for commonly confused characters such as `O` and `0`, and raises the
+/// scores of long strings that agree on most of their characters. Each of
+/// these adjustments can be turned off; without them, strings of letters
+/// score the same as with `jaro_winkler_ignore_case`.
+///
+/// ```
+/// use fuzzt::algorithms::Strcmp95;
+///
+/// let strcmp95 = Strcmp95::new();
+/// assert!((0.9309 - strcmp95.similarity("DUNNINGHAM", "CUNNIGHAM")).abs() < 0.0001);
+///
+/// let plain = strcmp95.with_long_strings(false).with_similar_characters(false);
+/// assert!((0.8963 - plain.similarity("DUNNINGHAM", "CUNNIGHAM")).abs() < 0.0001);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Strcmp95 {
+    long_strings: bool,
+    similar_characters: bool,
+    ignore_case: bool,
+    // Winkler's pairs are looked up without allocating a table
+    table: Option<SimilarCharacters>,
+}
+
+impl Strcmp95 {
+    /// Creates a metric with all adjustments enabled.
+    pub fn new() -> Self {
+        Strcmp95 {
+            long_strings: true,
+            similar_characters: true,
+            ignore_case: true,
+            table: None,
+        }
+    }
+
+    /// Sets whether long strings agreeing on most characters get a higher
+    /// score, which isn't appropriate for fixed length fields such as phone
+    /// numbers.
+    pub fn with_long_strings(mut self, enabled: bool) -> Self {
+        self.long_strings = enabled;
+        self
+    }
+
+    /// Sets whether commonly confused characters get partial credit.
+    pub fn with_similar_characters(mut self, enabled: bool) -> Self {
+        self.similar_characters = enabled;
+        self
+    }
+
+    /// Gives partial credit to the pairs of `table` instead of Winkler's
+    /// pairs, and enables the adjustment.
+    ///
+    /// ```
+    /// use fuzzt::algorithms::{SimilarCharacters, Strcmp95};
+    ///
+    /// let mut table = SimilarCharacters::winkler();
+    /// table.insert('Ö', 'O');
+    /// let strcmp95 = Strcmp95::new().with_similar_table(table);
+    /// assert!(strcmp95.similarity("MÖLLER", "MOLLER") > Strcmp95::new().similarity("MÖLLER", "MOLLER"));
+    /// ```
+    pub fn with_similar_table(mut self, table: SimilarCharacters) -> Self {
+        self.table = Some(table);
+        self.similar_characters = true;
+        self
+    }
+
+    /// Sets whether the strings are compared in upper case. Partial credit is
+    /// only given to upper case letters, so it mostly applies to strings
+    /// compared in upper case.
+    pub fn with_ignore_case(mut self, enabled: bool) -> Self {
+        self.ignore_case = enabled;
+        self
+    }
+
+    fn similar(&self, a: char, b: char) -> bool {
+        match &self.table {
+            Some(table) => table.contains(a, b),
+            None => {
+                WINKLER_SIMILAR_CHARACTERS.contains(&(a, b))
+                    || WINKLER_SIMILAR_CHARACTERS.contains(&(b, a))
+            }
+        }
+    }
+
+    fn chars(&self, s: &str) -> Vec<char> {
+        let s = s.trim_matches(' ');
+        if !self.ignore_case {
+            return s.chars().collect();
+        }
+        s.chars()
+            .map(|c| {
+                let mut uppercase = c.to_uppercase();
+                match uppercase.len() {
+                    1 => uppercase.next().unwrap_or(c),
+                    _ => c,
+                }
+            })
+            .collect()
+    }
+
+    /// Calculates the strcmp95 similarity between two strings, between 0.0
+    /// and 1.0.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        let (a, b) = (self.chars(a), self.chars(b));
+        let (a_len, b_len) = (a.len(), b.len());
+        if a_len == 0 || b_len == 0 {
+            return 0.0;
+        }
+        let min_len = a_len.min(b_len);
+        let range = (a_len.max(b_len) / 2).saturating_sub(1);
+
+        #[derive(Clone, Copy, PartialEq)]
+        enum Flag {
+            Unmatched,
+            Matched,
+            Similar,
+        }
+        let mut a_flags = vec![Flag::Unmatched; a_len];
+        let mut b_flags = vec![Flag::Unmatched; b_len];
+        let mut matches = 0;
+        for (i, &a_char) in a.iter().enumerate() {
+            let high = (i + range).min(b_len - 1);
+            for j in i.saturating_sub(range)..=high {
+                if b_flags[j] != Flag::Matched && b[j] == a_char {
+                    a_flags[i] = Flag::Matched;
+                    b_flags[j] = Flag::Matched;
+                    matches += 1;
+                    break;
+                }
+            }
+        }
+        if matches == 0 {
+            return 0.0;
+        }
+
+        let b_matched = b
+            .iter()
+            .zip(&b_flags)
+            .filter(|(_, &flag)| flag == Flag::Matched)
+            .map(|(&c, _)| c);
+        let transpositions = a
+            .iter()
+            .zip(&a_flags)
+            .filter(|(_, &flag)| flag == Flag::Matched)
+            .zip(b_matched)
+            .filter(|((&a_char, _), b_char)| a_char != *b_char)
+            .count()
+            / 2;
+
+        let mut similar_weight = 0;
+        if self.similar_characters && min_len > matches {
+            for (i, &a_char) in a.iter().enumerate() {
+                if a_flags[i] != Flag::Unmatched {
+                    continue;
+                }
+                for (j, &b_char) in b.iter().enumerate() {
+                    if b_flags[j] == Flag::Unmatched && self.similar(a_char, b_char) {
+                        similar_weight += 3;
+                        b_flags[j] = Flag::Similar;
+                        break;
+                    }
+                }
+            }
+        }
+
+        let similar_matches = similar_weight as f64 / 10.0 + matches as f64;
+        let mut weight = (similar_matches / a_len as f64
+            + similar_matches / b_len as f64
+            + (matches - transpositions) as f64 / matches as f64)
+            / 3.0;
+
+        if weight > 0.7 {
+            let prefix = a
+                .iter()
+                .zip(&b)
+                .take(min_len.min(4))
+                .take_while(|(a_char, b_char)| a_char == b_char && !a_char.is_ascii_digit())
+                .count();
+            weight += prefix as f64 * 0.1 * (1.0 - weight);
+
+            if self.long_strings
+                && min_len > 4
+                && matches > prefix + 1
+                && 2 * matches >= min_len + prefix
+                && !a[0].is_ascii_digit()
+            {
+                weight += (1.0 - weight) * (matches - prefix - 1) as f64
+                    / (a_len + b_len - 2 * prefix + 2) as f64;
+            }
+        }
+        weight
+    }
+}
+
+impl Default for Strcmp95 {
+    /// Enables all adjustments.
+    fn default() -> Self {
+        Strcmp95::new()
+    }
+}
+
+impl SimilarityMetric for Strcmp95 {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.similarity(a, b))
+    }
+}
+
+/// Calculates the strcmp95 similarity between two strings with all
+/// adjustments enabled, see [`Strcmp95`].
+///
+/// ```
+/// use fuzzt::algorithms::strcmp95;
+///
+/// assert!((0.9886 - strcmp95("SHACKLEFORD", "shackelford")).abs() < 0.0001);
+/// ```
+pub fn strcmp95(a: &str, b: &str) -> f64 {
+    Strcmp95::new().similarity(a, b)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn jaro_iterators() {
+        assert_eq!(1.0, jaro_iter("".chars(), "".chars()));
+        assert_eq!(0.0, jaro_iter("".chars(), "jaro".chars()));
+        assert_delta!(
+            jaro("Friedrich Nietzsche", "Jean-Paul Sartre"),
+            jaro_iter("Friedrich Nietzsche".chars(), "Jean-Paul Sartre".chars())
+        );
+        assert_delta!(
+            jaro_winkler("cheeseburger", "cheese fries"),
+            jaro_winkler_iter("cheeseburger".chars(), "cheese fries".chars())
+        );
+        assert_delta!(1.0, jaro_iter([1, 2, 3], vec![1, 2, 3]));
+    }
+
     #[test]
     fn jaro_both_empty() {
         assert_eq!(1.0, jaro("", ""));
@@ -193,6 +821,27 @@ mod tests {
         assert_eq!(0.0, generic_jaro(&[1, 2], &[3, 4]));
     }
 
+    #[test]
+    fn jaro_search_range() {
+        assert_eq!(0.0, jaro("abc", "cab"));
+        let wide = Jaro::with_search_range(SearchRange::Fixed(2));
+        assert_delta!(0.889, wide.jaro("abc", "cab"), 0.001);
+        let proportional = Jaro::with_search_range(SearchRange::Proportional(0.7));
+        assert_delta!(0.889, proportional.jaro("abc", "cab"), 0.001);
+        for huge in [
+            SearchRange::Fixed(usize::MAX),
+            SearchRange::Proportional(1e300),
+            SearchRange::Proportional(f64::INFINITY),
+        ] {
+            let huge = Jaro::with_search_range(huge);
+            assert_delta!(0.889, huge.jaro("abc", "cab"), 0.001);
+        }
+        assert_eq!(
+            jaro("dixon", "dicksonx"),
+            JaroConfig::default().jaro("dixon", "dicksonx")
+        );
+    }
+
     #[test]
     fn jaro_diff_one_and_two() {
         assert_delta!(0.83, jaro("a", "ab"), 0.01);
@@ -307,4 +956,138 @@ mod tests {
             jaro_winkler("thequickbrownfoxjumpedoverx", "thequickbrownfoxjumpedovery")
         );
     }
+
+    #[test]
+    fn jaro_ignore_case_diff_short() {
+        assert_delta!(0.767, jaro_ignore_case("DIXON", "dicksonx"), 0.001);
+        assert_delta!(
+            jaro("dixon", "dicksonx"),
+            jaro_ignore_case("Dixon", "DicksonX")
+        );
+    }
+
+    #[test]
+    fn jaro_winkler_ignore_case_diff_short() {
+        assert_delta!(0.813, jaro_winkler_ignore_case("DIXON", "dicksonx"), 0.001);
+        assert_delta!(
+            1.0,
+            jaro_winkler_ignore_case("Jaro-Winkler", "jaro-winkler")
+        );
+    }
+
+    #[test]
+    fn strcmp95_census_names() {
+        let strcmp95 = Strcmp95::new();
+        let plain = strcmp95
+            .clone()
+            .with_long_strings(false)
+            .with_similar_characters(false);
+        // expected scores computed with the reference C implementation
+        for (a, b, full, jaro_winkler) in [
+            ("SHACKLEFORD", "SHACKELFORD", 0.9886, 0.9818),
+            ("DUNNINGHAM", "CUNNIGHAM", 0.9309, 0.8963),
+            ("NICHLESON", "NICHULSON", 0.9767, 0.9556),
+            ("JONES", "JOHNSON", 0.8738, 0.8324),
+            ("MASSEY", "MASSIE", 0.9533, 0.9333),
+            ("ABROMS", "ABRAMS", 0.9524, 0.9222),
+            ("HARDIN", "MARTINEZ", 0.7743, 0.7222),
+            ("ITMAN", "SMITH", 0.5067, 0.4667),
+            ("JERALDINE", "GERALDINE", 0.9663, 0.9259),
+            ("MARHTA", "MARTHA", 0.9708, 0.9611),
+            ("MICHELLE", "MICHAEL", 0.9444, 0.9214),
+            ("TANYA", "TONYA", 0.9328, 0.88),
+            ("DWAYNE", "DUANE", 0.8961, 0.84),
+            ("SEAN", "SUSAN", 0.8455, 0.805),
+            ("JON", "JOHN", 0.9333, 0.9333),
+            ("JON", "JAN", 0.86, 0.8),
+        ] {
+            assert_delta!(full, strcmp95.similarity(a, b), 1e-4);
+            assert_delta!(jaro_winkler, plain.similarity(a, b), 1e-4);
+            assert_delta!(jaro_winkler, jaro_winkler_ignore_case(a, b), 1e-4);
+        }
+    }
+
+    #[test]
+    fn strcmp95_options() {
+        assert_delta!(0.0, strcmp95("", "abc"));
+        assert_delta!(0.0, strcmp95("   ", "abc"));
+        assert_delta!(1.0, strcmp95("  martha ", "MARTHA"));
+        let no_credit = Strcmp95::new().with_similar_characters(false);
+        assert!(strcmp95("B0ND", "BOND") > no_credit.similarity("B0ND", "BOND"));
+        assert_delta!(
+            strcmp95("öঙ香", "öঙX香"),
+            no_credit.similarity("öঙ香", "öঙX香")
+        );
+        let case_sensitive = Strcmp95::new().with_ignore_case(false);
+        assert!(case_sensitive.similarity("martha", "MARTHA") < 0.1);
+        assert!(matches!(
+            Strcmp95::default().compute_metric("12345", "12345"),
+            Similarity::Float(r) if r == 1.0
+        ));
+    }
+
+    #[test]
+    fn strcmp95_similar_table() {
+        let winkler = SimilarCharacters::winkler();
+        assert_eq!(39, winkler.len());
+        assert!(winkler.contains('Y', 'I'));
+        assert!(!winkler.contains('y', 'i'));
+        assert!(SimilarCharacters::new().is_empty());
+
+        // the built-in pairs score like an explicit copy of the table
+        for (a, b) in [
+            ("DUNNINGHAM", "CUNNIGHAM"),
+            ("B0ND", "BOND"),
+            ("JON", "JAN"),
+        ] {
+            assert_delta!(
+                strcmp95(a, b),
+                Strcmp95::new()
+                    .with_similar_table(winkler.clone())
+                    .similarity(a, b)
+            );
+        }
+
+        let mut table = SimilarCharacters::new();
+        table.insert('香', 'X');
+        let custom = Strcmp95::new().with_similar_table(table);
+        assert!(custom.similarity("ABC香", "ABCX") > strcmp95("ABC香", "ABCX"));
+        // an empty table gives no partial credit
+        let empty = Strcmp95::new().with_similar_table(SimilarCharacters::new());
+        assert_delta!(
+            Strcmp95::new()
+                .with_similar_characters(false)
+                .similarity("B0ND", "BOND"),
+            empty.similarity("B0ND", "BOND")
+        );
+    }
+
+    #[test]
+    fn jaro_winkler_config() {
+        let config = JaroWinklerConfig::new()
+            .with_prefix_weight(0.15)
+            .with_max_prefix(6);
+        let sim = jaro("abcdefgh", "abcdefxy");
+        assert_delta!(
+            sim + 0.9 * (1.0 - sim),
+            config.jaro_winkler("abcdefgh", "abcdefxy")
+        );
+        // capped at 1.0
+        assert_delta!(
+            1.0,
+            JaroWinkler::with_prefix(0.25, 8).jaro_winkler("abcdefgh", "abcdefxy")
+        );
+
+        let no_boost = JaroWinklerConfig::new().with_boost_threshold(1.0);
+        assert_delta!(
+            jaro("martha", "marhta"),
+            no_boost.jaro_winkler("martha", "marhta")
+        );
+        let strict = JaroWinklerConfig::new().with_search_range(SearchRange::Fixed(0));
+        assert!(strict.jaro_winkler("1234", "1243") < jaro_winkler("1234", "1243"));
+        assert!(matches!(
+            JaroWinklerConfig::default().compute_metric("martha", "marhta"),
+            Similarity::Float(r) if r == jaro_winkler("martha", "marhta")
+        ));
+    }
 }
@@ -0,0 +1,238 @@
+use crate::algorithms::{Similarity, SimilarityMetric};
+use std::collections::HashMap;
+
+// how far each row of a standard keyboard is shifted to the right, in keys,
+// relative to the row of digits
+const ROW_OFFSETS: [f64; 4] = [0.0, 0.5, 0.75, 1.25];
+
+// keys at most this far apart are adjacent, which includes the diagonal
+// neighbours on staggered rows
+const ADJACENT_DISTANCE: f64 = 1.5;
+
+/// The physical positions of the keys of a keyboard, used to make typos
+/// between neighbouring keys cheaper.
+///
+/// Positions are measured in keys, and letters are looked up in lower case.
+///
+/// ```
+/// use fuzzt::algorithms::KeyboardLayout;
+///
+/// let qwerty = KeyboardLayout::qwerty();
+/// assert!(qwerty.adjacent('q', 'w'));
+/// assert!(qwerty.adjacent('S', 'z'));
+/// assert!(!qwerty.adjacent('q', 'p'));
+/// assert_eq!(Some(9.0), qwerty.key_distance('q', 'p'));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyboardLayout {
+    positions: HashMap<char, (f64, f64)>,
+}
+
+impl KeyboardLayout {
+    /// Creates a layout without keys.
+    pub fn new() -> Self {
+        KeyboardLayout::default()
+    }
+
+    /// Creates a layout from its rows of keys, starting with the row of
+    /// digits, each row staggered like on a standard keyboard.
+    pub fn from_rows(rows: &[&str]) -> Self {
+        let mut layout = KeyboardLayout::new();
+        for (row, keys) in rows.iter().enumerate() {
+            let offset = ROW_OFFSETS.get(row).copied().unwrap_or(0.0);
+            for (column, key) in keys.chars().enumerate() {
+                layout.insert(key, row as f64, column as f64 + offset);
+            }
+        }
+        layout
+    }
+
+    /// The US QWERTY layout.
+    pub fn qwerty() -> Self {
+        KeyboardLayout::from_rows(&["1234567890-=", "qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"])
+    }
+
+    /// The French AZERTY layout.
+    pub fn azerty() -> Self {
+        KeyboardLayout::from_rows(&["&é\"'(-è_çà)=", "azertyuiop^$", "qsdfghjklmù", "wxcvbn,;:!"])
+    }
+
+    /// Places `key` at the given row and column, replacing its previous
+    /// position.
+    pub fn insert(&mut self, key: char, row: f64, column: f64) {
+        for key in key.to_lowercase() {
+            self.positions.insert(key, (row, column));
+        }
+    }
+
+    fn position(&self, key: char) -> Option<(f64, f64)> {
+        let mut lowercase = key.to_lowercase();
+        let key = match lowercase.len() {
+            1 => lowercase.next().unwrap_or(key),
+            _ => key,
+        };
+        self.positions.get(&key).copied()
+    }
+
+    /// Returns the distance between two keys, or `None` if one of them isn't
+    /// part of the layout.
+    pub fn key_distance(&self, a: char, b: char) -> Option<f64> {
+        let (a_row, a_column) = self.position(a)?;
+        let (b_row, b_column) = self.position(b)?;
+        Some((a_row - b_row).hypot(a_column - b_column))
+    }
+
+    /// Returns `true` if both keys are different and next to each other.
+    pub fn adjacent(&self, a: char, b: char) -> bool {
+        self.key_distance(a, b)
+            .is_some_and(|distance| distance > 0.0 && distance <= ADJACENT_DISTANCE)
+    }
+}
+
+/// Calculates a Levenshtein distance where substituting a character with one
+/// on a neighbouring key costs less than other substitutions, since such typos
+/// are far more common.
+///
+/// Insertions, deletions and substitutions cost 1.0, except substitutions of
+/// adjacent keys, which cost `adjacent_cost`. Like the other metrics of the
+/// crate, characters that only differ in case are different, unless the
+/// metric is configured with [`KeyboardLevenshtein::with_ignore_case`].
+///
+/// ```
+/// use fuzzt::algorithms::{KeyboardLayout, KeyboardLevenshtein};
+///
+/// let qwerty = KeyboardLayout::qwerty();
+/// let keyboard = KeyboardLevenshtein::new(&qwerty, 0.5);
+/// assert_eq!(0.5, keyboard.distance("quick", "wuick"));
+/// assert_eq!(1.0, keyboard.distance("quick", "puick"));
+/// assert_eq!(0.9, keyboard.similarity("quick", "wuick"));
+/// assert_eq!(5.0, keyboard.distance("quick", "QUICK"));
+/// assert_eq!(0.0, keyboard.with_ignore_case(true).distance("quick", "QUICK"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct KeyboardLevenshtein<'k> {
+    layout: &'k KeyboardLayout,
+    adjacent_cost: f64,
+    ignore_case: bool,
+}
+
+impl<'k> KeyboardLevenshtein<'k> {
+    /// Creates a metric for typos made on `layout`.
+    ///
+    /// Panics if `adjacent_cost` isn't between 0.0 and 1.0.
+    pub fn new(layout: &'k KeyboardLayout, adjacent_cost: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&adjacent_cost),
+            "the cost of adjacent keys must be between 0.0 and 1.0"
+        );
+        KeyboardLevenshtein {
+            layout,
+            adjacent_cost,
+            ignore_case: false,
+        }
+    }
+
+    /// Sets whether characters that only differ in case are considered equal,
+    /// like a typo where shift was missed.
+    pub fn with_ignore_case(mut self, enabled: bool) -> Self {
+        self.ignore_case = enabled;
+        self
+    }
+
+    fn substitution_cost(&self, a: char, b: char) -> f64 {
+        if a == b || (self.ignore_case && a.to_lowercase().eq(b.to_lowercase())) {
+            0.0
+        } else if self.layout.adjacent(a, b) {
+            self.adjacent_cost
+        } else {
+            1.0
+        }
+    }
+
+    /// Calculates the weighted edit distance between both strings.
+    pub fn distance(&self, a: &str, b: &str) -> f64 {
+        let b_chars: Vec<char> = b.chars().collect();
+        let mut previous: Vec<f64> = (0..=b_chars.len()).map(|j| j as f64).collect();
+        let mut current = vec![0.0; b_chars.len() + 1];
+
+        for (i, a_char) in a.chars().enumerate() {
+            current[0] = (i + 1) as f64;
+            for (j, &b_char) in b_chars.iter().enumerate() {
+                current[j + 1] = (previous[j] + self.substitution_cost(a_char, b_char))
+                    .min(previous[j + 1] + 1.0)
+                    .min(current[j] + 1.0);
+            }
+            std::mem::swap(&mut previous, &mut current);
+        }
+        previous[b_chars.len()]
+    }
+
+    /// Calculates the distance normalized by the length of the longer string,
+    /// subtracted from 1.0. Empty strings are identical.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        let max_len = a.chars().count().max(b.chars().count());
+        if max_len == 0 {
+            return 1.0;
+        }
+        1.0 - self.distance(a, b) / max_len as f64
+    }
+}
+
+impl SimilarityMetric for KeyboardLevenshtein<'_> {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.similarity(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyboard_layouts() {
+        let qwerty = KeyboardLayout::qwerty();
+        let azerty = KeyboardLayout::azerty();
+        assert!(qwerty.adjacent('a', 's'));
+        assert!(qwerty.adjacent('g', 'y'));
+        assert!(!qwerty.adjacent('a', 'a'));
+        assert!(!qwerty.adjacent('a', 'x'));
+        assert!(azerty.adjacent('a', 'z'));
+        assert!(!azerty.adjacent('a', 'w'));
+        assert!(azerty.adjacent('é', 'z'));
+        assert!(!qwerty.adjacent('a', 'é'));
+        assert_eq!(None, qwerty.key_distance('a', '香'));
+
+        let mut custom = KeyboardLayout::new();
+        custom.insert('X', 0.0, 0.0);
+        custom.insert('y', 0.0, 1.0);
+        assert!(custom.adjacent('x', 'Y'));
+    }
+
+    #[test]
+    fn keyboard_levenshtein_costs() {
+        let qwerty = KeyboardLayout::qwerty();
+        let keyboard = KeyboardLevenshtein::new(&qwerty, 0.25);
+        assert_delta!(0.0, keyboard.distance("", ""));
+        assert_delta!(3.0, keyboard.distance("", "abc"));
+        assert_delta!(1.0, keyboard.distance("Hello", "hello"));
+        assert_delta!(
+            0.0,
+            keyboard.with_ignore_case(true).distance("Hello", "hello")
+        );
+        assert_delta!(0.5, keyboard.distance("hello", "jrllo"));
+        assert_delta!(2.0, keyboard.distance("hello", "hlelo"));
+        assert_delta!(1.0, keyboard.distance("öঙ香", "öঙ"));
+        assert!(matches!(
+            keyboard.compute_metric("", ""),
+            Similarity::Float(r) if r == 1.0
+        ));
+    }
+
+    #[test]
+    fn keyboard_levenshtein_unit_costs() {
+        let qwerty = KeyboardLayout::qwerty();
+        let keyboard = KeyboardLevenshtein::new(&qwerty, 1.0);
+        assert_delta!(3.0, keyboard.distance("kitten", "sitting"));
+        assert_delta!(4.0, keyboard.distance("hello", "world"));
+    }
+}
@@ -1,7 +1,8 @@
-use crate::utils::StringWrapper;
+use crate::utils::{with_scratch, AsciiWrapper, LowercaseStringWrapper, StringWrapper};
 
 use crate::algorithms::{Similarity, SimilarityMetric};
 use std::cmp::min;
+use std::collections::HashMap;
 
 /// Calculates the minimum number of insertions, deletions, and substitutions
 /// required to change one sequence into the other.
@@ -19,24 +20,28 @@ where
 {
     let b_len = b.into_iter().count();
 
-    let mut cache: Vec<usize> = (1..b_len + 1).collect();
+    with_scratch(b_len, |cache| {
+        for (j, distance) in cache.iter_mut().enumerate() {
+            *distance = j + 1;
+        }
 
-    let mut result = b_len;
+        let mut result = b_len;
 
-    for (i, a_elem) in a.into_iter().enumerate() {
-        result = i + 1;
-        let mut distance_b = i;
+        for (i, a_elem) in a.into_iter().enumerate() {
+            result = i + 1;
+            let mut distance_b = i;
 
-        for (j, b_elem) in b.into_iter().enumerate() {
-            let cost = usize::from(a_elem != b_elem);
-            let distance_a = distance_b + cost;
-            distance_b = cache[j];
-            result = min(result + 1, min(distance_a, distance_b + 1));
-            cache[j] = result;
+            for (j, b_elem) in b.into_iter().enumerate() {
+                let cost = usize::from(a_elem != b_elem);
+                let distance_a = distance_b + cost;
+                distance_b = cache[j];
+                result = min(result + 1, min(distance_a, distance_b + 1));
+                cache[j] = result;
+            }
         }
-    }
 
-    result
+        result
+    })
 }
 
 /// Calculates the minimum number of insertions, deletions, and substitutions
@@ -48,9 +53,215 @@ where
 /// assert_eq!(3, levenshtein("kitten", "sitting"));
 /// ```
 pub fn levenshtein(a: &str, b: &str) -> usize {
+    if a.len() > BIT_PARALLEL_MIN_LEN || b.len() > BIT_PARALLEL_MIN_LEN {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        return bit_parallel_levenshtein(&a, &b);
+    }
     generic_levenshtein(&StringWrapper(a), &StringWrapper(b))
 }
 
+/// Like [`levenshtein`], but configured at compile time, so hot loops don't
+/// branch on the configuration.
+///
+/// With `IGNORE_CASE`, characters are compared case-insensitively, like
+/// [`levenshtein_ignore_case`]. With `ASCII_ONLY`, the strings are compared
+/// byte by byte, which skips decoding UTF-8 and gives the same distance for
+/// ASCII strings; a non-ASCII character counts as several bytes, and only
+/// ASCII letters are case-folded.
+///
+/// ```
+/// use fuzzt::algorithms::levenshtein_with;
+///
+/// assert_eq!(3, levenshtein_with::<false, false>("kitten", "sitting"));
+/// assert_eq!(0, levenshtein_with::<true, true>("Kitten", "kITTEN"));
+/// assert_eq!(1, levenshtein_with::<true, false>("Café", "CAFÉS"));
+/// assert_eq!(2, levenshtein_with::<false, true>("é", "e"));
+/// ```
+pub fn levenshtein_with<const IGNORE_CASE: bool, const ASCII_ONLY: bool>(
+    a: &str,
+    b: &str,
+) -> usize {
+    if !ASCII_ONLY {
+        return if IGNORE_CASE {
+            levenshtein_ignore_case(a, b)
+        } else {
+            levenshtein(a, b)
+        };
+    }
+    let a = AsciiWrapper::<IGNORE_CASE>(a);
+    let b = AsciiWrapper::<IGNORE_CASE>(b);
+    if a.0.len() > BIT_PARALLEL_MIN_LEN || b.0.len() > BIT_PARALLEL_MIN_LEN {
+        let a: Vec<char> = a.into_iter().map(char::from).collect();
+        let b: Vec<char> = b.into_iter().map(char::from).collect();
+        return bit_parallel_levenshtein(&a, &b);
+    }
+    generic_levenshtein(&a, &b)
+}
+
+// strings longer than this many bytes are compared with the bit-parallel
+// algorithm, shorter ones don't pay off the cost of collecting their characters
+const BIT_PARALLEL_MIN_LEN: usize = 64;
+
+/// Calculates the Levenshtein distance with the bit-parallel algorithm of
+/// Myers (1999), in the blocked formulation of Hyyrö (2003).
+///
+/// The columns of the matrix are encoded as vectors of vertical deltas, 64
+/// cells per machine word, so the distance takes `O(⌈m/64⌉ * n)` operations
+/// instead of `O(m * n)`.
+fn bit_parallel_levenshtein(a: &[char], b: &[char]) -> usize {
+    // the shorter string is encoded in the bit vectors
+    let (pattern, text) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if pattern.is_empty() {
+        return text.len();
+    }
+
+    let words = (pattern.len() + 63) / 64;
+    let mut matches: HashMap<char, Vec<u64>> = HashMap::new();
+    for (i, &c) in pattern.iter().enumerate() {
+        matches.entry(c).or_insert_with(|| vec![0; words])[i / 64] |= 1 << (i % 64);
+    }
+    let no_matches = vec![0; words];
+    let last = 1 << ((pattern.len() - 1) % 64);
+
+    // vertical deltas of +1 and -1 of the current column
+    let mut positive = vec![!0u64; words];
+    let mut negative = vec![0u64; words];
+    let mut distance = pattern.len();
+
+    for c in text {
+        let eq = matches.get(c).unwrap_or(&no_matches);
+        // horizontal deltas of the row above the block, +1 on the first row
+        let (mut hp_carry, mut hn_carry) = (1u64, 0u64);
+        for word in 0..words {
+            let (vp, vn) = (positive[word], negative[word]);
+            let x = eq[word] | hn_carry;
+            let d0 = (((x & vp).wrapping_add(vp)) ^ vp) | x | vn;
+            let mut hp = vn | !(d0 | vp);
+            let mut hn = d0 & vp;
+
+            let (hp_in, hn_in) = (hp_carry, hn_carry);
+            if word + 1 < words {
+                hp_carry = hp >> 63;
+                hn_carry = hn >> 63;
+            } else {
+                hp_carry = u64::from(hp & last != 0);
+                hn_carry = u64::from(hn & last != 0);
+            }
+            hp = (hp << 1) | hp_in;
+            hn = (hn << 1) | hn_in;
+
+            positive[word] = hn | !(d0 | hp);
+            negative[word] = hp & d0;
+        }
+        // the carries of the last word are the deltas of the last row
+        distance = distance + hp_carry as usize - hn_carry as usize;
+    }
+    distance
+}
+
+/// Like [`generic_levenshtein`], but takes the sequences by value, e.g. the
+/// output of a decoder or normalizer producing characters on the fly.
+///
+/// The distance visits the second sequence once per element of the first, so
+/// only the second one is buffered into a vector, and the first one is
+/// consumed one element at a time. Pass the shorter sequence second to buffer
+/// fewer elements.
+///
+/// ```
+/// use fuzzt::algorithms::levenshtein_iter;
+///
+/// let decoded = "KITTEN".chars().map(|c| c.to_ascii_lowercase());
+/// assert_eq!(3, levenshtein_iter(decoded, "sitting".chars()));
+/// ```
+pub fn levenshtein_iter<Iter1, Iter2>(a: Iter1, b: Iter2) -> usize
+where
+    Iter1: IntoIterator,
+    Iter2: IntoIterator,
+    Iter1::Item: PartialEq<Iter2::Item>,
+{
+    let b: Vec<Iter2::Item> = b.into_iter().collect();
+
+    with_scratch(b.len(), |cache| {
+        for (j, distance) in cache.iter_mut().enumerate() {
+            *distance = j + 1;
+        }
+
+        let mut result = b.len();
+
+        for (i, a_elem) in a.into_iter().enumerate() {
+            result = i + 1;
+            let mut distance_b = i;
+
+            for (j, b_elem) in b.iter().enumerate() {
+                let cost = usize::from(a_elem != *b_elem);
+                let distance_a = distance_b + cost;
+                distance_b = cache[j];
+                result = min(result + 1, min(distance_a, distance_b + 1));
+                cache[j] = result;
+            }
+        }
+
+        result
+    })
+}
+
+/// The maximum length of the second argument of [`const_levenshtein`].
+pub const CONST_LEVENSHTEIN_MAX_LEN: usize = 64;
+
+/// Like [`levenshtein`], but compares bytes and can be evaluated at compile
+/// time, e.g. to build static tables of distances between ASCII literals.
+///
+/// Panics if `b` is longer than [`CONST_LEVENSHTEIN_MAX_LEN`] bytes, which
+/// fails the compilation when evaluated in a constant.
+///
+/// ```
+/// use fuzzt::algorithms::const_levenshtein;
+///
+/// const TYPOS: [(&str, usize); 2] = [
+///     ("recieve", const_levenshtein(b"recieve", b"receive")),
+///     ("seperate", const_levenshtein(b"seperate", b"separate")),
+/// ];
+/// assert_eq!(TYPOS, [("recieve", 2), ("seperate", 1)]);
+/// ```
+pub const fn const_levenshtein(a: &[u8], b: &[u8]) -> usize {
+    assert!(
+        b.len() <= CONST_LEVENSHTEIN_MAX_LEN,
+        "second argument of const_levenshtein is too long"
+    );
+    let mut cache = [0; CONST_LEVENSHTEIN_MAX_LEN];
+    let mut j = 0;
+    while j < b.len() {
+        cache[j] = j + 1;
+        j += 1;
+    }
+
+    let mut result = b.len();
+    let mut i = 0;
+    while i < a.len() {
+        result = i + 1;
+        let mut distance_b = i;
+
+        let mut j = 0;
+        while j < b.len() {
+            let distance_a = distance_b + (a[i] != b[j]) as usize;
+            distance_b = cache[j];
+            result += 1;
+            if distance_a < result {
+                result = distance_a;
+            }
+            if distance_b + 1 < result {
+                result = distance_b + 1;
+            }
+            cache[j] = result;
+            j += 1;
+        }
+        i += 1;
+    }
+
+    result
+}
+
 /// Calculates a normalized score of the Levenshtein algorithm between 0.0 and
 /// 1.0 (inclusive), where 1.0 means the strings are the same.
 ///
@@ -70,8 +281,238 @@ pub fn normalized_levenshtein(a: &str, b: &str) -> f64 {
     1.0 - (levenshtein(a, b) as f64) / (a.chars().count().max(b.chars().count()) as f64)
 }
 
+/// Like `levenshtein`, but characters are compared case-insensitively. The
+/// strings are lowercased while they are iterated, without allocating copies.
+///
+/// ```
+/// use fuzzt::algorithms::levenshtein_ignore_case;
+///
+/// assert_eq!(0, levenshtein_ignore_case("Kitten", "kITTEN"));
+/// assert_eq!(3, levenshtein_ignore_case("KITTEN", "sitting"));
+/// ```
+pub fn levenshtein_ignore_case(a: &str, b: &str) -> usize {
+    generic_levenshtein(&LowercaseStringWrapper(a), &LowercaseStringWrapper(b))
+}
+
+/// Like `normalized_levenshtein`, but characters are compared
+/// case-insensitively.
+///
+/// ```
+/// use fuzzt::algorithms::normalized_levenshtein_ignore_case;
+///
+/// assert!((normalized_levenshtein_ignore_case("Kitten", "SITTING") - 0.57142).abs() < 0.00001);
+/// ```
+pub fn normalized_levenshtein_ignore_case(a: &str, b: &str) -> f64 {
+    let a = LowercaseStringWrapper(a);
+    let b = LowercaseStringWrapper(b);
+    let a_len = a.into_iter().count();
+    let b_len = b.into_iter().count();
+    if a_len == 0 && b_len == 0 {
+        return 1.0;
+    }
+    1.0 - (generic_levenshtein(&a, &b) as f64) / (a_len.max(b_len) as f64)
+}
+
+/// Like [`levenshtein`], but returns `None` as soon as the distance is known
+/// to exceed `max_dist`.
+///
+/// Only the cells within `max_dist` of the diagonal of the matrix are
+/// computed, as in Ukkonen's cutoff, and common prefixes and suffixes are
+/// skipped. Looking up words within a small distance of a query is therefore
+/// much faster than with the full matrix.
+///
+/// ```
+/// use fuzzt::algorithms::levenshtein_bounded;
+///
+/// assert_eq!(Some(3), levenshtein_bounded("kitten", "sitting", 3));
+/// assert_eq!(None, levenshtein_bounded("kitten", "sitting", 2));
+/// assert_eq!(None, levenshtein_bounded("kit", "kitten", 2));
+/// ```
+pub fn levenshtein_bounded(a: &str, b: &str, max_dist: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max_dist {
+        return None;
+    }
+
+    // common prefixes and suffixes don't change the distance
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let (a, b) = (&a[prefix..], &b[prefix..]);
+    let suffix = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a, b) = (&a[..a.len() - suffix], &b[..b.len() - suffix]);
+    if a.is_empty() || b.is_empty() {
+        return Some(a.len().max(b.len()));
+    }
+    if max_dist <= MBLEVEN_MAX_DIST {
+        return mbleven_chars(a, b, max_dist);
+    }
+
+    // cells further than `max_dist` from the diagonal, or with a larger
+    // distance, are all stored as `beyond`
+    let max_dist = max_dist.min(a.len().max(b.len()));
+    let beyond = max_dist + 1;
+    let mut previous: Vec<usize> = (0..=b.len()).map(|j| j.min(beyond)).collect();
+    let mut current = vec![beyond; b.len() + 1];
+
+    for (i, a_elem) in a.iter().enumerate() {
+        let row = i + 1;
+        let low = row.saturating_sub(max_dist).max(1);
+        let high = (row + max_dist).min(b.len());
+
+        current[low - 1] = if low == 1 { row.min(beyond) } else { beyond };
+        let mut row_min = current[low - 1];
+        for j in low..=high {
+            let cost = usize::from(*a_elem != b[j - 1]);
+            current[j] = (previous[j - 1] + cost)
+                .min(previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(beyond);
+            row_min = row_min.min(current[j]);
+        }
+        if high < b.len() {
+            current[high + 1] = beyond;
+        }
+
+        // distances never decrease from one row to the next
+        if row_min > max_dist {
+            return None;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    let distance = previous[b.len()];
+    (distance <= max_dist).then_some(distance)
+}
+
+/// The largest distance [`mbleven`] enumerates the edits for.
+const MBLEVEN_MAX_DIST: usize = 3;
+
+// the edit sequences turning the longer string into the shorter one, for
+// each maximum distance `k` and length difference `d`, at index
+// `k * (k + 1) / 2 - 1 + d`. Each edit takes two bits, starting from the
+// lowest ones: 0b01 deletes a character of the longer string, 0b10 inserts
+// one, and 0b11 substitutes one.
+const MBLEVEN_MODELS: [&[u8]; 9] = [
+    &[0x03],
+    &[0x01],
+    &[0x0F, 0x09, 0x06],
+    &[0x0D, 0x07],
+    &[0x05],
+    &[0x3F, 0x27, 0x2D, 0x39, 0x36, 0x1E, 0x1B],
+    &[0x3D, 0x37, 0x1F, 0x25, 0x19, 0x16],
+    &[0x35, 0x1D, 0x17],
+    &[0x15],
+];
+
+// the number of edits turning `longer` into `shorter` when the mismatches
+// are resolved in the order of `model`, or more than the model allows if it
+// doesn't apply
+fn mbleven_model(longer: &[char], shorter: &[char], mut model: u8) -> usize {
+    let (mut i, mut j, mut edits) = (0, 0, 0);
+    while i < longer.len() && j < shorter.len() {
+        if longer[i] == shorter[j] {
+            i += 1;
+            j += 1;
+            continue;
+        }
+        if model == 0 {
+            break;
+        }
+        if model & 1 != 0 {
+            i += 1;
+        }
+        if model & 2 != 0 {
+            j += 1;
+        }
+        model >>= 2;
+        edits += 1;
+    }
+    edits + (longer.len() - i) + (shorter.len() - j)
+}
+
+fn mbleven_chars(a: &[char], b: &[char], max_dist: usize) -> Option<usize> {
+    let (longer, shorter) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    let length_difference = longer.len() - shorter.len();
+    if length_difference > max_dist {
+        return None;
+    }
+    if max_dist == 0 {
+        return (longer == shorter).then_some(0);
+    }
+    let models = MBLEVEN_MODELS[max_dist * (max_dist + 1) / 2 - 1 + length_difference];
+    let distance = models
+        .iter()
+        .map(|&model| mbleven_model(longer, shorter, model))
+        .min()
+        .unwrap_or(usize::MAX);
+    (distance <= max_dist).then_some(distance)
+}
+
+/// Like [`levenshtein_bounded`], but for a `max_dist` of at most 3, which it
+/// handles with the mbleven algorithm.
+///
+/// Rather than filling a matrix, mbleven tries every sequence of at most
+/// `max_dist` edits consistent with the length difference of the strings,
+/// each in a single pass over them. There are at most 7 sequences to try, so
+/// checking whether a word is within a few typos of a query is much faster
+/// than with the matrix. [`levenshtein_bounded`] switches to it for small
+/// distances, and larger values of `max_dist` are handled by it.
+///
+/// ```
+/// use fuzzt::algorithms::mbleven;
+///
+/// assert_eq!(Some(3), mbleven("kitten", "sitting", 3));
+/// assert_eq!(None, mbleven("kitten", "sitting", 2));
+/// assert_eq!(Some(1), mbleven("flaw", "flaws", 1));
+/// ```
+pub fn mbleven(a: &str, b: &str, max_dist: usize) -> Option<usize> {
+    if max_dist > MBLEVEN_MAX_DIST {
+        return levenshtein_bounded(a, b, max_dist);
+    }
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    mbleven_chars(&a, &b, max_dist)
+}
+
+/// Returns a lower bound on `levenshtein` computed only from the lengths of
+/// the strings: at least the difference in length has to be inserted.
+///
+/// ```
+/// use fuzzt::algorithms::{levenshtein, levenshtein_lower_bound};
+///
+/// assert_eq!(1, levenshtein_lower_bound("kitten", "sitting"));
+/// assert!(levenshtein_lower_bound("kitten", "sitting") <= levenshtein("kitten", "sitting"));
+/// ```
+pub fn levenshtein_lower_bound(a: &str, b: &str) -> usize {
+    a.chars().count().abs_diff(b.chars().count())
+}
+
+/// Returns an upper bound on `normalized_levenshtein` computed only from the
+/// lengths of the strings.
+///
+/// ```
+/// use fuzzt::algorithms::normalized_levenshtein_upper_bound;
+///
+/// assert!((normalized_levenshtein_upper_bound("kitten", "sitting") - 0.85714).abs() < 0.00001);
+/// ```
+pub fn normalized_levenshtein_upper_bound(a: &str, b: &str) -> f64 {
+    let a_len = a.chars().count();
+    let b_len = b.chars().count();
+    if a_len == 0 && b_len == 0 {
+        return 1.0;
+    }
+    1.0 - (a_len.abs_diff(b_len) as f64) / (a_len.max(b_len) as f64)
+}
+
 pub struct Levenshtein;
 pub struct NormalizedLevenshtein;
+pub struct LevenshteinIgnoreCase;
+pub struct NormalizedLevenshteinIgnoreCase;
 
 impl SimilarityMetric for Levenshtein {
     fn compute_metric(&self, a: &str, b: &str) -> Similarity {
@@ -85,10 +526,91 @@ impl SimilarityMetric for NormalizedLevenshtein {
     }
 }
 
+impl SimilarityMetric for LevenshteinIgnoreCase {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Usize(levenshtein_ignore_case(a, b))
+    }
+}
+
+impl SimilarityMetric for NormalizedLevenshteinIgnoreCase {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(normalized_levenshtein_ignore_case(a, b))
+    }
+}
+
+/// Scores strings like [`NormalizedLevenshtein`], but only if they are within
+/// `max_dist` edits of each other, and 0.0 otherwise.
+///
+/// Pairs whose lengths differ by more than `max_dist` are rejected without
+/// looking at their characters, and the others are compared like
+/// [`levenshtein_bounded`], so scoring a large dictionary with a small
+/// `max_dist` is much faster than scoring it with `NormalizedLevenshtein`.
+///
+/// ```
+/// use fuzzt::{algorithms::BoundedLevenshtein, get_top_n};
+///
+/// let scorer = BoundedLevenshtein::new(2);
+/// let words = ["kitten", "mitten", "sitting", "kitchen", "knitting"];
+/// let matches = get_top_n("kitten", &words, Some(0.0001), Some(5), None, Some(&scorer));
+/// assert_eq!(matches, ["kitten", "mitten", "kitchen"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundedLevenshtein {
+    max_dist: usize,
+}
+
+impl BoundedLevenshtein {
+    /// Creates a scorer rejecting the strings more than `max_dist` edits
+    /// apart.
+    pub fn new(max_dist: usize) -> Self {
+        BoundedLevenshtein { max_dist }
+    }
+
+    /// Calculates the normalized Levenshtein similarity of the strings, or
+    /// 0.0 if they are more than `max_dist` edits apart.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        let a_len = a.chars().count();
+        let b_len = b.chars().count();
+        if a_len.abs_diff(b_len) > self.max_dist {
+            return 0.0;
+        }
+        if a_len == 0 && b_len == 0 {
+            return 1.0;
+        }
+        match levenshtein_bounded(a, b, self.max_dist) {
+            Some(distance) => 1.0 - distance as f64 / a_len.max(b_len) as f64,
+            None => 0.0,
+        }
+    }
+}
+
+impl SimilarityMetric for BoundedLevenshtein {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.similarity(a, b))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn levenshtein_iterators() {
+        assert_eq!(0, levenshtein_iter("".chars(), "".chars()));
+        assert_eq!(3, levenshtein_iter("".chars(), "öঙ香".chars()));
+        assert_eq!(
+            levenshtein("kitten", "sitting"),
+            levenshtein_iter("kitten".chars(), "sitting".chars())
+        );
+        assert_eq!(2, levenshtein_iter([1, 2, 3], vec![1, 3, 4]));
+        assert_eq!(3, levenshtein_iter("abc".chars(), "".chars()));
+        // the first sequence is consumed once, one element at a time
+        let mut consumed = 0;
+        let stream = "sitting".chars().inspect(|_| consumed += 1);
+        assert_eq!(3, levenshtein_iter(stream, "kitten".chars()));
+        assert_eq!(7, consumed);
+    }
+
     #[test]
     fn levenshtein_empty() {
         assert_eq!(0, levenshtein("", ""));
@@ -122,6 +644,23 @@ mod tests {
         assert_eq!(37, levenshtein(a, b));
     }
 
+    #[test]
+    fn const_levenshtein_matches_levenshtein() {
+        const DISTANCE: usize = const_levenshtein(b"kitten", b"sitting");
+        assert_eq!(levenshtein("kitten", "sitting"), DISTANCE);
+        assert_eq!(0, const_levenshtein(b"", b""));
+        assert_eq!(5, const_levenshtein(b"hello", b""));
+        assert_eq!(7, const_levenshtein(b"", b"sitting"));
+        let long = [b'a'; CONST_LEVENSHTEIN_MAX_LEN];
+        assert_eq!(1, const_levenshtein(&long[1..], &long));
+    }
+
+    #[test]
+    #[should_panic]
+    fn const_levenshtein_too_long() {
+        const_levenshtein(b"a", &[b'a'; CONST_LEVENSHTEIN_MAX_LEN + 1]);
+    }
+
     #[test]
     fn levenshtein_first_empty() {
         assert_eq!(7, levenshtein("", "sitting"));
@@ -156,4 +695,163 @@ mod tests {
     fn normalized_levenshtein_identical_strings() {
         assert_delta!(1.0, normalized_levenshtein("identical", "identical"));
     }
+
+    #[test]
+    fn levenshtein_ignore_case_same() {
+        assert_eq!(0, levenshtein_ignore_case("LeVenShtein", "levenshtein"));
+    }
+
+    #[test]
+    fn levenshtein_ignore_case_multibyte() {
+        assert_eq!(0, levenshtein_ignore_case("ÖSTERREICH", "österreich"));
+        assert_eq!(1, levenshtein_ignore_case("ÖSTERREICH", "osterreich"));
+    }
+
+    #[test]
+    fn levenshtein_ignore_case_expanding_lowercase() {
+        // 'İ' lowercases to two characters
+        assert_eq!(0, levenshtein_ignore_case("İ", "i\u{307}"));
+        assert_delta!(1.0, normalized_levenshtein_ignore_case("İ", "i\u{307}"));
+    }
+
+    #[test]
+    fn normalized_levenshtein_ignore_case_empty() {
+        assert_delta!(1.0, normalized_levenshtein_ignore_case("", ""));
+        assert_delta!(0.0, normalized_levenshtein_ignore_case("", "ABC"));
+    }
+
+    #[test]
+    fn levenshtein_with_configurations() {
+        let long_a = "The Quick Brown Fox ".repeat(5);
+        let long_b = "the quick brown box ".repeat(5);
+        for (a, b) in [("", ""), ("Kitten", "sitTING"), (&long_a[..], &long_b[..])] {
+            assert_eq!(levenshtein(a, b), levenshtein_with::<false, false>(a, b));
+            assert_eq!(levenshtein(a, b), levenshtein_with::<false, true>(a, b));
+            assert_eq!(
+                levenshtein_ignore_case(a, b),
+                levenshtein_with::<true, false>(a, b)
+            );
+            assert_eq!(
+                levenshtein_ignore_case(a, b),
+                levenshtein_with::<true, true>(a, b)
+            );
+        }
+        assert_eq!(4, levenshtein_with::<true, true>("long", "öö"));
+        assert_eq!(1, levenshtein_with::<true, true>("É", "é"));
+        assert_eq!(0, levenshtein_with::<true, false>("É", "é"));
+    }
+
+    #[test]
+    fn levenshtein_bounded_matches_full_matrix() {
+        let pairs = [
+            ("", ""),
+            ("", "abc"),
+            ("abc", ""),
+            ("kitten", "sitting"),
+            ("öঙ香", "abcde"),
+            ("flaw", "lawn"),
+            ("saturday", "sunday"),
+            ("abcdefgh", "hgfedcba"),
+            ("prefix-abc-suffix", "prefix-xbz-suffix"),
+        ];
+        for (a, b) in pairs {
+            let distance = levenshtein(a, b);
+            for max_dist in 0..=distance + 2 {
+                let expected = (distance <= max_dist).then_some(distance);
+                assert_eq!(
+                    expected,
+                    levenshtein_bounded(a, b, max_dist),
+                    "{a} {b} {max_dist}"
+                );
+            }
+            assert_eq!(Some(distance), levenshtein_bounded(a, b, usize::MAX));
+        }
+    }
+
+    #[test]
+    fn mbleven_matches_full_matrix() {
+        let words = [
+            "", "a", "ab", "ba", "abc", "acb", "bca", "abcd", "abdc", "xabc", "abxc", "kitten",
+            "sitting", "mitten", "kitchen", "öঙ香", "ö香",
+        ];
+        for a in words {
+            for b in words {
+                let distance = levenshtein(a, b);
+                for max_dist in 0..=4 {
+                    let expected = (distance <= max_dist).then_some(distance);
+                    assert_eq!(expected, mbleven(a, b, max_dist), "{a} {b} {max_dist}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bounded_levenshtein_scorer() {
+        let scorer = BoundedLevenshtein::new(1);
+        assert_delta!(1.0, scorer.similarity("", ""));
+        assert_delta!(0.0, scorer.similarity("", "ab"));
+        assert_delta!(0.5, scorer.similarity("a", "ab"));
+        assert_delta!(0.0, scorer.similarity("flaw", "lawn"));
+        assert_delta!(
+            normalized_levenshtein("kitten", "mitten"),
+            scorer.similarity("kitten", "mitten")
+        );
+    }
+
+    #[test]
+    fn bit_parallel_matches_full_matrix() {
+        // strings around the block boundaries from a small alphabet, so they
+        // share many characters
+        let mut state: u32 = 7;
+        let mut random_string = |len: usize| -> Vec<char> {
+            (0..len)
+                .map(|_| {
+                    state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                    ['a', 'b', 'c', 'ö', '香'][(state >> 16) as usize % 5]
+                })
+                .collect()
+        };
+        for (a_len, b_len) in [
+            (0, 70),
+            (1, 1),
+            (63, 64),
+            (64, 64),
+            (65, 30),
+            (128, 129),
+            (200, 150),
+            (300, 310),
+        ] {
+            let a = random_string(a_len);
+            let b = random_string(b_len);
+            assert_eq!(
+                generic_levenshtein(&a, &b),
+                bit_parallel_levenshtein(&a, &b),
+                "{a_len} {b_len}"
+            );
+        }
+    }
+
+    #[test]
+    fn levenshtein_long_strings() {
+        let a = "The quick brown fox jumps over the lazy dog. ".repeat(8);
+        let b = a.replace("fox", "cat").replace("lazy", "lazy old");
+        assert_eq!(56, levenshtein(&a, &b));
+        assert_eq!(levenshtein(&a, &b), levenshtein(&b, &a));
+        assert_eq!(a.len(), levenshtein(&a, ""));
+        assert_eq!(0, levenshtein(&a, &a));
+    }
+
+    #[test]
+    fn levenshtein_bounds() {
+        let pairs = [
+            ("", ""),
+            ("", "abc"),
+            ("kitten", "sitting"),
+            ("öঙ香", "abcde"),
+        ];
+        for (a, b) in pairs {
+            assert!(levenshtein_lower_bound(a, b) <= levenshtein(a, b));
+            assert!(normalized_levenshtein(a, b) <= normalized_levenshtein_upper_bound(a, b));
+        }
+    }
 }
@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+
+// the DFA state without any prefix within the maximum distance
+const DEAD: usize = 0;
+
+/// A state of a [`LevenshteinAutomaton`], reached after reading a prefix of a
+/// candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AutomatonState(usize);
+
+/// A deterministic automaton accepting the strings within `max_dist`
+/// Levenshtein edits of a query.
+///
+/// The automaton is built once per query, after which each candidate is
+/// accepted or rejected in a single pass over its characters, without
+/// computing the full distance matrix. Candidates are rejected as soon as
+/// none of their extensions can be within the distance, so most candidates
+/// of a dictionary are rejected after a few characters.
+///
+/// States are the rows of the Levenshtein matrix truncated to the entries
+/// within `max_dist`, following Jacobs' construction, and every state is
+/// computed up front. Characters absent from the query all behave the same,
+/// so the number of states only depends on the query and `max_dist`.
+///
+/// ```
+/// use fuzzt::algorithms::LevenshteinAutomaton;
+///
+/// let automaton = LevenshteinAutomaton::new("kitten", 2);
+/// assert!(automaton.accepts("sitten"));
+/// assert!(!automaton.accepts("sitting"));
+/// assert_eq!(Some(1), automaton.distance("kittens"));
+///
+/// let dictionary = ["bitten", "kitchen", "kitten", "mitten", "written"];
+/// assert_eq!(automaton.filter(&dictionary), ["bitten", "kitchen", "kitten", "mitten", "written"]);
+/// assert_eq!(LevenshteinAutomaton::new("kitten", 1).filter(&dictionary), ["bitten", "kitten", "mitten"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevenshteinAutomaton {
+    // index of each character of the query in the transition table, the
+    // other characters using the last index
+    symbols: HashMap<char, usize>,
+    // transitions[state][symbol]
+    transitions: Vec<Vec<usize>>,
+    // the distance between the query and the strings reaching each state, if
+    // it is within the maximum distance
+    distances: Vec<Option<usize>>,
+    start: usize,
+}
+
+// the entries of a row of the Levenshtein matrix within the maximum distance,
+// as (query index, distance) pairs with increasing indices
+type Row = Vec<(usize, usize)>;
+
+fn step(query: &[char], max_dist: usize, row: &Row, c: Option<char>) -> Row {
+    let mut next: Row = vec![];
+    if let Some(&(0, distance)) = row.first() {
+        if distance < max_dist {
+            next.push((0, distance + 1));
+        }
+    }
+    for (j, &(i, distance)) in row.iter().enumerate() {
+        if i == query.len() {
+            break;
+        }
+        let mut distance = distance + usize::from(c != Some(query[i]));
+        if let Some(&(last, last_distance)) = next.last() {
+            if last == i {
+                distance = distance.min(last_distance + 1);
+            }
+        }
+        if let Some(&(after, after_distance)) = row.get(j + 1) {
+            if after == i + 1 {
+                distance = distance.min(after_distance + 1);
+            }
+        }
+        if distance <= max_dist {
+            next.push((i + 1, distance));
+        }
+    }
+    next
+}
+
+impl LevenshteinAutomaton {
+    /// Builds the automaton accepting the strings within `max_dist` edits of
+    /// `query`.
+    ///
+    /// The number of states grows quickly with `max_dist`, which should stay
+    /// small, typically up to 3.
+    pub fn new(query: &str, max_dist: usize) -> Self {
+        let query: Vec<char> = query.chars().collect();
+        let mut symbols: HashMap<char, usize> = HashMap::new();
+        let mut alphabet: Vec<Option<char>> = vec![];
+        for &c in &query {
+            symbols.entry(c).or_insert_with(|| {
+                alphabet.push(Some(c));
+                alphabet.len() - 1
+            });
+        }
+        // any character absent from the query
+        alphabet.push(None);
+
+        let mut states: HashMap<Row, usize> = HashMap::new();
+        let mut rows: Vec<Row> = vec![];
+        let mut add_state = |row: Row, rows: &mut Vec<Row>| -> usize {
+            *states.entry(row.clone()).or_insert_with(|| {
+                rows.push(row);
+                rows.len() - 1
+            })
+        };
+        add_state(vec![], &mut rows);
+        let first: Row = (0..=query.len().min(max_dist)).map(|i| (i, i)).collect();
+        let start = add_state(first, &mut rows);
+
+        let mut transitions: Vec<Vec<usize>> = vec![];
+        let mut state = 0;
+        while state < rows.len() {
+            let row = rows[state].clone();
+            let targets = alphabet
+                .iter()
+                .map(|&c| add_state(step(&query, max_dist, &row, c), &mut rows))
+                .collect();
+            transitions.push(targets);
+            state += 1;
+        }
+
+        let distances = rows
+            .iter()
+            .map(|row| match row.last() {
+                Some(&(i, distance)) if i == query.len() => Some(distance),
+                _ => None,
+            })
+            .collect();
+
+        LevenshteinAutomaton {
+            symbols,
+            transitions,
+            distances,
+            start,
+        }
+    }
+
+    /// Returns the number of states of the automaton.
+    pub fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Returns `true` if the automaton has no states, which never happens
+    /// since it always has a start state.
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// Returns the state before any character is read.
+    pub fn start(&self) -> AutomatonState {
+        AutomatonState(self.start)
+    }
+
+    /// Returns the state reached by reading `c` in `state`.
+    pub fn step(&self, state: AutomatonState, c: char) -> AutomatonState {
+        let symbol = self.symbols.get(&c).copied().unwrap_or(self.symbols.len());
+        AutomatonState(self.transitions[state.0][symbol])
+    }
+
+    /// Returns the distance between the query and the strings reaching
+    /// `state`, if it is within the maximum distance.
+    pub fn state_distance(&self, state: AutomatonState) -> Option<usize> {
+        self.distances[state.0]
+    }
+
+    /// Returns `true` if some string starting with the characters read so far
+    /// can be within the maximum distance. Once this is `false`, the
+    /// candidate and all of its extensions can be skipped.
+    pub fn can_match(&self, state: AutomatonState) -> bool {
+        state.0 != DEAD
+    }
+
+    /// Returns the Levenshtein distance between the query and `candidate`, or
+    /// `None` if it exceeds the maximum distance.
+    pub fn distance(&self, candidate: &str) -> Option<usize> {
+        let mut state = self.start();
+        for c in candidate.chars() {
+            state = self.step(state, c);
+            if !self.can_match(state) {
+                return None;
+            }
+        }
+        self.state_distance(state)
+    }
+
+    /// Returns `true` if `candidate` is within the maximum distance of the
+    /// query.
+    pub fn accepts(&self, candidate: &str) -> bool {
+        self.distance(candidate).is_some()
+    }
+
+    /// Returns the words within the maximum distance of the query, in order.
+    ///
+    /// The states of the prefix shared with the previous word are reused, so
+    /// sorted dictionaries, whose consecutive words share long prefixes, are
+    /// filtered much faster than by running the automaton on each word.
+    pub fn filter<'a, S>(&self, words: &'a [S]) -> Vec<&'a str>
+    where
+        S: AsRef<str>,
+    {
+        let mut matches = vec![];
+        // the characters of the previous word and the states after each of
+        // them, the first state being the start state
+        let mut previous: Vec<char> = vec![];
+        let mut states = vec![self.start()];
+        for word in words {
+            let word = word.as_ref();
+            let mut shared = 0;
+            let mut chars = word.chars().peekable();
+            while let Some(&c) = chars.peek() {
+                if previous.get(shared) != Some(&c) {
+                    break;
+                }
+                chars.next();
+                shared += 1;
+            }
+            previous.truncate(shared);
+            states.truncate(shared + 1);
+
+            let mut state = states[shared];
+            for c in chars {
+                if !self.can_match(state) {
+                    break;
+                }
+                state = self.step(state, c);
+                previous.push(c);
+                states.push(state);
+            }
+            // words left unread lead to the dead state
+            if self.state_distance(state).is_some() {
+                matches.push(word);
+            }
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::levenshtein;
+
+    #[test]
+    fn automaton_matches_levenshtein() {
+        let words = [
+            "", "a", "ab", "abc", "acb", "bac", "abcd", "xabc", "öঙ香", "öঙ", "kitten", "sitting",
+            "kitchen", "mitten", "aaaa",
+        ];
+        for query in ["", "abc", "öঙ香", "kitten", "aa"] {
+            for max_dist in 0..=3 {
+                let automaton = LevenshteinAutomaton::new(query, max_dist);
+                for word in words {
+                    let distance = levenshtein(query, word);
+                    let expected = (distance <= max_dist).then_some(distance);
+                    assert_eq!(
+                        expected,
+                        automaton.distance(word),
+                        "{query} {word} {max_dist}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn automaton_filters_dictionaries() {
+        let automaton = LevenshteinAutomaton::new("abc", 1);
+        let mut words = vec![
+            "ab", "abc", "abcd", "abcde", "abd", "b", "bbc", "xyz", "zabc",
+        ];
+        assert_eq!(
+            automaton.filter(&words),
+            ["ab", "abc", "abcd", "abd", "bbc", "zabc"]
+        );
+        // the result doesn't depend on the order of the words
+        words.reverse();
+        assert_eq!(
+            automaton.filter(&words),
+            ["zabc", "bbc", "abd", "abcd", "abc", "ab"]
+        );
+        let owned: Vec<String> = vec!["abc".to_string(), "abc".to_string()];
+        assert_eq!(automaton.filter(&owned), ["abc", "abc"]);
+    }
+
+    #[test]
+    fn automaton_states() {
+        let automaton = LevenshteinAutomaton::new("ab", 0);
+        assert!(!automaton.is_empty());
+        let state = automaton.step(automaton.start(), 'a');
+        assert!(automaton.can_match(state));
+        assert_eq!(None, automaton.state_distance(state));
+        let state = automaton.step(state, 'b');
+        assert_eq!(Some(0), automaton.state_distance(state));
+        let state = automaton.step(state, 'b');
+        assert!(!automaton.can_match(state));
+        assert!(!automaton.can_match(automaton.step(state, 'a')));
+    }
+}
@@ -14,6 +14,28 @@ macro_rules! assert_delta {
     };
 }
 
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(feature = "cache")]
+pub use cache::CachedMetric;
+
+#[cfg(feature = "confusion")]
+pub mod confusion;
+#[cfg(feature = "confusion")]
+pub use confusion::ConfusionMatrix;
+
+#[cfg(feature = "containment")]
+pub mod containment;
+#[cfg(feature = "containment")]
+pub use containment::{
+    containment, containment_ngrams, Containment, ContainmentDirection, FuzzyTokenContainment,
+};
+
+#[cfg(feature = "cosine")]
+pub mod cosine;
+#[cfg(feature = "cosine")]
+pub use cosine::{cosine, cosine_ngrams, Cosine};
+
 #[cfg(feature = "damerau_levenshtein")]
 pub mod damerau_levenshtein;
 #[cfg(feature = "damerau_levenshtein")]
@@ -22,24 +44,91 @@ pub use damerau_levenshtein::{
     DamerauLevenshtein, NormalizedDamerauLevenshtein,
 };
 
+#[cfg(feature = "fingerprint")]
+pub mod fingerprint;
+#[cfg(feature = "fingerprint")]
+pub use fingerprint::{fingerprint_similarity, Fingerprint, FingerprintStream, Fingerprinter};
+
+#[cfg(feature = "fingerprint")]
+pub mod winnowing;
+#[cfg(feature = "fingerprint")]
+pub use winnowing::{SharedSpan, Winnower, WinnowingFingerprint};
+
 pub mod gestalt;
-pub use gestalt::{sequence_matcher, SequenceMatcher};
+pub use gestalt::{quick_ratio, real_quick_ratio, sequence_matcher, SequenceMatcher};
+
+#[cfg(feature = "gotoh")]
+pub mod gotoh;
+#[cfg(feature = "gotoh")]
+pub use gotoh::{gotoh, Gotoh};
+
+pub mod guard;
+pub use guard::{LengthDamping, LengthGuard, OversizedInput};
+
+pub mod ngram_profile;
+pub use ngram_profile::NGramProfile;
 
 #[cfg(feature = "hamming")]
 pub mod hamming;
 #[cfg(feature = "hamming")]
-pub use hamming::{hamming, Hamming};
+pub use hamming::{
+    const_hamming, hamming, hamming_iter, hamming_with_policy, normalized_hamming,
+    normalized_hamming_with_policy, Hamming, LengthPolicy, NormalizedHamming,
+};
+
+#[cfg(feature = "jaccard")]
+pub mod jaccard;
+#[cfg(feature = "jaccard")]
+pub use jaccard::{
+    jaccard, jaccard_padded, token_jaccard, Jaccard, PaddedJaccard, TokenJaccard,
+    WeightedTokenJaccard,
+};
 
 #[cfg(feature = "jaro")]
 pub mod jaro;
 #[cfg(feature = "jaro")]
-pub use jaro::{jaro, jaro_winkler, Jaro, JaroWinkler};
+pub use jaro::{
+    jaro, jaro_ignore_case, jaro_iter, jaro_winkler, jaro_winkler_ignore_case, jaro_winkler_iter,
+    strcmp95, Jaro, JaroConfig, JaroIgnoreCase, JaroWinkler, JaroWinklerConfig,
+    JaroWinklerIgnoreCase, SearchRange, SimilarCharacters, Strcmp95,
+};
+
+#[cfg(feature = "keyboard")]
+pub mod keyboard;
+#[cfg(feature = "keyboard")]
+pub use keyboard::{KeyboardLayout, KeyboardLevenshtein};
 
 #[cfg(feature = "levenshtein")]
 pub mod levenshtein;
 #[cfg(feature = "levenshtein")]
 pub use levenshtein::{
-    generic_levenshtein, levenshtein, normalized_levenshtein, Levenshtein, NormalizedLevenshtein,
+    const_levenshtein, generic_levenshtein, levenshtein, levenshtein_bounded,
+    levenshtein_ignore_case, levenshtein_iter, levenshtein_lower_bound, levenshtein_with, mbleven,
+    normalized_levenshtein, normalized_levenshtein_ignore_case, normalized_levenshtein_upper_bound,
+    BoundedLevenshtein, Levenshtein, LevenshteinIgnoreCase, NormalizedLevenshtein,
+    NormalizedLevenshteinIgnoreCase, CONST_LEVENSHTEIN_MAX_LEN,
+};
+
+#[cfg(feature = "levenshtein")]
+pub mod levenshtein_automaton;
+#[cfg(feature = "levenshtein")]
+pub use levenshtein_automaton::{AutomatonState, LevenshteinAutomaton};
+
+#[cfg(feature = "fzf")]
+pub mod fzf;
+#[cfg(feature = "fzf")]
+pub use fzf::{fzf_match, Fzf, FzfMatch};
+
+#[cfg(feature = "monge_elkan")]
+pub mod monge_elkan;
+#[cfg(feature = "monge_elkan")]
+pub use monge_elkan::MongeElkan;
+
+#[cfg(feature = "ngram")]
+pub mod ngram;
+#[cfg(feature = "ngram")]
+pub use ngram::{
+    ngram_distance, ngram_similarity, positional_qgram_distance, KondrakNGram, PositionalQGram,
 };
 
 #[cfg(feature = "optimal_string_alignment")]
@@ -47,16 +136,136 @@ pub mod optimal_string_alignment;
 #[cfg(feature = "optimal_string_alignment")]
 pub use optimal_string_alignment::{osa_distance, OSADistance};
 
+#[cfg(feature = "palette")]
+pub mod palette;
+#[cfg(feature = "palette")]
+pub use palette::{palette_match, CommandPalette, PaletteMatch};
+
+#[cfg(feature = "phonetic")]
+pub mod phonetic;
+#[cfg(feature = "phonetic")]
+pub use phonetic::{
+    double_metaphone, refined_soundex, soundex, DoubleMetaphone, MetaphoneCodes, PhoneticHybrid,
+    RefinedSoundex, Soundex,
+};
+
+#[cfg(feature = "shingle")]
+pub mod shingle;
+#[cfg(feature = "shingle")]
+pub use shingle::{shingle_containment, shingle_jaccard, ShingleMeasure, ShingleSimilarity};
+
+#[cfg(feature = "sift4")]
+pub mod sift4;
+#[cfg(feature = "sift4")]
+pub use sift4::{sift4, Sift4};
+
 #[cfg(feature = "sorensen_dice")]
 pub mod sorensen_dice;
 #[cfg(feature = "sorensen_dice")]
-pub use sorensen_dice::{sorensen_dice, SorensenDice};
+pub use sorensen_dice::{
+    sorensen_dice, sorensen_dice_padded, sorensen_dice_upper_bound, PaddedSorensenDice,
+    SorensenDice,
+};
 
+#[cfg(feature = "tfidf")]
+pub mod tfidf;
+#[cfg(feature = "tfidf")]
+pub use tfidf::{TfIdfTerms, TfIdfVector, TfIdfVectorizer};
+
+/// The result of comparing two strings with a [`SimilarityMetric`].
+///
+/// Metrics returning `Usize` are distances, where 0 means the strings are
+/// identical and larger values mean they are more different. Metrics returning
+/// `Float` are similarities, usually between 0.0 and 1.0, where larger values
+/// mean the strings are more similar.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Similarity {
     Usize(usize),
     Float(f64),
 }
 
+impl Similarity {
+    /// Returns the raw value of the result, without taking its kind into
+    /// account.
+    ///
+    /// ```
+    /// use fuzzt::algorithms::Similarity;
+    ///
+    /// assert_eq!(3.0, Similarity::Usize(3).as_f64());
+    /// assert_eq!(0.5, Similarity::Float(0.5).as_f64());
+    /// ```
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Similarity::Usize(r) => r as f64,
+            Similarity::Float(r) => r,
+        }
+    }
+
+    /// Returns `true` if the result is a distance, i.e. lower is better.
+    pub fn is_distance(&self) -> bool {
+        matches!(self, Similarity::Usize(_))
+    }
+
+    /// Turns the result into a similarity, where 1.0 means the strings are
+    /// identical.
+    ///
+    /// Distances are divided by `max_len`, usually the length of the longer
+    /// string, and subtracted from 1.0, saturating at 0.0. Similarities are
+    /// returned as is.
+    ///
+    /// ```
+    /// use fuzzt::algorithms::{Levenshtein, SimilarityMetric};
+    ///
+    /// let distance = Levenshtein.compute_metric("kitten", "sitting");
+    /// assert!((distance.normalize(7) - 0.57142).abs() < 0.00001);
+    /// ```
+    pub fn normalize(&self, max_len: usize) -> f64 {
+        match *self {
+            Similarity::Usize(_) if max_len == 0 => 1.0,
+            Similarity::Usize(r) => (1.0 - r as f64 / max_len as f64).max(0.0),
+            Similarity::Float(r) => r,
+        }
+    }
+}
+
+/// Results of the same kind are ordered by their value. Distances and
+/// similarities can't be compared with each other.
+///
+/// ```
+/// use fuzzt::algorithms::Similarity;
+///
+/// assert!(Similarity::Usize(1) < Similarity::Usize(2));
+/// assert!(Similarity::Float(0.9) > Similarity::Float(0.5));
+/// assert_eq!(None, Similarity::Usize(1).partial_cmp(&Similarity::Float(1.0)));
+/// ```
+impl PartialOrd for Similarity {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (Similarity::Usize(a), Similarity::Usize(b)) => a.partial_cmp(b),
+            (Similarity::Float(a), Similarity::Float(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+impl From<usize> for Similarity {
+    fn from(distance: usize) -> Self {
+        Similarity::Usize(distance)
+    }
+}
+
+impl From<f64> for Similarity {
+    fn from(similarity: f64) -> Self {
+        Similarity::Float(similarity)
+    }
+}
+
+impl From<Similarity> for f64 {
+    fn from(similarity: Similarity) -> Self {
+        similarity.as_f64()
+    }
+}
+
 pub trait SimilarityMetric {
     // The smaller, the more similar 2 strings are.
     fn compute_metric(&self, a: &str, b: &str) -> Similarity;
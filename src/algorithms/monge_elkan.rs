@@ -0,0 +1,112 @@
+use crate::algorithms::{Similarity, SimilarityMetric};
+use crate::processors::Tokenizer;
+
+/// Scores multi-word strings by matching every token of the first string with
+/// its most similar token in the second string.
+/// See <https://www.aaai.org/Papers/KDD/1996/KDD96-044.pdf>.
+///
+/// The score is the average, over the tokens of `a`, of the best `inner`
+/// score against the tokens of `b`. This tolerates both reordered words and
+/// typos within words, which makes it well suited to names of people and
+/// companies. Distances are normalized by the length of the longer token, so
+/// any metric can be used as `inner`. Strings without any token are only
+/// similar to each other.
+///
+/// ```
+/// use fuzzt::algorithms::{MongeElkan, NormalizedLevenshtein};
+/// use fuzzt::processors::WhitespaceTokenizer;
+///
+/// let scorer = MongeElkan::new(&NormalizedLevenshtein, &WhitespaceTokenizer);
+/// let score = scorer.similarity("paul johnson", "johson paule");
+/// assert!((score - (0.8 + 6.0 / 7.0) / 2.0).abs() < 0.00001);
+/// ```
+pub struct MongeElkan<'m> {
+    inner: &'m dyn SimilarityMetric,
+    tokenizer: &'m dyn Tokenizer,
+}
+
+impl<'m> MongeElkan<'m> {
+    /// Creates a scorer that compares the tokens produced by `tokenizer` with
+    /// `inner`.
+    pub fn new(inner: &'m dyn SimilarityMetric, tokenizer: &'m dyn Tokenizer) -> Self {
+        MongeElkan { inner, tokenizer }
+    }
+
+    fn score(&self, a: &str, b: &str) -> f64 {
+        let max_len = a.chars().count().max(b.chars().count());
+        self.inner.compute_metric(a, b).normalize(max_len)
+    }
+
+    /// Calculates the average best score of the tokens of `a` against the
+    /// tokens of `b`. The score is asymmetric: extra tokens in `b` aren't
+    /// penalized.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        let a_tokens = self.tokenizer.tokenize(a);
+        let b_tokens = self.tokenizer.tokenize(b);
+        if a_tokens.is_empty() && b_tokens.is_empty() {
+            return 1.0;
+        }
+        if a_tokens.is_empty() || b_tokens.is_empty() {
+            return 0.0;
+        }
+
+        let total: f64 = a_tokens
+            .iter()
+            .map(|a_token| {
+                b_tokens
+                    .iter()
+                    .map(|b_token| self.score(a_token, b_token))
+                    .fold(0.0, f64::max)
+            })
+            .sum();
+        total / a_tokens.len() as f64
+    }
+
+    /// Like `similarity`, but averages both directions, so extra tokens on
+    /// either side lower the score.
+    pub fn symmetric_similarity(&self, a: &str, b: &str) -> f64 {
+        (self.similarity(a, b) + self.similarity(b, a)) / 2.0
+    }
+}
+
+impl SimilarityMetric for MongeElkan<'_> {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.similarity(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{Levenshtein, SequenceMatcher};
+    use crate::processors::{WhitespaceTokenizer, WordTokenizer};
+
+    #[test]
+    fn monge_elkan_empty() {
+        let scorer = MongeElkan::new(&SequenceMatcher, &WhitespaceTokenizer);
+        assert_delta!(1.0, scorer.similarity("", " "));
+        assert_delta!(0.0, scorer.similarity("", "word"));
+        assert_delta!(0.0, scorer.similarity("word", ""));
+    }
+
+    #[test]
+    fn monge_elkan_normalizes_distances() {
+        let scorer = MongeElkan::new(&Levenshtein, &WordTokenizer);
+        assert_delta!(
+            (0.8 + 6.0 / 7.0) / 2.0,
+            scorer.similarity("Paul Johnson", "Johson, Paule")
+        );
+        assert_delta!(1.0, scorer.similarity("john smith", "smith john"));
+    }
+
+    #[test]
+    fn monge_elkan_asymmetry() {
+        let scorer = MongeElkan::new(&SequenceMatcher, &WhitespaceTokenizer);
+        assert_delta!(1.0, scorer.similarity("acme", "acme corporation"));
+        assert_delta!(0.5, scorer.similarity("acme corporation", "acme"), 0.2);
+        assert_delta!(
+            (1.0 + scorer.similarity("acme corporation", "acme")) / 2.0,
+            scorer.symmetric_similarity("acme", "acme corporation")
+        );
+    }
+}
@@ -0,0 +1,361 @@
+use crate::algorithms::{Similarity, SimilarityMetric};
+
+/// Compares strings with the n-gram similarity and distance of Kondrak
+/// (2005), "N-Gram Similarity and Distance".
+///
+/// Both strings are split into overlapping n-grams, which are aligned with
+/// the dynamic programming of the edit distance. Unlike the metrics counting
+/// shared n-grams, such as [`crate::algorithms::sorensen_dice`], the order of
+/// the n-grams matters, and n-grams that only partially match count
+/// partially: two n-grams score the fraction of their positions holding the
+/// same character. With `n` set to 1, the distance is the normalized
+/// Levenshtein distance and the similarity the length of the longest common
+/// subsequence divided by the length of the longer string.
+///
+/// By default each string is prefixed with `n - 1` padding positions, so its
+/// first characters are part of as many n-grams as the others and each
+/// character starts an n-gram. Positions where both n-grams hold padding
+/// don't count.
+///
+/// ```
+/// use fuzzt::algorithms::KondrakNGram;
+///
+/// let bigrams = KondrakNGram::new(2);
+/// assert_eq!(0.5, bigrams.similarity("dixon", "dicksonx"));
+/// assert_eq!(0.5, bigrams.distance("dixon", "dicksonx"));
+/// let unpadded = KondrakNGram::new(2).with_padding(false);
+/// assert_eq!(3.0 / 7.0, unpadded.similarity("dixon", "dicksonx"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KondrakNGram {
+    n: usize,
+    padded: bool,
+}
+
+impl KondrakNGram {
+    /// Creates a metric comparing n-grams of `n` characters, with padding.
+    ///
+    /// Panics if `n` is 0.
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0, "n-grams have at least one character");
+        KondrakNGram { n, padded: true }
+    }
+
+    /// Sets whether the strings are prefixed with `n - 1` padding positions.
+    /// Without padding, a string shorter than `n` is a single, shorter
+    /// n-gram.
+    pub fn with_padding(mut self, padded: bool) -> Self {
+        self.padded = padded;
+        self
+    }
+
+    // the characters of `s`, `None` standing for padding
+    fn positions(&self, s: &str) -> Vec<Option<char>> {
+        let padding = if self.padded { self.n - 1 } else { 0 };
+        let mut positions = vec![None; padding];
+        positions.extend(s.chars().map(Some));
+        positions
+    }
+
+    // the number of n-grams of the string with `positions`
+    fn count(&self, positions: &[Option<char>]) -> usize {
+        (positions.len() + 1).saturating_sub(self.n).max(1)
+    }
+
+    // the fraction of the positions that differ between the n-grams starting
+    // at `i` in `a` and `j` in `b`, not counting shared padding
+    fn cost(&self, a: &[Option<char>], i: usize, b: &[Option<char>], j: usize) -> f64 {
+        let mut differences = 0;
+        let mut compared = 0;
+        for u in 0..self.n {
+            let x = a.get(i + u).copied().flatten();
+            let y = b.get(j + u).copied().flatten();
+            if x != y {
+                differences += 1;
+                compared += 1;
+            } else if x.is_some() {
+                compared += 1;
+            }
+        }
+        f64::from(differences) / f64::from(compared)
+    }
+
+    /// Calculates the n-gram similarity of both strings, between 0.0 and 1.0.
+    /// Two empty strings have a similarity of 1.0.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        if a.is_empty() || b.is_empty() {
+            return if a.is_empty() && b.is_empty() {
+                1.0
+            } else {
+                0.0
+            };
+        }
+        let a = self.positions(a);
+        let b = self.positions(b);
+        let (a_count, b_count) = (self.count(&a), self.count(&b));
+        let mut prev = vec![0.0; b_count + 1];
+        let mut curr = vec![0.0; b_count + 1];
+        for i in 0..a_count {
+            for j in 0..b_count {
+                let aligned = prev[j] + 1.0 - self.cost(&a, i, &b, j);
+                curr[j + 1] = aligned.max(prev[j + 1]).max(curr[j]);
+            }
+            std::mem::swap(&mut prev, &mut curr);
+        }
+        prev[b_count] / a_count.max(b_count) as f64
+    }
+
+    /// Calculates the n-gram distance of both strings, between 0.0 and 1.0.
+    /// Two empty strings have a distance of 0.0.
+    pub fn distance(&self, a: &str, b: &str) -> f64 {
+        if a.is_empty() || b.is_empty() {
+            return if a.is_empty() && b.is_empty() {
+                0.0
+            } else {
+                1.0
+            };
+        }
+        let a = self.positions(a);
+        let b = self.positions(b);
+        let (a_count, b_count) = (self.count(&a), self.count(&b));
+        let mut prev: Vec<f64> = (0..=b_count).map(|j| j as f64).collect();
+        let mut curr = vec![0.0; b_count + 1];
+        for i in 0..a_count {
+            curr[0] = (i + 1) as f64;
+            for j in 0..b_count {
+                let aligned = prev[j] + self.cost(&a, i, &b, j);
+                curr[j + 1] = aligned.min(prev[j + 1] + 1.0).min(curr[j] + 1.0);
+            }
+            std::mem::swap(&mut prev, &mut curr);
+        }
+        prev[b_count] / a_count.max(b_count) as f64
+    }
+}
+
+impl Default for KondrakNGram {
+    /// Compares padded bigrams, the BI-SIM and BI-DIST measures of Kondrak.
+    fn default() -> Self {
+        KondrakNGram::new(2)
+    }
+}
+
+impl SimilarityMetric for KondrakNGram {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.similarity(a, b))
+    }
+}
+
+/// Calculates the n-gram similarity of Kondrak (2005) with padded n-grams of
+/// `n` characters, see [`KondrakNGram`].
+///
+/// Panics if `n` is 0.
+///
+/// ```
+/// use fuzzt::algorithms::ngram_similarity;
+///
+/// assert_eq!(1.0, ngram_similarity("martha", "martha", 2));
+/// assert_eq!(4.0 / 6.0, ngram_similarity("martha", "marhta", 2));
+/// assert_eq!(0.0, ngram_similarity("six", "ten", 2));
+/// ```
+pub fn ngram_similarity(a: &str, b: &str, n: usize) -> f64 {
+    KondrakNGram::new(n).similarity(a, b)
+}
+
+/// Calculates the n-gram distance of Kondrak (2005) with padded n-grams of
+/// `n` characters, see [`KondrakNGram`].
+///
+/// Panics if `n` is 0.
+///
+/// ```
+/// use fuzzt::algorithms::ngram_distance;
+///
+/// assert_eq!(0.0, ngram_distance("martha", "martha", 2));
+/// assert_eq!(2.0 / 6.0, ngram_distance("martha", "marhta", 2));
+/// assert_eq!(0.75, ngram_distance("natural", "contrary", 2));
+/// ```
+pub fn ngram_distance(a: &str, b: &str, n: usize) -> f64 {
+    KondrakNGram::new(n).distance(a, b)
+}
+
+/// Compares strings by their positional q-grams: the sequences of `q`
+/// consecutive characters they contain, along with their position. See
+/// Gravano et al. (2001), "Approximate String Joins in a Database (Almost)
+/// for Free".
+///
+/// Two q-grams only match if they are equal and their positions differ by at
+/// most `window`, and the distance is the number of q-grams of both strings
+/// left without a match. Plain q-gram profiles, which ignore positions, can't
+/// tell apart strings whose parts are rearranged, as long as they contain the
+/// same q-grams; a small window can. Strings are padded with `q - 1`
+/// positions at both ends, so a string of `n` characters has `n + q - 1`
+/// q-grams, and the empty string has none.
+///
+/// With a window of `k` insertions or deletions, every q-gram of two strings
+/// at edit distance `k` or less that isn't affected by an edit is matched,
+/// which makes the distance a cheap filter for edit distance queries.
+///
+/// ```
+/// use fuzzt::algorithms::PositionalQGram;
+///
+/// // both strings have the same bigrams, in a different order
+/// assert_eq!(0, PositionalQGram::new(2, 100).distance("aaabac", "aabaac"));
+/// assert_eq!(2, PositionalQGram::new(2, 1).distance("aaabac", "aabaac"));
+/// assert_eq!(6, PositionalQGram::new(2, 0).distance("aaabac", "aabaac"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PositionalQGram {
+    q: usize,
+    window: usize,
+}
+
+impl PositionalQGram {
+    /// Creates a metric comparing q-grams of `q` characters whose positions
+    /// differ by at most `window`.
+    ///
+    /// Panics if `q` is 0.
+    pub fn new(q: usize, window: usize) -> Self {
+        assert!(q > 0, "q-grams have at least one character");
+        PositionalQGram { q, window }
+    }
+
+    // the padded q-grams of `s` along with their position, sorted
+    fn qgrams(&self, s: &str) -> Vec<(Vec<Option<char>>, usize)> {
+        if s.is_empty() {
+            return vec![];
+        }
+        let mut positions = vec![None; self.q - 1];
+        positions.extend(s.chars().map(Some));
+        positions.extend(vec![None; self.q - 1]);
+        let mut qgrams: Vec<(Vec<Option<char>>, usize)> = positions
+            .windows(self.q)
+            .enumerate()
+            .map(|(i, qgram)| (qgram.to_vec(), i))
+            .collect();
+        qgrams.sort_unstable();
+        qgrams
+    }
+
+    /// Calculates the number of q-grams of both strings without a match.
+    pub fn distance(&self, a: &str, b: &str) -> usize {
+        let a = self.qgrams(a);
+        let b = self.qgrams(b);
+        // the occurrences of each q-gram are sorted by position, so matching
+        // them greedily matches as many as possible
+        let mut matched = 0;
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            let ((a_qgram, a_pos), (b_qgram, b_pos)) = (&a[i], &b[j]);
+            if a_qgram == b_qgram && a_pos.abs_diff(*b_pos) <= self.window {
+                matched += 1;
+                i += 1;
+                j += 1;
+            } else if a[i] < b[j] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        a.len() + b.len() - 2 * matched
+    }
+}
+
+impl Default for PositionalQGram {
+    /// Compares bigrams whose positions differ by at most 2.
+    fn default() -> Self {
+        PositionalQGram::new(2, 2)
+    }
+}
+
+impl SimilarityMetric for PositionalQGram {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Usize(self.distance(a, b))
+    }
+}
+
+/// Calculates the positional q-gram distance between two strings, with
+/// q-grams of `q` characters whose positions differ by at most `window`, see
+/// [`PositionalQGram`].
+///
+/// Panics if `q` is 0.
+///
+/// ```
+/// use fuzzt::algorithms::positional_qgram_distance;
+///
+/// assert_eq!(8, positional_qgram_distance("john smith", "smith john", 2, 100));
+/// assert_eq!(22, positional_qgram_distance("john smith", "smith john", 2, 2));
+/// ```
+pub fn positional_qgram_distance(a: &str, b: &str, q: usize, window: usize) -> usize {
+    PositionalQGram::new(q, window).distance(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::levenshtein;
+
+    #[test]
+    fn ngram_measures() {
+        // similarities of padded bigrams, as 1 - distance and as similarity
+        let cases = [
+            ("al", "al", 1.0, 1.0),
+            ("b", "a", 0.0, 0.0),
+            ("martha", "marhta", 0.6667, 0.6667),
+            ("jones", "johnson", 0.4286, 0.4286),
+            ("natural", "contrary", 0.25, 0.375),
+            ("abcvwxyz", "cabvwxyz", 0.625, 0.75),
+            ("dwayne", "duane", 0.5833, 0.5833),
+            ("öঙ香", "öঙ", 0.6667, 0.6667),
+        ];
+        for (a, b, distance, similarity) in cases {
+            assert_delta!(distance, 1.0 - ngram_distance(a, b, 2), 0.0001);
+            assert_delta!(similarity, ngram_similarity(a, b, 2), 0.0001);
+            assert_delta!(similarity, ngram_similarity(b, a, 2), 0.0001);
+        }
+        assert_eq!(1.0, ngram_similarity("", "", 3));
+        assert_eq!(1.0, ngram_distance("", "abc", 3));
+    }
+
+    #[test]
+    fn unigrams_are_edit_distance() {
+        let unigrams = KondrakNGram::new(1);
+        for (a, b) in [("kitten", "sitting"), ("abcd", "dcba"), ("öঙ香", "香o")] {
+            let longest = a.chars().count().max(b.chars().count()) as f64;
+            assert_delta!(levenshtein(a, b) as f64, unigrams.distance(a, b) * longest);
+        }
+        // "ittn" is the longest common subsequence
+        assert_delta!(4.0 / 7.0, unigrams.similarity("kitten", "sitting"));
+    }
+
+    #[test]
+    fn unpadded_short_strings() {
+        let trigrams = KondrakNGram::new(3).with_padding(false);
+        assert_eq!(1.0, trigrams.similarity("ab", "ab"));
+        assert_eq!(0.5, trigrams.similarity("ab", "ac"));
+        assert_delta!(1.0 / 3.0, trigrams.distance("abc", "abd"));
+    }
+
+    #[test]
+    fn positional_qgrams() {
+        let cases = [
+            ("aaabac", "aabaac", 2, 1, 2),
+            ("kitten", "sitting", 2, 0, 11),
+            ("kitten", "sitting", 2, 1, 11),
+            ("öঙ香", "öঙ", 2, 1, 3),
+            ("abc", "abc", 3, 0, 0),
+            ("", "ab", 2, 1, 3),
+            ("", "", 2, 1, 0),
+        ];
+        for (a, b, q, window, distance) in cases {
+            assert_eq!(
+                distance,
+                positional_qgram_distance(a, b, q, window),
+                "{a} {b}"
+            );
+            assert_eq!(
+                distance,
+                positional_qgram_distance(b, a, q, window),
+                "{a} {b}"
+            );
+        }
+    }
+}
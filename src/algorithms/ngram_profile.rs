@@ -0,0 +1,395 @@
+use crate::utils::FuzztError;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+// the largest total of a deserialized profile, so that adding up the totals of
+// two profiles never overflows
+const MAX_TOTAL: usize = usize::MAX / 2;
+
+/// A multiset of the character n-grams of a string.
+///
+/// Profiles compute the same coefficients as the n-gram based metrics
+/// (Sørensen-Dice, Jaccard, cosine), and are what `cosine_ngrams` compares, so
+/// building them once and reusing them saves work when the same strings are
+/// compared many times. They can be turned into a compact byte
+/// representation with [`NGramProfile::to_bytes`] and restored with
+/// [`NGramProfile::from_bytes`].
+///
+/// ```
+/// use fuzzt::algorithms::NGramProfile;
+///
+/// let a = NGramProfile::new("healed", 2);
+/// let b = NGramProfile::new("sealed", 2);
+///
+/// assert_eq!(5, a.len());
+/// assert_eq!(4, a.intersection(&b));
+/// assert_eq!(6, a.union(&b));
+/// assert_eq!(0.8, a.dice(&b));
+///
+/// let restored = NGramProfile::from_bytes(&a.to_bytes()).unwrap();
+/// assert_eq!(a, restored);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NGramProfile {
+    n: usize,
+    total: usize,
+    counts: HashMap<String, usize>,
+}
+
+impl NGramProfile {
+    /// Builds the profile of all overlapping n-grams of `s`. Strings shorter
+    /// than `n` characters produce an empty profile.
+    ///
+    /// Panics if `n` is 0.
+    pub fn new(s: &str, n: usize) -> Self {
+        assert!(n > 0, "n-grams must contain at least one character");
+
+        let mut profile = NGramProfile {
+            n,
+            total: 0,
+            counts: HashMap::new(),
+        };
+
+        let boundaries: Vec<usize> = s
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(s.len()))
+            .collect();
+
+        for window in boundaries.windows(n + 1) {
+            profile.add(&s[window[0]..window[n]], 1);
+        }
+
+        profile
+    }
+
+    /// Builds the profile of `s` padded with `n - 1` copies of `pad` on both
+    /// sides, e.g. `#word#` for bigrams, so the first and last characters are
+    /// part of as many n-grams as the others. Empty strings produce an empty
+    /// profile.
+    ///
+    /// Panics if `n` is 0.
+    ///
+    /// ```
+    /// use fuzzt::algorithms::NGramProfile;
+    ///
+    /// let profile = NGramProfile::padded("ab", 2, '#');
+    /// assert_eq!(3, profile.len());
+    /// assert_eq!(1, profile.count("#a"));
+    /// assert_eq!(1, profile.count("b#"));
+    /// ```
+    pub fn padded(s: &str, n: usize, pad: char) -> Self {
+        assert!(n > 0, "n-grams must contain at least one character");
+        if s.is_empty() {
+            return NGramProfile::new(s, n);
+        }
+
+        let padding: String = std::iter::repeat(pad).take(n - 1).collect();
+        NGramProfile::new(&format!("{padding}{s}{padding}"), n)
+    }
+
+    fn add(&mut self, ngram: &str, count: usize) {
+        if let Some(value) = self.counts.get_mut(ngram) {
+            *value += count;
+        } else {
+            self.counts.insert(ngram.to_owned(), count);
+        }
+        self.total += count;
+    }
+
+    /// Returns the size of the n-grams stored in the profile.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the number of n-grams in the profile, counting repetitions.
+    pub fn len(&self) -> usize {
+        self.total
+    }
+
+    /// Returns `true` if the profile contains no n-grams.
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Returns how many times `ngram` occurs in the profile.
+    pub fn count(&self, ngram: &str) -> usize {
+        self.counts.get(ngram).copied().unwrap_or(0)
+    }
+
+    /// Iterates over the distinct n-grams of the profile and their counts, in
+    /// arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.counts
+            .iter()
+            .map(|(ngram, &count)| (ngram.as_str(), count))
+    }
+
+    /// Returns the size of the multiset intersection of both profiles.
+    pub fn intersection(&self, other: &NGramProfile) -> usize {
+        let (small, large) = if self.counts.len() <= other.counts.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        small
+            .iter()
+            .map(|(ngram, count)| count.min(large.count(ngram)))
+            .sum()
+    }
+
+    /// Returns the size of the multiset union of both profiles.
+    pub fn union(&self, other: &NGramProfile) -> usize {
+        self.total + other.total - self.intersection(other)
+    }
+
+    /// Calculates the Sørensen-Dice coefficient between both profiles. Two
+    /// empty profiles are considered identical.
+    pub fn dice(&self, other: &NGramProfile) -> f64 {
+        let total = self.total + other.total;
+        if total == 0 {
+            return 1.0;
+        }
+        (2 * self.intersection(other)) as f64 / total as f64
+    }
+
+    /// Calculates the Jaccard index between both profiles. Two empty profiles
+    /// are considered identical.
+    pub fn jaccard(&self, other: &NGramProfile) -> f64 {
+        let union = self.union(other);
+        if union == 0 {
+            return 1.0;
+        }
+        self.intersection(other) as f64 / union as f64
+    }
+
+    /// Calculates the cosine similarity between the n-gram frequency vectors
+    /// of both profiles. Two empty profiles are considered identical.
+    pub fn cosine(&self, other: &NGramProfile) -> f64 {
+        if self.is_empty() && other.is_empty() {
+            return 1.0;
+        }
+        if self.is_empty() || other.is_empty() {
+            return 0.0;
+        }
+
+        let dot: f64 = self
+            .iter()
+            .map(|(ngram, count)| count as f64 * other.count(ngram) as f64)
+            .sum();
+
+        dot / (self.norm() * other.norm())
+    }
+
+    fn norm(&self) -> f64 {
+        self.counts
+            .values()
+            .map(|&count| count as f64 * count as f64)
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Serializes the profile into a compact little-endian byte buffer.
+    ///
+    /// The layout is the n-gram size and the number of distinct n-grams as
+    /// `u32`, followed by every n-gram as a `u32` byte length, its UTF-8 bytes
+    /// and its count as `u64`. N-grams are written in sorted order, so equal
+    /// profiles always produce the same bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut entries: Vec<(&str, usize)> = self.iter().collect();
+        entries.sort_unstable();
+
+        let mut bytes = Vec::with_capacity(8 + entries.len() * (12 + self.n));
+        bytes.extend_from_slice(&(self.n as u32).to_le_bytes());
+        bytes.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+        for (ngram, count) in entries {
+            bytes.extend_from_slice(&(ngram.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(ngram.as_bytes());
+            bytes.extend_from_slice(&(count as u64).to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Restores a profile written by [`NGramProfile::to_bytes`].
+    ///
+    /// ```
+    /// use fuzzt::{algorithms::NGramProfile, FuzztError};
+    ///
+    /// assert_eq!(Err(FuzztError::MalformedProfile), NGramProfile::from_bytes(&[1, 2, 3]));
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FuzztError> {
+        let mut reader = ByteReader { bytes };
+
+        let n = reader.read_u32()? as usize;
+        if n == 0 {
+            return Err(FuzztError::MalformedProfile);
+        }
+        let entries = reader.read_u32()? as usize;
+
+        let mut profile = NGramProfile {
+            n,
+            total: 0,
+            counts: HashMap::with_capacity(entries.min(bytes.len())),
+        };
+
+        for _ in 0..entries {
+            let len = reader.read_u32()? as usize;
+            let ngram =
+                std::str::from_utf8(reader.take(len)?).map_err(|_| FuzztError::MalformedProfile)?;
+            let count =
+                usize::try_from(reader.read_u64()?).map_err(|_| FuzztError::MalformedProfile)?;
+
+            if ngram.chars().count() != n || count == 0 || profile.counts.contains_key(ngram) {
+                return Err(FuzztError::MalformedProfile);
+            }
+            if count > MAX_TOTAL - profile.total {
+                return Err(FuzztError::MalformedProfile);
+            }
+            profile.add(ngram, count);
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err(FuzztError::MalformedProfile);
+        }
+
+        Ok(profile)
+    }
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], FuzztError> {
+        if self.bytes.len() < len {
+            return Err(FuzztError::MalformedProfile);
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, FuzztError> {
+        let head = self.take(4)?;
+        Ok(u32::from_le_bytes(
+            head.try_into().expect("slice has exactly 4 bytes"),
+        ))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, FuzztError> {
+        let head = self.take(8)?;
+        Ok(u64::from_le_bytes(
+            head.try_into().expect("slice has exactly 8 bytes"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ngram_profile_counts() {
+        let profile = NGramProfile::new("banana", 2);
+        assert_eq!(2, profile.n());
+        assert_eq!(5, profile.len());
+        assert_eq!(2, profile.count("an"));
+        assert_eq!(2, profile.count("na"));
+        assert_eq!(1, profile.count("ba"));
+        assert_eq!(0, profile.count("ab"));
+    }
+
+    #[test]
+    fn ngram_profile_short_input() {
+        assert!(NGramProfile::new("", 2).is_empty());
+        assert!(NGramProfile::new("a", 2).is_empty());
+        assert_eq!(1, NGramProfile::new("ab", 2).len());
+    }
+
+    #[test]
+    fn ngram_profile_multibyte() {
+        let profile = NGramProfile::new("öঙ香x", 3);
+        assert_eq!(2, profile.len());
+        assert_eq!(1, profile.count("öঙ香"));
+        assert_eq!(1, profile.count("ঙ香x"));
+    }
+
+    #[test]
+    fn ngram_profile_set_operations() {
+        let a = NGramProfile::new("night", 2);
+        let b = NGramProfile::new("nacht", 2);
+        assert_eq!(1, a.intersection(&b));
+        assert_eq!(7, a.union(&b));
+        assert_delta!(0.25, a.dice(&b));
+        assert_delta!(0.14285, a.jaccard(&b));
+        assert_delta!(0.25, a.cosine(&b));
+    }
+
+    #[test]
+    fn ngram_profile_empty_operations() {
+        let empty = NGramProfile::new("", 2);
+        let other = NGramProfile::new("abc", 2);
+        assert_delta!(1.0, empty.dice(&empty));
+        assert_delta!(1.0, empty.jaccard(&empty));
+        assert_delta!(1.0, empty.cosine(&empty));
+        assert_delta!(0.0, empty.dice(&other));
+        assert_delta!(0.0, empty.jaccard(&other));
+        assert_delta!(0.0, empty.cosine(&other));
+    }
+
+    #[test]
+    fn ngram_profile_cosine_repetitions() {
+        let a = NGramProfile::new("aaa", 1);
+        let b = NGramProfile::new("ab", 1);
+        assert_delta!(std::f64::consts::FRAC_1_SQRT_2, a.cosine(&b));
+    }
+
+    #[test]
+    fn ngram_profile_bytes_roundtrip() {
+        let profile = NGramProfile::new("the quick brown fox, the lazy dog", 3);
+        let bytes = profile.to_bytes();
+        assert_eq!(Ok(profile.clone()), NGramProfile::from_bytes(&bytes));
+        assert_eq!(bytes, NGramProfile::from_bytes(&bytes).unwrap().to_bytes());
+    }
+
+    #[test]
+    fn ngram_profile_bytes_malformed() {
+        let bytes = NGramProfile::new("abcd", 2).to_bytes();
+        assert_eq!(
+            Err(FuzztError::MalformedProfile),
+            NGramProfile::from_bytes(&bytes[..bytes.len() - 1])
+        );
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            Err(FuzztError::MalformedProfile),
+            NGramProfile::from_bytes(&trailing)
+        );
+
+        let mut wrong_n = bytes;
+        wrong_n[0] = 3;
+        assert_eq!(
+            Err(FuzztError::MalformedProfile),
+            NGramProfile::from_bytes(&wrong_n)
+        );
+    }
+
+    #[test]
+    fn ngram_profile_bytes_huge_counts() {
+        let mut bytes = NGramProfile::new("ab", 1).to_bytes();
+        bytes[13..21].copy_from_slice(&(MAX_TOTAL as u64).to_le_bytes());
+        assert_eq!(
+            Err(FuzztError::MalformedProfile),
+            NGramProfile::from_bytes(&bytes)
+        );
+
+        let mut single = NGramProfile::new("a", 1).to_bytes();
+        single[13..21].copy_from_slice(&(MAX_TOTAL as u64).to_le_bytes());
+        let huge = NGramProfile::from_bytes(&single).unwrap();
+        assert!(huge.cosine(&huge).is_finite());
+        assert_delta!(1.0, huge.dice(&huge));
+    }
+}
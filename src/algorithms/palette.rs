@@ -0,0 +1,266 @@
+use crate::algorithms::{Similarity, SimilarityMetric};
+
+// the score of a character of the query matching the first character of a
+// word of the label
+const SCORE_WORD_START: i32 = 8;
+// the score of a character continuing the prefix of a word
+const SCORE_WORD_PREFIX: i32 = 4;
+// the score of a character of a query that only occurs inside a word
+const SCORE_SUBSTRING: i32 = 1;
+// the penalty of each word of the label skipped before the last matched one
+const PENALTY_SKIPPED_WORD: i32 = 1;
+
+/// The result of matching a query against a label with [`palette_match`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteMatch {
+    /// The score of the match. Higher is better, and it only makes sense
+    /// to compare the scores of the same query.
+    pub score: i32,
+    /// The indices of the characters of the label matched by the characters
+    /// of the query, in increasing order.
+    pub positions: Vec<usize>,
+}
+
+/// Returns the index of the first character of each word of `label`, along
+/// with its length. Words are separated by whitespace and punctuation, and
+/// also start at an uppercase letter following a lowercase one and at the
+/// first digit of a number.
+fn words(label: &[char]) -> Vec<(usize, usize)> {
+    let mut words: Vec<(usize, usize)> = vec![];
+    let mut prev: Option<char> = None;
+    for (i, &c) in label.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            prev = None;
+            continue;
+        }
+        let starts_word = match prev {
+            None => true,
+            Some(p) => {
+                (p.is_lowercase() && c.is_uppercase()) || (!p.is_numeric() && c.is_numeric())
+            }
+        };
+        match words.last_mut() {
+            Some(word) if !starts_word => word.1 += 1,
+            _ => words.push((i, 1)),
+        }
+        prev = Some(c);
+    }
+    words
+}
+
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Matches `query` against `label` like the command palette of an editor,
+/// returning the score of the best match along with the matched positions,
+/// or `None` if the label doesn't match.
+///
+/// The query is split into chunks that each match the start of a word of the
+/// label, in order, so both acronyms (`"gc"` for "Git: Commit") and word
+/// prefixes (`"gitcom"`) match. Each character matching the first letter of
+/// a word scores twice as much as one continuing a prefix, so acronyms rank
+/// first, and each word skipped before the last matched one costs a little.
+/// Words are separated by whitespace, punctuation and camel case, and
+/// matching is case-insensitive. Whitespace and punctuation in the query only
+/// separate its words, so they are skipped rather than matched, and
+/// `"git commit"` matches "Git: Commit" like `"gitcommit"`. Failing that, a
+/// query occurring inside a word still matches, with a low score.
+///
+/// An empty query matches any label with a score of 0.
+///
+/// ```
+/// use fuzzt::algorithms::palette_match;
+///
+/// let found = palette_match("gc", "Git: Commit").unwrap();
+/// assert_eq!(found.positions, [0, 5]);
+/// assert!(found.score > palette_match("gc", "Configure GCC").unwrap().score);
+/// assert_eq!(palette_match("gitcom", "Git: Commit").unwrap().positions, [0, 1, 2, 5, 6, 7]);
+/// assert_eq!(None, palette_match("gx", "Git: Commit"));
+/// assert_eq!(palette_match("git com", "Git: Commit").unwrap().positions, [0, 1, 2, 5, 6, 7]);
+/// ```
+pub fn palette_match(query: &str, label: &str) -> Option<PaletteMatch> {
+    let full: Vec<char> = query.chars().map(fold).collect();
+    let original: Vec<char> = label.chars().collect();
+    let label: Vec<char> = original.iter().map(|&c| fold(c)).collect();
+    if full.is_empty() {
+        return Some(PaletteMatch {
+            score: 0,
+            positions: vec![],
+        });
+    }
+
+    let query: Vec<char> = full
+        .iter()
+        .copied()
+        .filter(|c| c.is_alphanumeric())
+        .collect();
+    if !query.is_empty() {
+        if let Some(found) = word_match(&query, &label, &words(&original)) {
+            return Some(found);
+        }
+    }
+
+    let start = label
+        .windows(full.len())
+        .position(|window| window == full.as_slice())?;
+    Some(PaletteMatch {
+        score: full.len() as i32 * SCORE_SUBSTRING,
+        positions: (start..start + full.len()).collect(),
+    })
+}
+
+/// Matches the alphanumeric characters of the query against the starts of the
+/// `words` of `label`, both case folded.
+fn word_match(query: &[char], label: &[char], words: &[(usize, usize)]) -> Option<PaletteMatch> {
+    // `best[i][w]` is the best score of matching `query[i..]` with the words
+    // from `w` on, along with the length of the prefix of word `w` it
+    // matches, 0 if it skips the word
+    let width = words.len() + 1;
+    let mut best: Vec<Option<(i32, usize)>> = vec![None; (query.len() + 1) * width];
+    for w in 0..width {
+        best[query.len() * width + w] = Some((0, 0));
+    }
+    for i in (0..query.len()).rev() {
+        for (w, &(start, len)) in words.iter().enumerate().rev() {
+            let mut cell =
+                best[i * width + w + 1].map(|(score, _)| (score - PENALTY_SKIPPED_WORD, 0));
+            let prefix = label[start..start + len]
+                .iter()
+                .zip(&query[i..])
+                .take_while(|(a, b)| a == b)
+                .count();
+            for matched in 1..=prefix {
+                if let Some((rest, _)) = best[(i + matched) * width + w + 1] {
+                    let score = rest + SCORE_WORD_START + (matched as i32 - 1) * SCORE_WORD_PREFIX;
+                    if cell.map_or(true, |(s, _)| score > s) {
+                        cell = Some((score, matched));
+                    }
+                }
+            }
+            best[i * width + w] = cell;
+        }
+    }
+
+    let (score, _) = best[0]?;
+    let mut positions = Vec::with_capacity(query.len());
+    let (mut i, mut w) = (0, 0);
+    while i < query.len() {
+        if let Some((_, matched)) = best[i * width + w] {
+            positions.extend(words[w].0..words[w].0 + matched);
+            i += matched;
+        }
+        w += 1;
+    }
+    Some(PaletteMatch { score, positions })
+}
+
+/// Scores command and menu labels like the command palette of an editor,
+/// favouring acronyms and word prefixes, see [`palette_match`]. The first
+/// string is the query.
+///
+/// Scores are divided by the score of a query matching the first letter of
+/// as many consecutive words as it has letters and digits, so they are between
+/// 0.0 and 1.0, and labels that
+/// don't match score 0.0.
+///
+/// ```
+/// use fuzzt::algorithms::CommandPalette;
+/// use fuzzt::get_top_n;
+///
+/// let commands = ["Configure GCC", "Git: Commit", "Go to Line"];
+/// let matches = get_top_n("gc", &commands, Some(0.1), None, None, Some(&CommandPalette));
+/// assert_eq!(matches, ["Git: Commit", "Configure GCC"]);
+/// ```
+pub struct CommandPalette;
+
+impl SimilarityMetric for CommandPalette {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        let best = a.chars().filter(|c| c.is_alphanumeric()).count() as i32 * SCORE_WORD_START;
+        Similarity::Float(match palette_match(a, b) {
+            Some(found) if best > 0 => (f64::from(found.score) / f64::from(best)).max(0.0),
+            _ => 0.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_words() {
+        let label: Vec<char> = "View: toggleSideBar2 (x86)".chars().collect();
+        let words: Vec<String> = words(&label)
+            .into_iter()
+            .map(|(start, len)| label[start..start + len].iter().collect())
+            .collect();
+        assert_eq!(words, ["View", "toggle", "Side", "Bar", "2", "x", "86"]);
+    }
+
+    #[test]
+    fn palette_positions() {
+        let cases = [
+            ("gc", "Git: Commit", vec![0, 5]),
+            ("tsb", "View: Toggle Side Bar", vec![6, 13, 18]),
+            ("togsb", "View: toggleSideBar", vec![6, 7, 8, 12, 16]),
+            ("com", "Git: Commit", vec![5, 6, 7]),
+            ("mit", "Git: Commit", vec![8, 9, 10]),
+            ("öঙ", "Öঙ香", vec![0, 1]),
+            (
+                "git commit",
+                "Git: Commit",
+                vec![0, 1, 2, 5, 6, 7, 8, 9, 10],
+            ),
+            (
+                "toggle side",
+                "View: Toggle Side Bar",
+                (6..12).chain(13..17).collect(),
+            ),
+            ("view: t", "View: Toggle Side Bar", vec![0, 1, 2, 3, 6]),
+            (": c", "Git: Commit", vec![5]),
+        ];
+        for (query, label, positions) in cases {
+            let found = palette_match(query, label).unwrap();
+            assert_eq!(positions, found.positions, "{query} {label}");
+        }
+    }
+
+    #[test]
+    fn palette_ranking() {
+        let score = |query, label| palette_match(query, label).map(|found| found.score);
+        // acronyms beat prefixes, which beat substrings
+        assert!(score("gc", "Git: Commit") > score("gc", "gcc: Build"));
+        assert!(score("co", "Git: Commit") > score("co", "Git: Checkout"));
+        // skipped words cost a little
+        assert!(score("gc", "Git: Commit") > score("gc", "Git: Stash Clear"));
+        assert_eq!(None, score("tg", "Git: Commit"));
+        assert_eq!(Some(0), score("", "Git: Commit"));
+        assert_eq!(None, score("g", ""));
+    }
+
+    #[test]
+    fn palette_metric() {
+        assert_eq!(
+            Similarity::Float(1.0),
+            CommandPalette.compute_metric("gc", "Git: Commit")
+        );
+        assert_eq!(
+            Similarity::Float(0.0),
+            CommandPalette.compute_metric("x", "Git: Commit")
+        );
+        assert_eq!(
+            Similarity::Float(0.0),
+            CommandPalette.compute_metric("", "Git")
+        );
+        // separators in the query aren't counted
+        assert_eq!(
+            Similarity::Float(1.0),
+            CommandPalette.compute_metric("g c", "Git: Commit")
+        );
+        assert_eq!(
+            CommandPalette.compute_metric("gitcommit", "Git: Commit"),
+            CommandPalette.compute_metric("Git: Commit", "Git: Commit")
+        );
+    }
+}
@@ -0,0 +1,971 @@
+use crate::algorithms::{normalized_damerau_levenshtein, Similarity, SimilarityMetric};
+
+// the Soundex digit of each letter from A to Z, 0 for the letters that aren't
+// coded
+const SOUNDEX_CODES: &[u8; 26] = b"01230120022455012623010202";
+
+// the Refined Soundex digit of each letter from A to Z
+const REFINED_SOUNDEX_CODES: &[u8; 26] = b"01360240043788015936020505";
+
+/// Returns the ASCII letters of `s`, uppercased. Other characters are ignored
+/// by the phonetic codes.
+fn letters(s: &str) -> impl Iterator<Item = u8> + '_ {
+    s.bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_uppercase())
+}
+
+/// Calculates the American Soundex code of a name: its first letter followed
+/// by three digits coding the consonants that follow, e.g. `"R163"` for
+/// "Robert". Names sounding alike in English, such as "Robert" and "Rupert",
+/// get the same code.
+///
+/// Only ASCII letters are coded, and names without any get an empty code.
+/// Consonants with the same digit are coded once when they are adjacent or
+/// only separated by an H or a W, and codes are padded with zeros.
+///
+/// ```
+/// use fuzzt::algorithms::soundex;
+///
+/// assert_eq!("R163", soundex("Robert"));
+/// assert_eq!("R163", soundex("Rupert"));
+/// assert_eq!("A261", soundex("Ashcraft"));
+/// assert_eq!("L000", soundex("Lee"));
+/// assert_eq!("", soundex("123"));
+/// ```
+pub fn soundex(s: &str) -> String {
+    let mut letters = letters(s);
+    let Some(first) = letters.next() else {
+        return String::new();
+    };
+    let mut code = vec![first];
+    let mut last = SOUNDEX_CODES[usize::from(first - b'A')];
+    for letter in letters {
+        if code.len() == 4 {
+            break;
+        }
+        if letter == b'H' || letter == b'W' {
+            continue;
+        }
+        let digit = SOUNDEX_CODES[usize::from(letter - b'A')];
+        if digit != b'0' && digit != last {
+            code.push(digit);
+        }
+        last = digit;
+    }
+    code.resize(4, b'0');
+    String::from_utf8(code).unwrap_or_default()
+}
+
+/// Calculates the Refined Soundex code of a name: its first letter followed
+/// by one digit per letter, including the first one, e.g. `"R901096"` for
+/// "Robert".
+///
+/// Compared with [`soundex`], consonants are split into finer groups, vowels
+/// are coded too, and codes aren't truncated, so fewer distinct names share a
+/// code. Only ASCII letters are coded, adjacent letters with the same digit
+/// are coded once, and names without any letter get an empty code.
+///
+/// ```
+/// use fuzzt::algorithms::{refined_soundex, soundex};
+///
+/// assert_eq!("T6036084", refined_soundex("testing"));
+/// assert_eq!(soundex("Smith"), soundex("Schmidt"));
+/// assert_ne!(refined_soundex("Smith"), refined_soundex("Schmidt"));
+/// ```
+pub fn refined_soundex(s: &str) -> String {
+    let mut letters = letters(s).peekable();
+    let Some(&first) = letters.peek() else {
+        return String::new();
+    };
+    let mut code = vec![first];
+    let mut last = None;
+    for letter in letters {
+        let digit = REFINED_SOUNDEX_CODES[usize::from(letter - b'A')];
+        if last != Some(digit) {
+            code.push(digit);
+        }
+        last = Some(digit);
+    }
+    String::from_utf8(code).unwrap_or_default()
+}
+
+/// Scores names 1.0 if they have the same [`soundex`] code, and 0.0
+/// otherwise. Names without any letter have an empty code, which matches
+/// nothing.
+///
+/// ```
+/// use fuzzt::algorithms::{Similarity, SimilarityMetric, Soundex};
+///
+/// assert_eq!(Similarity::Float(1.0), Soundex.compute_metric("Tymczak", "Tomasik"));
+/// assert_eq!(Similarity::Float(0.0), Soundex.compute_metric("Robert", "Rubin"));
+/// assert_eq!(Similarity::Float(0.0), Soundex.compute_metric("123", "456"));
+/// ```
+pub struct Soundex;
+
+/// Scores names 1.0 if they have the same [`refined_soundex`] code, and 0.0
+/// otherwise. Names without any letter have an empty code, which matches
+/// nothing.
+///
+/// ```
+/// use fuzzt::algorithms::{RefinedSoundex, Similarity, SimilarityMetric};
+///
+/// assert_eq!(Similarity::Float(1.0), RefinedSoundex.compute_metric("Caren", "Carren"));
+/// assert_eq!(Similarity::Float(0.0), RefinedSoundex.compute_metric("Tymczak", "Tomasik"));
+/// ```
+pub struct RefinedSoundex;
+
+// scores 1.0 if both codes are equal and not empty, and 0.0 otherwise
+fn same_code(a: String, b: String) -> Similarity {
+    Similarity::Float(if !a.is_empty() && a == b { 1.0 } else { 0.0 })
+}
+
+impl SimilarityMetric for Soundex {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        same_code(soundex(a), soundex(b))
+    }
+}
+
+impl SimilarityMetric for RefinedSoundex {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        same_code(refined_soundex(a), refined_soundex(b))
+    }
+}
+
+/// The length of the codes of [`double_metaphone`].
+const DOUBLE_METAPHONE_LEN: usize = 4;
+
+/// The primary and secondary encodings of a name by [`double_metaphone`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetaphoneCodes {
+    /// The encoding of the most common pronunciation of the name.
+    pub primary: String,
+    /// The encoding of an alternative pronunciation, e.g. the native one of
+    /// a foreign name. It is the same as the primary code if there is none.
+    pub secondary: String,
+}
+
+// the state of the Double Metaphone encoding of a word
+struct Metaphone {
+    // the uppercased word, padded with spaces so the rules can look past its
+    // end
+    word: Vec<char>,
+    // the number of characters of the word, without the padding
+    len: isize,
+    slavo_germanic: bool,
+    primary: String,
+    secondary: String,
+}
+
+impl Metaphone {
+    fn new(s: &str) -> Self {
+        let upper = s.to_uppercase();
+        let slavo_germanic = ["W", "K", "CZ", "WITZ"]
+            .iter()
+            .any(|pattern| upper.contains(pattern));
+        let mut word: Vec<char> = upper.chars().collect();
+        let len = word.len() as isize;
+        word.extend([' '; 5]);
+        Metaphone {
+            word,
+            len,
+            slavo_germanic,
+            primary: String::new(),
+            secondary: String::new(),
+        }
+    }
+
+    fn at(&self, pos: isize) -> char {
+        usize::try_from(pos)
+            .ok()
+            .and_then(|pos| self.word.get(pos))
+            .copied()
+            .unwrap_or('\0')
+    }
+
+    // whether one of `patterns`, which are ASCII, starts at `start`
+    fn string_at(&self, start: isize, patterns: &[&str]) -> bool {
+        let Ok(start) = usize::try_from(start) else {
+            return false;
+        };
+        patterns.iter().any(|pattern| {
+            self.word
+                .get(start..start + pattern.len())
+                .is_some_and(|window| window.iter().copied().eq(pattern.chars()))
+        })
+    }
+
+    fn is_vowel(&self, pos: isize) -> bool {
+        pos >= 0 && pos < self.len && matches!(self.at(pos), 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+    }
+
+    fn add(&mut self, primary: &str, secondary: &str) {
+        self.primary.push_str(primary);
+        self.secondary.push_str(secondary);
+    }
+
+    fn add_both(&mut self, code: &str) {
+        self.add(code, code);
+    }
+
+    fn germanic(&self) -> bool {
+        self.string_at(0, &["VAN ", "VON "]) || self.string_at(0, &["SCH"])
+    }
+
+    // encodes the consonant or vowel at `current` and returns the position of
+    // the next one to encode
+    fn encode_at(&mut self, current: isize) -> isize {
+        let last = self.len - 1;
+        let next = self.at(current + 1);
+        match self.at(current) {
+            'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                if current == 0 {
+                    self.add_both("A");
+                }
+                current + 1
+            }
+            'B' => {
+                self.add_both("P");
+                current + if next == 'B' { 2 } else { 1 }
+            }
+            'Ç' => {
+                self.add_both("S");
+                current + 1
+            }
+            'C' => self.encode_c(current),
+            'D' => {
+                if self.string_at(current, &["DG"]) {
+                    if self.string_at(current + 2, &["I", "E", "Y"]) {
+                        // e.g. "edge"
+                        self.add_both("J");
+                        return current + 3;
+                    }
+                    // e.g. "edgar"
+                    self.add_both("TK");
+                    return current + 2;
+                }
+                self.add_both("T");
+                current
+                    + if self.string_at(current, &["DT", "DD"]) {
+                        2
+                    } else {
+                        1
+                    }
+            }
+            'F' | 'K' | 'N' | 'Q' | 'V' => {
+                let c = self.at(current);
+                self.add_both(match c {
+                    'F' | 'V' => "F",
+                    'N' => "N",
+                    _ => "K",
+                });
+                current + if next == c { 2 } else { 1 }
+            }
+            'G' => self.encode_g(current),
+            'H' => {
+                // only kept at the start of the word or between vowels
+                if (current == 0 || self.is_vowel(current - 1)) && self.is_vowel(current + 1) {
+                    self.add_both("H");
+                    return current + 2;
+                }
+                current + 1
+            }
+            'J' => self.encode_j(current),
+            'L' => {
+                if next == 'L' {
+                    // Spanish, e.g. "cabrillo", "gallegos"
+                    if (current == self.len - 3
+                        && self.string_at(current - 1, &["ILLO", "ILLA", "ALLE"]))
+                        || ((self.string_at(last - 1, &["AS", "OS"])
+                            || self.string_at(last, &["A", "O"]))
+                            && self.string_at(current - 1, &["ALLE"]))
+                    {
+                        self.add("L", "");
+                        return current + 2;
+                    }
+                    self.add_both("L");
+                    return current + 2;
+                }
+                self.add_both("L");
+                current + 1
+            }
+            'M' => {
+                self.add_both("M");
+                // e.g. "dumb", "thumb"
+                if (self.string_at(current - 1, &["UMB"])
+                    && (current + 1 == last || self.string_at(current + 2, &["ER"])))
+                    || next == 'M'
+                {
+                    return current + 2;
+                }
+                current + 1
+            }
+            'Ñ' => {
+                self.add_both("N");
+                current + 1
+            }
+            'P' => {
+                if next == 'H' {
+                    self.add_both("F");
+                    return current + 2;
+                }
+                // e.g. "campbell", "raspberry"
+                self.add_both("P");
+                current + if matches!(next, 'P' | 'B') { 2 } else { 1 }
+            }
+            'R' => {
+                // French, e.g. "rogier", but not "hochmeier"
+                if current == last
+                    && !self.slavo_germanic
+                    && self.string_at(current - 2, &["IE"])
+                    && !self.string_at(current - 4, &["ME", "MA"])
+                {
+                    self.add("", "R");
+                } else {
+                    self.add_both("R");
+                }
+                current + if next == 'R' { 2 } else { 1 }
+            }
+            'S' => self.encode_s(current),
+            'T' => {
+                if self.string_at(current, &["TION"]) || self.string_at(current, &["TIA", "TCH"]) {
+                    self.add_both("X");
+                    return current + 3;
+                }
+                if self.string_at(current, &["TH"]) || self.string_at(current, &["TTH"]) {
+                    // e.g. "thomas", "thames", or Germanic
+                    if self.string_at(current + 2, &["OM", "AM"]) || self.germanic() {
+                        self.add_both("T");
+                    } else {
+                        self.add("0", "T");
+                    }
+                    return current + 2;
+                }
+                self.add_both("T");
+                current + if matches!(next, 'T' | 'D') { 2 } else { 1 }
+            }
+            'W' => self.encode_w(current),
+            'X' => {
+                // French, e.g. "breaux"
+                if !(current == last
+                    && (self.string_at(current - 3, &["IAU", "EAU"])
+                        || self.string_at(current - 2, &["AU", "OU"])))
+                {
+                    self.add_both("KS");
+                }
+                current + if matches!(next, 'C' | 'X') { 2 } else { 1 }
+            }
+            'Z' => {
+                // Chinese pinyin, e.g. "zhao"
+                if next == 'H' {
+                    self.add_both("J");
+                    return current + 2;
+                }
+                if self.string_at(current + 1, &["ZO", "ZI", "ZA"])
+                    || (self.slavo_germanic && current > 0 && self.at(current - 1) != 'T')
+                {
+                    self.add("S", "TS");
+                } else {
+                    self.add_both("S");
+                }
+                current + if next == 'Z' { 2 } else { 1 }
+            }
+            _ => current + 1,
+        }
+    }
+
+    fn encode_c(&mut self, current: isize) -> isize {
+        // various Germanic
+        if current > 1
+            && !self.is_vowel(current - 2)
+            && self.string_at(current - 1, &["ACH"])
+            && self.at(current + 2) != 'I'
+            && (self.at(current + 2) != 'E' || self.string_at(current - 2, &["BACHER", "MACHER"]))
+        {
+            self.add_both("K");
+            return current + 2;
+        }
+        // e.g. "caesar"
+        if current == 0 && self.string_at(current, &["CAESAR"]) {
+            self.add_both("S");
+            return current + 2;
+        }
+        // Italian, e.g. "chianti"
+        if self.string_at(current, &["CHIA"]) {
+            self.add_both("K");
+            return current + 2;
+        }
+        if self.string_at(current, &["CH"]) {
+            // e.g. "michael"
+            if current > 0 && self.string_at(current, &["CHAE"]) {
+                self.add("K", "X");
+                return current + 2;
+            }
+            // Greek roots, e.g. "chemistry", "chorus"
+            if current == 0
+                && (self.string_at(current + 1, &["HARAC", "HARIS"])
+                    || self.string_at(current + 1, &["HOR", "HYM", "HIA", "HEM"]))
+                && !self.string_at(0, &["CHORE"])
+            {
+                self.add_both("K");
+                return current + 2;
+            }
+            // Germanic, Greek, or otherwise "ch" for the "kh" sound
+            if self.germanic()
+                // "architect", but not "arch", "orchestra", "orchid"
+                || self.string_at(current - 2, &["ORCHES", "ARCHIT", "ORCHID"])
+                || self.string_at(current + 2, &["T", "S"])
+                // e.g. "wachtler", "wechsler", but not "tichner"
+                || ((self.string_at(current - 1, &["A", "O", "U", "E"]) || current == 0)
+                    && self.string_at(
+                        current + 2,
+                        &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "],
+                    ))
+            {
+                self.add_both("K");
+            } else if current > 0 {
+                // e.g. "mchugh"
+                if self.string_at(0, &["MC"]) {
+                    self.add_both("K");
+                } else {
+                    self.add("X", "K");
+                }
+            } else {
+                self.add_both("X");
+            }
+            return current + 2;
+        }
+        // e.g. "czerny"
+        if self.string_at(current, &["CZ"]) && !self.string_at(current - 2, &["WICZ"]) {
+            self.add("S", "X");
+            return current + 2;
+        }
+        // e.g. "focaccia"
+        if self.string_at(current + 1, &["CIA"]) {
+            self.add_both("X");
+            return current + 3;
+        }
+        // double "c", but not e.g. "mcclellan"
+        if self.string_at(current, &["CC"]) && !(current == 1 && self.at(0) == 'M') {
+            // "bellocchio", but not "bacchus"
+            if self.string_at(current + 2, &["I", "E", "H"])
+                && !self.string_at(current + 2, &["HU"])
+            {
+                // e.g. "accident", "accede", "succeed"
+                if (current == 1 && self.at(current - 1) == 'A')
+                    || self.string_at(current - 1, &["UCCEE", "UCCES"])
+                {
+                    self.add_both("KS");
+                } else {
+                    // e.g. "bacci", "bertucci"
+                    self.add_both("X");
+                }
+                return current + 3;
+            }
+            // Pierce's rule
+            self.add_both("K");
+            return current + 2;
+        }
+        if self.string_at(current, &["CK", "CG", "CQ"]) {
+            self.add_both("K");
+            return current + 2;
+        }
+        if self.string_at(current, &["CI", "CE", "CY"]) {
+            // Italian or English
+            if self.string_at(current, &["CIO", "CIE", "CIA"]) {
+                self.add("S", "X");
+            } else {
+                self.add_both("S");
+            }
+            return current + 2;
+        }
+        self.add_both("K");
+        // e.g. "mac caffrey", "mac gregor"
+        if self.string_at(current + 1, &[" C", " Q", " G"]) {
+            current + 3
+        } else if self.string_at(current + 1, &["C", "K", "Q"])
+            && !self.string_at(current + 1, &["CE", "CI"])
+        {
+            current + 2
+        } else {
+            current + 1
+        }
+    }
+
+    fn encode_g(&mut self, current: isize) -> isize {
+        let next = self.at(current + 1);
+        if next == 'H' {
+            if current > 0 && !self.is_vowel(current - 1) {
+                self.add_both("K");
+                return current + 2;
+            }
+            // e.g. "ghislane", "ghiradelli"
+            if current == 0 {
+                if self.at(current + 2) == 'I' {
+                    self.add_both("J");
+                } else {
+                    self.add_both("K");
+                }
+                return current + 2;
+            }
+            // Parker's rule, e.g. "hugh", "bough", "broughton"
+            if (current > 1 && self.string_at(current - 2, &["B", "H", "D"]))
+                || (current > 2 && self.string_at(current - 3, &["B", "H", "D"]))
+                || (current > 3 && self.string_at(current - 4, &["B", "H"]))
+            {
+                return current + 2;
+            }
+            // e.g. "laugh", "mclaughlin", "cough", "gough", "rough", "tough"
+            if current > 2
+                && self.at(current - 1) == 'U'
+                && self.string_at(current - 3, &["C", "G", "L", "R", "T"])
+            {
+                self.add_both("F");
+            } else if current > 0 && self.at(current - 1) != 'I' {
+                self.add_both("K");
+            }
+            return current + 2;
+        }
+        if next == 'N' {
+            if current == 1 && self.is_vowel(0) && !self.slavo_germanic {
+                self.add("KN", "N");
+            } else if !self.string_at(current + 2, &["EY"]) && !self.slavo_germanic {
+                // not e.g. "cagney"
+                self.add("N", "KN");
+            } else {
+                self.add_both("KN");
+            }
+            return current + 2;
+        }
+        // e.g. "tagliaro"
+        if self.string_at(current + 1, &["LI"]) && !self.slavo_germanic {
+            self.add("KL", "L");
+            return current + 2;
+        }
+        // -ges-, -gep-, -gel-, -gie- at the start of the word
+        if current == 0
+            && (next == 'Y'
+                || self.string_at(
+                    current + 1,
+                    &[
+                        "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+                    ],
+                ))
+        {
+            self.add("K", "J");
+            return current + 2;
+        }
+        // -ger-, -gy-
+        if (self.string_at(current + 1, &["ER"]) || next == 'Y')
+            && !self.string_at(0, &["DANGER", "RANGER", "MANGER"])
+            && !self.string_at(current - 1, &["E", "I"])
+            && !self.string_at(current - 1, &["RGY", "OGY"])
+        {
+            self.add("K", "J");
+            return current + 2;
+        }
+        // Italian, e.g. "biaggi"
+        if self.string_at(current + 1, &["E", "I", "Y"])
+            || self.string_at(current - 1, &["AGGI", "OGGI"])
+        {
+            if self.germanic() || self.string_at(current + 1, &["ET"]) {
+                self.add_both("K");
+            } else if self.string_at(current + 1, &["IER "]) {
+                // always soft with a French ending
+                self.add_both("J");
+            } else {
+                self.add("J", "K");
+            }
+            return current + 2;
+        }
+        self.add_both("K");
+        current + if next == 'G' { 2 } else { 1 }
+    }
+
+    fn encode_j(&mut self, current: isize) -> isize {
+        // obviously Spanish, e.g. "jose", "san jacinto"
+        if self.string_at(current, &["JOSE"]) || self.string_at(0, &["SAN "]) {
+            if (current == 0 && self.at(current + 4) == ' ') || self.string_at(0, &["SAN "]) {
+                self.add_both("H");
+            } else {
+                self.add("J", "H");
+            }
+            return current + 1;
+        }
+        if current == 0 {
+            // e.g. "yankelovich", "jankelowicz"
+            self.add("J", "A");
+        } else if self.is_vowel(current - 1)
+            && !self.slavo_germanic
+            && matches!(self.at(current + 1), 'A' | 'O')
+        {
+            // Spanish pronunciation, e.g. "bajador"
+            self.add("J", "H");
+        } else if current == self.len - 1 {
+            self.add("J", "");
+        } else if !self.string_at(current + 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.string_at(current - 1, &["S", "K", "L"])
+        {
+            self.add_both("J");
+        }
+        current + if self.at(current + 1) == 'J' { 2 } else { 1 }
+    }
+
+    fn encode_s(&mut self, current: isize) -> isize {
+        // e.g. "island", "isle", "carlisle", "carlysle"
+        if self.string_at(current - 1, &["ISL", "YSL"]) {
+            return current + 1;
+        }
+        // e.g. "sugar"
+        if current == 0 && self.string_at(current, &["SUGAR"]) {
+            self.add("X", "S");
+            return current + 1;
+        }
+        if self.string_at(current, &["SH"]) {
+            // Germanic
+            if self.string_at(current + 1, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.add_both("S");
+            } else {
+                self.add_both("X");
+            }
+            return current + 2;
+        }
+        // Italian and Armenian
+        if self.string_at(current, &["SIO", "SIA"]) || self.string_at(current, &["SIAN"]) {
+            if self.slavo_germanic {
+                self.add_both("S");
+            } else {
+                self.add("S", "X");
+            }
+            return current + 3;
+        }
+        // German and anglicisations, e.g. "smith" matching "schmidt" and
+        // "snider" matching "schneider", and -sz- in Slavic languages
+        if (current == 0 && self.string_at(current + 1, &["M", "N", "L", "W"]))
+            || self.string_at(current + 1, &["Z"])
+        {
+            self.add("S", "X");
+            return current
+                + if self.string_at(current + 1, &["Z"]) {
+                    2
+                } else {
+                    1
+                };
+        }
+        if self.string_at(current, &["SC"]) {
+            // Schlesinger's rule
+            if self.at(current + 2) == 'H' {
+                // Dutch, e.g. "school", "schooner"
+                if self.string_at(current + 3, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                    // e.g. "schermerhorn", "schenker"
+                    if self.string_at(current + 3, &["ER", "EN"]) {
+                        self.add("X", "SK");
+                    } else {
+                        self.add_both("SK");
+                    }
+                } else if current == 0 && !self.is_vowel(3) && self.at(3) != 'W' {
+                    self.add("X", "S");
+                } else {
+                    self.add_both("X");
+                }
+                return current + 3;
+            }
+            if self.string_at(current + 2, &["I", "E", "Y"]) {
+                self.add_both("S");
+            } else {
+                self.add_both("SK");
+            }
+            return current + 3;
+        }
+        // French, e.g. "resnais", "artois"
+        if current == self.len - 1 && self.string_at(current - 2, &["AI", "OI"]) {
+            self.add("", "S");
+        } else {
+            self.add_both("S");
+        }
+        current
+            + if self.string_at(current + 1, &["S", "Z"]) {
+                2
+            } else {
+                1
+            }
+    }
+
+    fn encode_w(&mut self, current: isize) -> isize {
+        if self.string_at(current, &["WR"]) {
+            self.add_both("R");
+            return current + 2;
+        }
+        if current == 0 && (self.is_vowel(current + 1) || self.string_at(current, &["WH"])) {
+            // "wasserman" matching "vasserman", and "uomo" matching "womo"
+            if self.is_vowel(current + 1) {
+                self.add("A", "F");
+            } else {
+                self.add_both("A");
+            }
+        }
+        // "arnow" matching "arnoff"
+        if (current == self.len - 1 && self.is_vowel(current - 1))
+            || self.string_at(current - 1, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.string_at(0, &["SCH"])
+        {
+            self.add("", "F");
+            return current + 1;
+        }
+        // Polish, e.g. "filipowicz"
+        if self.string_at(current, &["WICZ", "WITZ"]) {
+            self.add("TS", "FX");
+            return current + 4;
+        }
+        current + 1
+    }
+
+    fn encode(mut self) -> MetaphoneCodes {
+        let mut current = 0;
+        // letters not pronounced at the start of the word
+        if self.string_at(0, &["GN", "KN", "PN", "WR", "PS"]) {
+            current += 1;
+        }
+        // e.g. "xavier"
+        if self.at(0) == 'X' {
+            self.add_both("S");
+            current += 1;
+        }
+        while (self.primary.len() < DOUBLE_METAPHONE_LEN
+            || self.secondary.len() < DOUBLE_METAPHONE_LEN)
+            && current < self.len
+        {
+            current = self.encode_at(current);
+        }
+        self.primary.truncate(DOUBLE_METAPHONE_LEN);
+        self.secondary.truncate(DOUBLE_METAPHONE_LEN);
+        MetaphoneCodes {
+            primary: self.primary,
+            secondary: self.secondary,
+        }
+    }
+}
+
+/// Calculates the Double Metaphone codes of a name, as described by Philips
+/// (2000).
+///
+/// Unlike [`soundex`], the rules take the spelling conventions of many
+/// languages into account, e.g. Slavic, Germanic, Spanish, Italian or
+/// French, and the name gets a secondary code when it has two plausible
+/// pronunciations, so "Schmidt" and "Smith" share one of their codes. Codes
+/// contain up to four characters, `0` standing for "th".
+///
+/// ```
+/// use fuzzt::algorithms::double_metaphone;
+///
+/// let smith = double_metaphone("Smith");
+/// assert_eq!((smith.primary.as_str(), smith.secondary.as_str()), ("SM0", "XMT"));
+/// let schmidt = double_metaphone("Schmidt");
+/// assert_eq!((schmidt.primary.as_str(), schmidt.secondary.as_str()), ("XMT", "SMT"));
+/// ```
+pub fn double_metaphone(s: &str) -> MetaphoneCodes {
+    Metaphone::new(s).encode()
+}
+
+/// Scores names 1.0 if any of the [`double_metaphone`] codes of one of them
+/// is equal to any of the codes of the other, and 0.0 otherwise. Names
+/// without any letter have empty codes, which match nothing.
+///
+/// ```
+/// use fuzzt::algorithms::{DoubleMetaphone, Similarity, SimilarityMetric};
+///
+/// assert_eq!(Similarity::Float(1.0), DoubleMetaphone.compute_metric("Schmidt", "Smith"));
+/// assert_eq!(Similarity::Float(1.0), DoubleMetaphone.compute_metric("Arnow", "Arnoff"));
+/// assert_eq!(Similarity::Float(0.0), DoubleMetaphone.compute_metric("Thomas", "Tamsin"));
+/// assert_eq!(Similarity::Float(0.0), DoubleMetaphone.compute_metric("123", "!!"));
+/// ```
+pub struct DoubleMetaphone;
+
+impl SimilarityMetric for DoubleMetaphone {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        let a = double_metaphone(a);
+        let b = double_metaphone(b);
+        let matches = [&a.primary, &a.secondary]
+            .iter()
+            .filter(|code| !code.is_empty())
+            .any(|code| *code == &b.primary || *code == &b.secondary);
+        Similarity::Float(if matches { 1.0 } else { 0.0 })
+    }
+}
+
+/// Blends a phonetic code with the spelling of names: the score is a weighted
+/// mean of 1.0 if both names have the same non-empty code and 0.0 otherwise,
+/// and of their normalized Damerau-Levenshtein similarity.
+///
+/// The phonetic part matches names that sound alike however they are
+/// spelled, and the edit distance part ranks the names sharing a code and
+/// still scores typos that change the code. Any function returning a code can
+/// be used as the encoder, such as [`soundex`] or one of the codes of
+/// [`double_metaphone`].
+///
+/// ```
+/// use fuzzt::algorithms::{double_metaphone, soundex, PhoneticHybrid};
+///
+/// let hybrid = PhoneticHybrid::new(&soundex).with_weight(0.4);
+/// // "R163" for both names
+/// assert_eq!(0.4 + 0.6 * (1.0 - 2.0 / 6.0), hybrid.similarity("Robert", "Rupert"));
+/// // "R163" and "R150"
+/// assert_eq!(0.6 * (1.0 - 4.0 / 6.0), hybrid.similarity("Robert", "Rubin"));
+///
+/// let primary = |s: &str| double_metaphone(s).primary;
+/// let hybrid = PhoneticHybrid::new(&primary);
+/// assert!(hybrid.similarity("Catherine", "Kathryn") > hybrid.similarity("Catherine", "Caroline"));
+/// ```
+pub struct PhoneticHybrid<'e> {
+    encoder: &'e dyn Fn(&str) -> String,
+    weight: f64,
+}
+
+impl<'e> PhoneticHybrid<'e> {
+    /// Creates a metric encoding names with `encoder`, giving the same
+    /// weight to the codes and to the spelling.
+    pub fn new(encoder: &'e dyn Fn(&str) -> String) -> Self {
+        PhoneticHybrid {
+            encoder,
+            weight: 0.5,
+        }
+    }
+
+    /// Sets the weight of the phonetic codes, the spelling getting the rest.
+    ///
+    /// Panics if `weight` isn't between 0.0 and 1.0.
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&weight),
+            "the weight must be between 0 and 1"
+        );
+        self.weight = weight;
+        self
+    }
+
+    /// Calculates the blended similarity of both names, between 0.0 and 1.0.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        let code = (self.encoder)(a);
+        let same_code = !code.is_empty() && code == (self.encoder)(b);
+        let phonetic = if same_code { 1.0 } else { 0.0 };
+        self.weight * phonetic + (1.0 - self.weight) * normalized_damerau_levenshtein(a, b)
+    }
+}
+
+impl SimilarityMetric for PhoneticHybrid<'_> {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.similarity(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn soundex_codes() {
+        let cases = [
+            ("Robert", "R163"),
+            ("Rubin", "R150"),
+            ("Tymczak", "T522"),
+            ("Pfister", "P236"),
+            ("Honeyman", "H555"),
+            ("o'Brien-Smith", "O165"),
+            ("Zoë", "Z000"),
+            ("", ""),
+        ];
+        for (name, code) in cases {
+            assert_eq!(code, soundex(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn refined_soundex_codes() {
+        let cases = [
+            ("Robert", "R901096"),
+            ("TESTING", "T6036084"),
+            ("The", "T60"),
+            ("quick", "Q503"),
+            ("brown", "B1908"),
+            ("jumped", "J408106"),
+            ("lazy", "L7050"),
+            ("Ashcraft", "A03039026"),
+            ("Peters", "P106093"),
+            ("Petrie", "P10690"),
+            ("", ""),
+        ];
+        for (name, code) in cases {
+            assert_eq!(code, refined_soundex(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn soundex_metrics_need_a_code() {
+        assert_eq!(Similarity::Float(0.0), Soundex.compute_metric("123", "456"));
+        assert_eq!(Similarity::Float(0.0), Soundex.compute_metric("", ""));
+        assert_eq!(
+            Similarity::Float(0.0),
+            RefinedSoundex.compute_metric("123", "!!")
+        );
+        assert_eq!(
+            Similarity::Float(1.0),
+            RefinedSoundex.compute_metric("Robert", "robert")
+        );
+    }
+
+    #[test]
+    fn double_metaphone_codes() {
+        // checked against the dmetaphone functions of PostgreSQL
+        let cases = [
+            ("Schmidt", "XMT", "SMT"),
+            ("Smith", "SM0", "XMT"),
+            ("Jose", "HS", "HS"),
+            ("Thomas", "TMS", "TMS"),
+            ("Gallegos", "KLKS", "KKS"),
+            ("Caesar", "SSR", "SSR"),
+            ("Michael", "MKL", "MXL"),
+            ("Czerny", "SRN", "XRN"),
+            ("Filipowicz", "FLPT", "FLPF"),
+            ("Womo", "AM", "FM"),
+            ("Xavier", "SF", "SFR"),
+            ("Raj", "RJ", "R"),
+            ("Rogier", "RJ", "RJR"),
+            ("", "", ""),
+        ];
+        for (name, primary, secondary) in cases {
+            let codes = double_metaphone(name);
+            assert_eq!(primary, codes.primary, "{name}");
+            assert_eq!(secondary, codes.secondary, "{name}");
+        }
+    }
+
+    #[test]
+    fn double_metaphone_metric_needs_a_code() {
+        assert_eq!(
+            Similarity::Float(0.0),
+            DoubleMetaphone.compute_metric("123", "!!")
+        );
+        assert_eq!(
+            Similarity::Float(0.0),
+            DoubleMetaphone.compute_metric("", "")
+        );
+        assert_eq!(
+            Similarity::Float(1.0),
+            DoubleMetaphone.compute_metric("Smith", "Smith")
+        );
+    }
+
+    #[test]
+    fn phonetic_hybrid_weights() {
+        let phonetic = PhoneticHybrid::new(&soundex).with_weight(1.0);
+        assert_eq!(1.0, phonetic.similarity("Tymczak", "Tomasik"));
+        assert_eq!(0.0, phonetic.similarity("Robert", "Rubin"));
+        // names without a code don't match phonetically
+        assert_eq!(0.0, phonetic.similarity("123", "456"));
+        let spelling = PhoneticHybrid::new(&soundex).with_weight(0.0);
+        assert_eq!(
+            normalized_damerau_levenshtein("Tymczak", "Tomasik"),
+            spelling.similarity("Tymczak", "Tomasik")
+        );
+    }
+}
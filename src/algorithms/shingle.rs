@@ -0,0 +1,157 @@
+use crate::algorithms::{Similarity, SimilarityMetric};
+use crate::processors::{Tokenizer, WhitespaceTokenizer};
+use std::collections::HashSet;
+
+/// How two sets of shingles are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShingleMeasure {
+    /// The number of shared shingles divided by the number of distinct
+    /// shingles of both strings.
+    Jaccard,
+    /// The fraction of the shingles of the first string that also occur in the
+    /// second string.
+    Containment,
+}
+
+/// Compares strings by their shingles: the sequences of `size` consecutive
+/// tokens they contain.
+///
+/// Shingles of a few words are much better than character-level metrics at
+/// detecting near-duplicate sentences, since rewording a sentence changes few
+/// of its shingles while two unrelated sentences rarely share any. Strings
+/// with fewer than `size` tokens form a single shingle.
+///
+/// ```
+/// use fuzzt::algorithms::{ShingleMeasure, ShingleSimilarity};
+/// use fuzzt::processors::WordTokenizer;
+///
+/// let shingles = ShingleSimilarity::new(3, &WordTokenizer, ShingleMeasure::Jaccard);
+/// let score = shingles.similarity(
+///     "the quick brown fox jumps over the lazy dog",
+///     "the quick brown fox leaps over the lazy dog",
+/// );
+/// assert!((score - 0.4).abs() < 0.00001);
+/// ```
+pub struct ShingleSimilarity<'t> {
+    size: usize,
+    tokenizer: &'t dyn Tokenizer,
+    measure: ShingleMeasure,
+}
+
+impl<'t> ShingleSimilarity<'t> {
+    /// Creates a metric over shingles of `size` tokens produced by `tokenizer`.
+    ///
+    /// Panics if `size` is 0.
+    pub fn new(size: usize, tokenizer: &'t dyn Tokenizer, measure: ShingleMeasure) -> Self {
+        assert!(size > 0, "shingles must contain at least one token");
+        ShingleSimilarity {
+            size,
+            tokenizer,
+            measure,
+        }
+    }
+
+    fn shingles<'a>(&self, s: &'a str) -> HashSet<Vec<&'a str>> {
+        let tokens = self.tokenizer.tokenize(s);
+        if tokens.is_empty() {
+            return HashSet::new();
+        }
+        if tokens.len() < self.size {
+            return HashSet::from([tokens]);
+        }
+        tokens
+            .windows(self.size)
+            .map(|window| window.to_vec())
+            .collect()
+    }
+
+    /// Compares the shingles of both strings. Strings without any token are
+    /// only similar to each other.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        let a = self.shingles(a);
+        let b = self.shingles(b);
+        if a.is_empty() && b.is_empty() {
+            return 1.0;
+        }
+        if a.is_empty() || b.is_empty() {
+            return 0.0;
+        }
+
+        let shared = a.intersection(&b).count();
+        match self.measure {
+            ShingleMeasure::Jaccard => shared as f64 / (a.len() + b.len() - shared) as f64,
+            ShingleMeasure::Containment => shared as f64 / a.len() as f64,
+        }
+    }
+}
+
+impl SimilarityMetric for ShingleSimilarity<'_> {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.similarity(a, b))
+    }
+}
+
+/// Calculates the Jaccard index between the shingles of `size` words of two
+/// strings, splitting words on whitespace.
+///
+/// ```
+/// use fuzzt::algorithms::shingle_jaccard;
+///
+/// assert_eq!(1.0, shingle_jaccard("a rose is a rose", "a rose is a rose", 3));
+/// assert_eq!(2.0 / 3.0, shingle_jaccard("a rose is a rose", "a rose is a", 3));
+/// ```
+pub fn shingle_jaccard(a: &str, b: &str, size: usize) -> f64 {
+    ShingleSimilarity::new(size, &WhitespaceTokenizer, ShingleMeasure::Jaccard).similarity(a, b)
+}
+
+/// Calculates the fraction of the shingles of `size` words of `a` that also
+/// occur in `b`, splitting words on whitespace.
+///
+/// ```
+/// use fuzzt::algorithms::shingle_containment;
+///
+/// let sentence = "it was the best of times";
+/// let longer = "and so it was the best of times indeed";
+/// assert_eq!(1.0, shingle_containment(sentence, longer, 3));
+/// assert_eq!(4.0 / 7.0, shingle_containment(longer, sentence, 3));
+/// ```
+pub fn shingle_containment(a: &str, b: &str, size: usize) -> f64 {
+    ShingleSimilarity::new(size, &WhitespaceTokenizer, ShingleMeasure::Containment).similarity(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::WordTokenizer;
+
+    #[test]
+    fn shingle_empty() {
+        assert_delta!(1.0, shingle_jaccard("", " ", 3));
+        assert_delta!(0.0, shingle_jaccard("", "word", 3));
+        assert_delta!(0.0, shingle_containment("word", "", 3));
+    }
+
+    #[test]
+    fn shingle_short_strings() {
+        assert_delta!(1.0, shingle_jaccard("hello world", "hello  world", 3));
+        assert_delta!(0.0, shingle_jaccard("hello world", "world hello", 3));
+    }
+
+    #[test]
+    fn shingle_containment_is_asymmetric() {
+        let short = "the lazy dog";
+        let long = "the quick brown fox jumps over the lazy dog";
+        assert_delta!(1.0, shingle_containment(short, long, 2));
+        assert_delta!(0.25, shingle_containment(long, short, 2));
+    }
+
+    #[test]
+    fn shingle_tokenizer() {
+        let shingles = ShingleSimilarity::new(2, &WordTokenizer, ShingleMeasure::Jaccard);
+        assert_delta!(1.0, shingles.similarity("Hello, world!", "Hello world"));
+        assert!(matches!(
+            shingles.compute_metric("a b c", "a b d"),
+            Similarity::Float(r) if (r - 1.0 / 3.0).abs() < 1e-9
+        ));
+    }
+}
@@ -0,0 +1,246 @@
+use crate::algorithms::{Similarity, SimilarityMetric};
+use crate::processors::Tokenizer;
+
+/// A match found while scanning the sequences, remembered to detect
+/// transpositions.
+struct Offset {
+    a: usize,
+    b: usize,
+    transposed: bool,
+}
+
+/// Approximates the edit distance between two sequences with Sift4, looking
+/// for matching elements at most `max_offset` positions ahead. Stops as soon
+/// as the distance is known to exceed `max_distance`.
+fn generic_sift4<T>(a: &[T], b: &[T], max_offset: usize, max_distance: Option<usize>) -> usize
+where
+    T: PartialEq,
+{
+    let (a_len, b_len) = (a.len(), b.len());
+    if a_len == 0 || b_len == 0 {
+        return a_len.max(b_len);
+    }
+
+    // cursors may step back one position before being advanced again, so
+    // they are signed
+    let (mut i, mut j) = (0_isize, 0_isize);
+    let mut lcss = 0;
+    let mut local_cs = 0;
+    let mut transpositions = 0;
+    let mut offsets: Vec<Offset> = vec![];
+
+    while (i as usize) < a_len && (j as usize) < b_len {
+        let (ui, uj) = (i as usize, j as usize);
+        if a[ui] == b[uj] {
+            local_cs += 1;
+            let mut transposed = false;
+            let mut k = 0;
+            while k < offsets.len() {
+                let offset = &mut offsets[k];
+                if ui <= offset.a || uj <= offset.b {
+                    transposed = ui.abs_diff(uj) >= offset.a.abs_diff(offset.b);
+                    if transposed {
+                        transpositions += 1;
+                    } else if !offset.transposed {
+                        offset.transposed = true;
+                        transpositions += 1;
+                    }
+                    break;
+                } else if ui > offset.b && uj > offset.a {
+                    offsets.remove(k);
+                } else {
+                    k += 1;
+                }
+            }
+            offsets.push(Offset {
+                a: ui,
+                b: uj,
+                transposed,
+            });
+        } else {
+            lcss += local_cs;
+            local_cs = 0;
+            let cursor = i.min(j);
+            (i, j) = (cursor, cursor);
+            let ucursor = cursor as usize;
+
+            if let Some(max_distance) = max_distance {
+                let distance = ucursor + transpositions - lcss;
+                if distance > max_distance {
+                    return distance;
+                }
+            }
+
+            for step in 0..max_offset {
+                if ucursor + step >= a_len && ucursor + step >= b_len {
+                    break;
+                }
+                if ucursor + step < a_len && a[ucursor + step] == b[ucursor] {
+                    i += step as isize - 1;
+                    j -= 1;
+                    break;
+                }
+                if ucursor + step < b_len && a[ucursor] == b[ucursor + step] {
+                    i -= 1;
+                    j += step as isize - 1;
+                    break;
+                }
+            }
+        }
+
+        i += 1;
+        j += 1;
+        if i as usize >= a_len || j as usize >= b_len {
+            lcss += local_cs;
+            local_cs = 0;
+            let cursor = i.min(j);
+            (i, j) = (cursor, cursor);
+        }
+    }
+    lcss += local_cs;
+
+    a_len.max(b_len) + transpositions - lcss
+}
+
+/// Approximates the edit distance between two strings with the common variant
+/// of Sift4, which counts transpositions.
+/// See <https://siderite.dev/blog/super-fast-and-accurate-string-distance.html>.
+///
+/// Sift4 runs in roughly linear time, so it's much faster than Levenshtein on
+/// long strings such as log lines, while usually staying close to it. Matching
+/// characters are only looked for up to `max_offset` positions ahead, so a
+/// larger offset is more accurate on heavily edited strings but slower.
+///
+/// ```
+/// use fuzzt::algorithms::sift4;
+///
+/// assert_eq!(3, sift4("kitten", "sitting", 5));
+/// assert_eq!(1, sift4("ab", "ba", 5));
+/// assert_eq!(
+///     2,
+///     sift4(
+///         "2024-01-01 ERROR disk full on /dev/sda1",
+///         "2024-01-02 ERROR disk full on /dev/sdb1",
+///         5,
+///     )
+/// );
+/// ```
+pub fn sift4(a: &str, b: &str, max_offset: usize) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    generic_sift4(&a, &b, max_offset, None)
+}
+
+/// The general variant of Sift4, which can stop early and compare tokens
+/// instead of characters.
+///
+/// ```
+/// use fuzzt::algorithms::Sift4;
+/// use fuzzt::processors::WhitespaceTokenizer;
+///
+/// let words = Sift4::new(5).with_tokenizer(&WhitespaceTokenizer);
+/// assert_eq!(2, words.distance("the quick brown fox", "quick the brown dog"));
+///
+/// // the distance is known to exceed 2 before the end of the strings
+/// let bounded = Sift4::new(5).with_max_distance(2);
+/// assert!(bounded.distance("This is the first string", "Thwas the first qeng") > 2);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Sift4<'t> {
+    max_offset: usize,
+    max_distance: Option<usize>,
+    tokenizer: Option<&'t dyn Tokenizer>,
+}
+
+impl<'t> Sift4<'t> {
+    /// Creates a metric comparing characters up to `max_offset` positions
+    /// apart.
+    pub fn new(max_offset: usize) -> Self {
+        Sift4 {
+            max_offset,
+            max_distance: None,
+            tokenizer: None,
+        }
+    }
+
+    /// Stops comparing the strings as soon as their distance is known to be
+    /// greater than `max_distance`, returning a distance greater than
+    /// `max_distance` but possibly lower than the full one.
+    pub fn with_max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = Some(max_distance);
+        self
+    }
+
+    /// Compares the tokens produced by `tokenizer` instead of characters.
+    pub fn with_tokenizer(mut self, tokenizer: &'t dyn Tokenizer) -> Self {
+        self.tokenizer = Some(tokenizer);
+        self
+    }
+
+    /// Approximates the edit distance between two strings.
+    pub fn distance(&self, a: &str, b: &str) -> usize {
+        match self.tokenizer {
+            Some(tokenizer) => generic_sift4(
+                &tokenizer.tokenize(a),
+                &tokenizer.tokenize(b),
+                self.max_offset,
+                self.max_distance,
+            ),
+            None => {
+                let a: Vec<char> = a.chars().collect();
+                let b: Vec<char> = b.chars().collect();
+                generic_sift4(&a, &b, self.max_offset, self.max_distance)
+            }
+        }
+    }
+}
+
+impl Default for Sift4<'_> {
+    /// Compares characters up to 5 positions apart, without a maximum
+    /// distance.
+    fn default() -> Self {
+        Sift4::new(5)
+    }
+}
+
+impl SimilarityMetric for Sift4<'_> {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Usize(self.distance(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sift4_empty() {
+        assert_eq!(0, sift4("", "", 5));
+        assert_eq!(3, sift4("", "abc", 5));
+        assert_eq!(3, sift4("abc", "", 5));
+    }
+
+    #[test]
+    fn sift4_offsets() {
+        let (a, b) = ("This is the first string", "Thwas the first qeng");
+        assert_eq!(8, sift4(a, b, 5));
+        assert_eq!(22, sift4(a, b, 1));
+        assert_eq!(5, sift4("abcdefghij", "badcfehgji", 5));
+        assert_eq!(6, sift4("hello world", "world hello", 5));
+        assert_eq!(0, sift4("öঙ香", "öঙ香", 0));
+    }
+
+    #[test]
+    fn sift4_max_distance() {
+        let bounded = Sift4::new(5).with_max_distance(2);
+        assert_eq!(
+            3,
+            bounded.distance("This is the first string", "Thwas the first qeng")
+        );
+        assert_eq!(3, bounded.distance("kitten", "sitting"));
+        assert!(matches!(
+            Sift4::default().compute_metric("ab", "ba"),
+            Similarity::Usize(1)
+        ));
+    }
+}
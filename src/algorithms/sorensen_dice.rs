@@ -1,10 +1,16 @@
 use crate::algorithms::{Similarity, SimilarityMetric};
 use crate::utils::bigrams;
 use std::collections::HashMap;
+use std::iter;
 
 /// Calculates a Sørensen-Dice similarity distance using bigrams.
 /// See <https://en.wikipedia.org/wiki/S%C3%B8rensen%E2%80%93Dice_coefficient>.
 ///
+/// Whitespace is ignored and bigrams are pairs of characters, so a string of
+/// `n` characters has `n - 1` bigrams however many bytes it takes, and the
+/// score is the one of [`NGramProfile::dice`](crate::algorithms::NGramProfile::dice)
+/// for bigrams.
+///
 /// ```
 /// use fuzzt::algorithms::sorensen_dice;
 ///
@@ -13,6 +19,7 @@ use std::collections::HashMap;
 /// assert_eq!(0.0, sorensen_dice("french", "quebec"));
 /// assert_eq!(1.0, sorensen_dice("ferris", "ferris"));
 /// assert_eq!(0.8888888888888888, sorensen_dice("feris", "ferris"));
+/// assert_eq!(2.0 / 3.0, sorensen_dice("café", "cafe"));
 /// ```
 pub fn sorensen_dice(a: &str, b: &str) -> f64 {
     // implementation guided by
@@ -25,28 +32,97 @@ pub fn sorensen_dice(a: &str, b: &str) -> f64 {
         return 1.0;
     }
 
-    if a.len() < 2 || b.len() < 2 {
+    bigram_dice(bigrams(&a), bigrams(&b))
+}
+
+/// Like [`sorensen_dice`], but pads both strings with `pad` before splitting
+/// them into bigrams, so differences in the first and last characters weigh
+/// as much as the others. This makes matching short strings more stable.
+///
+/// ```
+/// use fuzzt::algorithms::{sorensen_dice, sorensen_dice_padded};
+///
+/// assert_eq!(0.0, sorensen_dice("ab", "ac"));
+/// assert_eq!(1.0 / 3.0, sorensen_dice_padded("ab", "ac", '#'));
+/// assert_eq!(12.0 / 13.0, sorensen_dice_padded("feris", "ferris", '#'));
+/// ```
+pub fn sorensen_dice_padded(a: &str, b: &str, pad: char) -> f64 {
+    let a: String = a.chars().filter(|&x| !char::is_whitespace(x)).collect();
+    let b: String = b.chars().filter(|&x| !char::is_whitespace(x)).collect();
+
+    if a == b {
+        return 1.0;
+    }
+
+    if a.is_empty() || b.is_empty() {
         return 0.0;
     }
 
-    let mut a_bigrams: HashMap<(char, char), usize> = HashMap::new();
+    bigram_dice(padded_bigrams(&a, pad), padded_bigrams(&b, pad))
+}
 
-    for bigram in bigrams(&a) {
+/// Returns the bigrams of `s` with one `pad` on both sides, e.g. `#word#`.
+fn padded_bigrams(s: &str, pad: char) -> impl Iterator<Item = (char, char)> + '_ {
+    let padded = move || iter::once(pad).chain(s.chars()).chain(iter::once(pad));
+    padded().zip(padded().skip(1))
+}
+
+/// Calculates the Sørensen-Dice coefficient of two multisets of bigrams,
+/// counting them as char pairs. Returns 0.0 if either is empty.
+fn bigram_dice(
+    a: impl Iterator<Item = (char, char)>,
+    b: impl Iterator<Item = (char, char)>,
+) -> f64 {
+    let mut a_bigrams: HashMap<(char, char), usize> = HashMap::new();
+    let mut total = 0;
+    for bigram in a {
         *a_bigrams.entry(bigram).or_insert(0) += 1;
+        total += 1;
     }
+    let a_len = total;
 
-    let mut intersection_size = 0_usize;
-
-    for bigram in bigrams(&b) {
-        a_bigrams.entry(bigram).and_modify(|bi| {
-            if *bi > 0 {
-                *bi -= 1;
+    let mut intersection_size = 0;
+    for bigram in b {
+        if let Some(count) = a_bigrams.get_mut(&bigram) {
+            if *count > 0 {
+                *count -= 1;
                 intersection_size += 1;
             }
-        });
+        }
+        total += 1;
+    }
+
+    if a_len == 0 || a_len == total {
+        return 0.0;
+    }
+
+    (2 * intersection_size) as f64 / total as f64
+}
+
+/// Returns an upper bound on `sorensen_dice` computed only from the number of
+/// bigrams of each string, as if all bigrams of the shorter string were shared.
+///
+/// ```
+/// use fuzzt::algorithms::{sorensen_dice, sorensen_dice_upper_bound};
+///
+/// assert_eq!(1.0, sorensen_dice_upper_bound("french", "quebec"));
+/// assert!(sorensen_dice("iphone", "iphone x") <= sorensen_dice_upper_bound("iphone", "iphone x"));
+/// ```
+pub fn sorensen_dice_upper_bound(a: &str, b: &str) -> f64 {
+    let a_len = a.chars().filter(|&x| !char::is_whitespace(x)).count();
+    let b_len = b.chars().filter(|&x| !char::is_whitespace(x)).count();
+
+    if a_len == b_len {
+        // equal strings have a similarity of 1.0
+        return 1.0;
+    }
+
+    if a_len < 2 || b_len < 2 {
+        return 0.0;
     }
 
-    (2 * intersection_size) as f64 / (a.len() + b.len() - 2) as f64
+    let (a_bigrams, b_bigrams) = (a_len - 1, b_len - 1);
+    (2 * a_bigrams.min(b_bigrams)) as f64 / (a_bigrams + b_bigrams) as f64
 }
 
 pub struct SorensenDice;
@@ -57,9 +133,47 @@ impl SimilarityMetric for SorensenDice {
     }
 }
 
+/// Scores strings with [`sorensen_dice_padded`].
+pub struct PaddedSorensenDice {
+    pad: char,
+}
+
+impl PaddedSorensenDice {
+    /// Creates a metric padding strings with `pad`.
+    pub fn new(pad: char) -> Self {
+        PaddedSorensenDice { pad }
+    }
+}
+
+impl Default for PaddedSorensenDice {
+    /// Pads strings with `#`.
+    fn default() -> Self {
+        PaddedSorensenDice::new('#')
+    }
+}
+
+impl SimilarityMetric for PaddedSorensenDice {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(sorensen_dice_padded(a, b, self.pad))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algorithms::NGramProfile;
+
+    #[test]
+    fn sorensen_dice_counts_chars() {
+        // 2 shared bigrams out of 3 + 3, not out of the 5 + 4 - 2 bytes
+        assert_delta!(0.66667, sorensen_dice("café", "cafe"));
+        assert_delta!(0.5, sorensen_dice("öঙ香", "öঙa"));
+        for (a, b) in [("café", "cafe"), ("öঙ香a", "öঙa香"), ("healed", "sealed")] {
+            let profile = |s| NGramProfile::new(s, 2);
+            assert_delta!(profile(a).dice(&profile(b)), sorensen_dice(a, b));
+        }
+    }
+
     #[test]
     fn sorensen_dice_all() {
         // test cases taken from
@@ -113,4 +227,39 @@ mod tests {
             sorensen_dice("this has one extra word", "this has one word")
         );
     }
+
+    #[test]
+    fn sorensen_dice_padded_edges() {
+        assert_delta!(1.0, sorensen_dice_padded("", "", '#'));
+        assert_delta!(0.0, sorensen_dice_padded("", "a", '#'));
+        assert_delta!(0.4, sorensen_dice_padded("a", "ab", '#'));
+        assert_delta!(
+            1.0,
+            sorensen_dice_padded("apple event", "apple    event", '#')
+        );
+        // a difference at the edge costs as much as in the middle
+        assert_delta!(
+            sorensen_dice_padded("healed", "sealed", '#'),
+            sorensen_dice_padded("healed", "heafed", '#')
+        );
+        assert!(matches!(
+            PaddedSorensenDice::default().compute_metric("ab", "ac"),
+            Similarity::Float(r) if (r - 1.0 / 3.0).abs() < 1e-9
+        ));
+    }
+
+    #[test]
+    fn sorensen_dice_bound() {
+        let pairs = [
+            ("", ""),
+            ("a", ""),
+            ("a", "a"),
+            ("iphone", "iphone x"),
+            ("apple event", "apple    event"),
+            ("this has one extra word", "this has one word"),
+        ];
+        for (a, b) in pairs {
+            assert!(sorensen_dice(a, b) <= sorensen_dice_upper_bound(a, b));
+        }
+    }
 }
@@ -0,0 +1,222 @@
+use crate::algorithms::{NGramProfile, Similarity, SimilarityMetric};
+use crate::processors::Tokenizer;
+use std::collections::{HashMap, HashSet};
+
+/// The terms a [`TfIdfVectorizer`] extracts from strings.
+#[derive(Clone, Copy)]
+pub enum TfIdfTerms<'t> {
+    /// Overlapping n-grams of the given number of characters.
+    CharNGrams(usize),
+    /// The tokens produced by a tokenizer.
+    Tokens(&'t dyn Tokenizer),
+}
+
+impl TfIdfTerms<'_> {
+    fn counts(&self, s: &str) -> HashMap<String, usize> {
+        match *self {
+            TfIdfTerms::CharNGrams(n) => NGramProfile::new(s, n)
+                .iter()
+                .map(|(ngram, count)| (ngram.to_owned(), count))
+                .collect(),
+            TfIdfTerms::Tokens(tokenizer) => {
+                let mut counts = HashMap::new();
+                for token in tokenizer.tokenize(s) {
+                    *counts.entry(token.to_owned()).or_insert(0) += 1;
+                }
+                counts
+            }
+        }
+    }
+}
+
+/// The sparse TF-IDF vector of a string, normalized to a length of 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TfIdfVector {
+    weights: HashMap<String, f64>,
+}
+
+impl TfIdfVector {
+    /// Returns the number of distinct terms in the vector.
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Returns `true` if the string had no terms.
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Returns the weight of `term`, 0.0 if the string doesn't contain it.
+    pub fn weight(&self, term: &str) -> f64 {
+        self.weights.get(term).copied().unwrap_or(0.0)
+    }
+
+    /// Calculates the cosine similarity between both vectors. Vectors without
+    /// terms are only similar to each other.
+    pub fn cosine(&self, other: &TfIdfVector) -> f64 {
+        if self.is_empty() && other.is_empty() {
+            return 1.0;
+        }
+        let (small, large) = if self.len() <= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        small
+            .weights
+            .iter()
+            .map(|(term, weight)| weight * large.weight(term))
+            .sum::<f64>()
+            .min(1.0)
+    }
+}
+
+/// Weighs the terms of strings by how rare they are in a corpus.
+/// See <https://en.wikipedia.org/wiki/Tf%E2%80%93idf>.
+///
+/// The vectorizer is built from a corpus, usually the choices that are
+/// matched. A term occurring in `df` of the `N` strings of the corpus gets an
+/// inverse document frequency of `ln((1 + N) / (1 + df)) + 1`, so common terms
+/// such as "inc" or "ltd" in company names barely contribute to the cosine
+/// similarity of two vectors. Comparing precomputed vectors is much cheaper
+/// than computing edit distances, which makes TF-IDF a good first stage for
+/// large collections, e.g. as the retriever of a `RerankPipeline`.
+///
+/// Used as a `SimilarityMetric`, the vectorizer scores pairs with the cosine
+/// similarity of their vectors.
+///
+/// ```
+/// use fuzzt::algorithms::{TfIdfTerms, TfIdfVectorizer};
+/// use fuzzt::processors::WhitespaceTokenizer;
+///
+/// let companies = ["acme inc", "globex inc", "initech inc", "acme llc"];
+/// let vectorizer = TfIdfVectorizer::new(&companies, TfIdfTerms::Tokens(&WhitespaceTokenizer));
+///
+/// let query = vectorizer.vectorize("acme inc");
+/// let acme_llc = vectorizer.vectorize("acme llc");
+/// let globex_inc = vectorizer.vectorize("globex inc");
+/// // "inc" occurs everywhere, so "acme" matters more
+/// assert!(query.cosine(&acme_llc) > query.cosine(&globex_inc));
+/// ```
+pub struct TfIdfVectorizer<'t> {
+    terms: TfIdfTerms<'t>,
+    documents: usize,
+    frequencies: HashMap<String, usize>,
+}
+
+impl<'t> TfIdfVectorizer<'t> {
+    /// Counts in how many strings of `corpus` every term occurs.
+    ///
+    /// Panics if the terms are n-grams of 0 characters.
+    pub fn new<S>(corpus: &[S], terms: TfIdfTerms<'t>) -> Self
+    where
+        S: AsRef<str>,
+    {
+        if let TfIdfTerms::CharNGrams(n) = terms {
+            assert!(n > 0, "n-grams must contain at least one character");
+        }
+
+        let mut frequencies = HashMap::new();
+        for document in corpus {
+            let distinct: HashSet<String> = terms.counts(document.as_ref()).into_keys().collect();
+            for term in distinct {
+                *frequencies.entry(term).or_insert(0) += 1;
+            }
+        }
+
+        TfIdfVectorizer {
+            terms,
+            documents: corpus.len(),
+            frequencies,
+        }
+    }
+
+    /// Returns the number of strings in the corpus.
+    pub fn documents(&self) -> usize {
+        self.documents
+    }
+
+    /// Returns the inverse document frequency of `term`. Terms missing from
+    /// the corpus get the highest weight.
+    pub fn idf(&self, term: &str) -> f64 {
+        let frequency = self.frequencies.get(term).copied().unwrap_or(0);
+        ((1 + self.documents) as f64 / (1 + frequency) as f64).ln() + 1.0
+    }
+
+    /// Computes the TF-IDF vector of `s`.
+    pub fn vectorize(&self, s: &str) -> TfIdfVector {
+        let mut weights: HashMap<String, f64> = self
+            .terms
+            .counts(s)
+            .into_iter()
+            .map(|(term, count)| {
+                let weight = count as f64 * self.idf(&term);
+                (term, weight)
+            })
+            .collect();
+
+        let norm = weights
+            .values()
+            .map(|weight| weight * weight)
+            .sum::<f64>()
+            .sqrt();
+        for weight in weights.values_mut() {
+            *weight /= norm;
+        }
+        TfIdfVector { weights }
+    }
+
+    /// Calculates the cosine similarity between the TF-IDF vectors of both
+    /// strings.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        self.vectorize(a).cosine(&self.vectorize(b))
+    }
+}
+
+impl SimilarityMetric for TfIdfVectorizer<'_> {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        Similarity::Float(self.similarity(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::WhitespaceTokenizer;
+
+    const CORPUS: [&str; 4] = ["acme inc", "globex inc", "initech inc", "acme llc"];
+
+    #[test]
+    fn tfidf_idf() {
+        let vectorizer = TfIdfVectorizer::new(&CORPUS, TfIdfTerms::Tokens(&WhitespaceTokenizer));
+        assert_eq!(4, vectorizer.documents());
+        assert_delta!(1.0, vectorizer.idf("inc") - (5.0_f64 / 4.0).ln());
+        assert_delta!(1.0 + (5.0_f64 / 3.0).ln(), vectorizer.idf("acme"));
+        assert_delta!(1.0 + 5.0_f64.ln(), vectorizer.idf("unknown"));
+    }
+
+    #[test]
+    fn tfidf_vectors_are_normalized() {
+        let vectorizer = TfIdfVectorizer::new(&CORPUS, TfIdfTerms::CharNGrams(2));
+        let vector = vectorizer.vectorize("acme inc");
+        let norm: f64 = ["ac", "cm", "me", "e ", " i", "in", "nc"]
+            .iter()
+            .map(|term| vector.weight(term).powi(2))
+            .sum();
+        assert_eq!(7, vector.len());
+        assert_delta!(1.0, norm);
+        assert_delta!(1.0, vector.cosine(&vector));
+    }
+
+    #[test]
+    fn tfidf_similarity() {
+        let vectorizer = TfIdfVectorizer::new(&CORPUS, TfIdfTerms::CharNGrams(3));
+        assert_delta!(1.0, vectorizer.similarity("", ""));
+        assert_delta!(0.0, vectorizer.similarity("", "acme"));
+        assert_delta!(0.0, vectorizer.similarity("abc", "xyz"));
+        assert!(matches!(
+            vectorizer.compute_metric("acme", "acme inc"),
+            Similarity::Float(r) if r > 0.5 && r < 1.0
+        ));
+    }
+}
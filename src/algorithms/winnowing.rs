@@ -0,0 +1,310 @@
+use crate::algorithms::{Similarity, SimilarityMetric};
+use crate::utils::RollingHash;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Range;
+
+/// Selects document fingerprints with the winnowing algorithm of Schleimer,
+/// Wilkerson and Aiken (2003).
+///
+/// Every k-gram of characters is hashed, and in each window of `window`
+/// consecutive hashes the minimum one is kept, along with the position of its
+/// k-gram. Any passage of at least `window + k - 1` characters shared by two
+/// documents is guaranteed to produce a common fingerprint, which makes it
+/// possible to report where the documents overlap.
+///
+/// ```
+/// use fuzzt::algorithms::Winnower;
+///
+/// let winnower = Winnower::new(5, 4);
+/// let a = "It was the best of times, it was the worst of times.";
+/// let b = "Call me Ishmael. It was the best of times, indeed.";
+///
+/// let spans = winnower.fingerprint(a).shared_spans(&winnower.fingerprint(b));
+/// assert_eq!(&a[spans[0].a.clone()], &b[spans[0].b.clone()]);
+/// assert!(a[spans[0].a.clone()].contains("best of"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Winnower {
+    k: usize,
+    window: usize,
+}
+
+impl Winnower {
+    /// Creates a winnower over k-grams of `k` characters and windows of
+    /// `window` hashes.
+    ///
+    /// Panics if `k` or `window` is 0.
+    pub fn new(k: usize, window: usize) -> Self {
+        assert!(k > 0, "k-grams must contain at least one character");
+        assert!(window > 0, "windows must contain at least one hash");
+        Winnower { k, window }
+    }
+
+    /// Returns the winnowed fingerprints of `s`.
+    pub fn fingerprint(&self, s: &str) -> WinnowingFingerprint {
+        let mut rolling = RollingHash::new(self.k);
+        // byte offsets of the characters currently covered by the rolling hash
+        let mut starts: VecDeque<usize> = VecDeque::with_capacity(self.k);
+        // (hash, k-gram index, byte range) of the candidates of the current
+        // window, with increasing hashes
+        let mut minima: VecDeque<(u64, usize, Range<usize>)> = VecDeque::new();
+        let mut selected: Vec<(u64, Range<usize>)> = vec![];
+        let mut last_selected = None;
+
+        let mut kgram_index = 0;
+        for (offset, c) in s.char_indices() {
+            if starts.len() == self.k {
+                starts.pop_front();
+            }
+            starts.push_back(offset);
+
+            let hash = match rolling.push(c) {
+                Some(hash) => hash,
+                None => continue,
+            };
+            let span = starts[0]..offset + c.len_utf8();
+
+            // ties are resolved in favour of the rightmost hash
+            while minima.back().is_some_and(|(back, _, _)| *back >= hash) {
+                minima.pop_back();
+            }
+            minima.push_back((hash, kgram_index, span));
+            while minima
+                .front()
+                .is_some_and(|(_, index, _)| index + self.window <= kgram_index)
+            {
+                minima.pop_front();
+            }
+
+            if kgram_index + 1 >= self.window {
+                let (hash, index, span) = minima.front().expect("window is not empty");
+                if last_selected != Some(*index) {
+                    last_selected = Some(*index);
+                    selected.push((*hash, span.clone()));
+                }
+            }
+            kgram_index += 1;
+        }
+
+        // documents shorter than a window still get their minimum selected
+        if kgram_index > 0 && kgram_index < self.window {
+            let (hash, _, span) = minima.front().expect("at least one k-gram");
+            selected.push((*hash, span.clone()));
+        }
+
+        WinnowingFingerprint { selected }
+    }
+}
+
+impl SimilarityMetric for Winnower {
+    /// Compares the fingerprints of both strings. Strings shorter than `k`
+    /// characters have no fingerprint, so they are compared exactly instead.
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        let (a_fingerprint, b_fingerprint) = (self.fingerprint(a), self.fingerprint(b));
+        Similarity::Float(if a_fingerprint.is_empty() || b_fingerprint.is_empty() {
+            f64::from(u8::from(a == b))
+        } else {
+            a_fingerprint.overlap(&b_fingerprint)
+        })
+    }
+}
+
+/// The fingerprints selected by a [`Winnower`], with the byte range of the
+/// k-gram each of them was computed from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WinnowingFingerprint {
+    selected: Vec<(u64, Range<usize>)>,
+}
+
+/// A passage shared by two documents, as byte ranges into each of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedSpan {
+    pub a: Range<usize>,
+    pub b: Range<usize>,
+}
+
+impl WinnowingFingerprint {
+    /// Returns the number of selected fingerprints.
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    /// Returns `true` if no fingerprint was selected, i.e. the document is
+    /// shorter than `k` characters.
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// Iterates over the selected hashes and the byte ranges of their k-grams,
+    /// in document order.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, Range<usize>)> + '_ {
+        self.selected.iter().cloned()
+    }
+
+    /// Calculates the Jaccard index between the distinct fingerprints of both
+    /// documents. Empty fingerprints share no hash, so they score 0.0 even
+    /// against each other.
+    pub fn overlap(&self, other: &WinnowingFingerprint) -> f64 {
+        let a: HashSet<u64> = self.selected.iter().map(|(hash, _)| *hash).collect();
+        let b: HashSet<u64> = other.selected.iter().map(|(hash, _)| *hash).collect();
+        let intersection = a.intersection(&b).count();
+        let union = a.len() + b.len() - intersection;
+        if union == 0 {
+            return 0.0;
+        }
+        intersection as f64 / union as f64
+    }
+
+    /// Returns the passages both documents have in common, ordered by their
+    /// position in the first document.
+    ///
+    /// Every shared fingerprint yields the k-gram it was computed from, and
+    /// k-grams that overlap or touch in both documents at the same relative
+    /// offset are merged into a single span. Fingerprints don't keep the text
+    /// of the documents, so k-grams are only compared by hash: a hash
+    /// collision between different k-grams is reported as a shared span too.
+    pub fn shared_spans(&self, other: &WinnowingFingerprint) -> Vec<SharedSpan> {
+        let mut positions: HashMap<u64, Vec<&Range<usize>>> = HashMap::new();
+        for (hash, span) in &other.selected {
+            positions.entry(*hash).or_default().push(span);
+        }
+
+        let mut matches: Vec<SharedSpan> = vec![];
+        for (hash, a_span) in &self.selected {
+            for b_span in positions.get(hash).into_iter().flatten() {
+                matches.push(SharedSpan {
+                    a: a_span.clone(),
+                    b: (*b_span).clone(),
+                });
+            }
+        }
+        matches.sort_by_key(|span| (span.a.start, span.b.start));
+
+        // a span can only extend the last one merged at the same relative
+        // offset, its diagonal: spans are sorted, so that one ends furthest
+        let mut merged: Vec<SharedSpan> = vec![];
+        let mut last_on_diagonal: HashMap<isize, usize> = HashMap::new();
+        for span in matches {
+            let diagonal = span.a.start as isize - span.b.start as isize;
+            match last_on_diagonal.get(&diagonal) {
+                Some(&last) if span.a.start <= merged[last].a.end => {
+                    let last = &mut merged[last];
+                    last.a.end = last.a.end.max(span.a.end);
+                    last.b.end = last.b.end.max(span.b.end);
+                }
+                _ => {
+                    last_on_diagonal.insert(diagonal, merged.len());
+                    merged.push(span);
+                }
+            }
+        }
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn winnowing_selects_one_hash_per_window() {
+        let winnower = Winnower::new(3, 4);
+        let text = "the quick brown fox jumps over the lazy dog";
+        let fingerprint = winnower.fingerprint(text);
+        let kgrams = text.chars().count() - 2;
+        assert!(fingerprint.len() >= kgrams / 4);
+        assert!(fingerprint.len() <= kgrams);
+
+        // every window of 4 consecutive k-grams contains a selected one
+        let starts: Vec<usize> = fingerprint.iter().map(|(_, span)| span.start).collect();
+        for window_start in 0..=kgrams - 4 {
+            assert!(starts
+                .iter()
+                .any(|&start| (window_start..window_start + 4).contains(&start)));
+        }
+    }
+
+    #[test]
+    fn winnowing_short_documents() {
+        let winnower = Winnower::new(4, 3);
+        assert!(winnower.fingerprint("abc").is_empty());
+        assert_eq!(1, winnower.fingerprint("abcd").len());
+        assert_eq!(1, winnower.fingerprint("abcde").len());
+    }
+
+    #[test]
+    fn winnowing_overlap() {
+        let winnower = Winnower::new(4, 3);
+        let text = "a passage that is identical";
+        assert_delta!(
+            1.0,
+            winnower
+                .fingerprint(text)
+                .overlap(&winnower.fingerprint(text))
+        );
+        assert_delta!(
+            0.0,
+            winnower
+                .fingerprint("aaaaaaaa")
+                .overlap(&winnower.fingerprint("bbbbbbbb"))
+        );
+    }
+
+    #[test]
+    fn winnowing_metric_short_strings() {
+        let winnower = Winnower::new(4, 3);
+        let empty = WinnowingFingerprint::default();
+        assert_delta!(0.0, empty.overlap(&empty));
+        assert_eq!(
+            Similarity::Float(0.0),
+            winnower.compute_metric("abc", "xyz")
+        );
+        assert_eq!(
+            Similarity::Float(1.0),
+            winnower.compute_metric("abc", "abc")
+        );
+        assert_eq!(
+            Similarity::Float(0.0),
+            winnower.compute_metric("abc", "abc is longer")
+        );
+    }
+
+    #[test]
+    fn winnowing_shared_spans_multibyte() {
+        let winnower = Winnower::new(3, 2);
+        let a = "xx öঙ香 shared passage yy";
+        let b = "öঙ香 shared passage";
+        let spans = winnower
+            .fingerprint(a)
+            .shared_spans(&winnower.fingerprint(b));
+        assert_eq!(1, spans.len());
+        assert_eq!(&a[spans[0].a.clone()], &b[spans[0].b.clone()]);
+        assert!(spans[0].a.end - spans[0].a.start >= "shared passage".len());
+    }
+
+    #[test]
+    fn winnowing_shared_spans_per_diagonal() {
+        let winnower = Winnower::new(3, 2);
+        let a = "abcdefgh abcdefgh";
+        let b = "abcdefgh";
+        let spans = winnower
+            .fingerprint(a)
+            .shared_spans(&winnower.fingerprint(b));
+        let starts: Vec<usize> = spans.iter().map(|span| span.a.start).collect();
+        // one span for each copy of the passage
+        assert_eq!(2, starts.len());
+        assert_eq!(9, starts[1] - starts[0]);
+        for span in spans {
+            assert_eq!(&a[span.a.clone()], &b[span.b.clone()]);
+        }
+    }
+
+    #[test]
+    fn winnowing_no_shared_spans() {
+        let winnower = Winnower::new(3, 2);
+        let spans = winnower
+            .fingerprint("abcdefgh")
+            .shared_spans(&winnower.fingerprint("stuvwxyz"));
+        assert!(spans.is_empty());
+    }
+}
@@ -0,0 +1,300 @@
+use crate::{
+    algorithms::SimilarityMetric,
+    matcher::{
+        compare_matches, TieBreak, DEFAULT_CUTOFF, DEFAULT_N, DEFAULT_PROCESSOR, DEFAULT_SCORER,
+    },
+    processors::StringProcessor,
+};
+
+/// Boost given to the choices starting with the query when none is set.
+const DEFAULT_PREFIX_BOOST: f64 = 0.5;
+
+/// A choice suggested by an [`AutocompleteSession`] or found by the functions
+/// of [`crate::process`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchResult<'a> {
+    /// The suggested choice.
+    pub choice: &'a str,
+    /// The index of the choice in the collection it was found in.
+    pub index: usize,
+    /// The score of the choice, including its prefix boost in the suggestions
+    /// of a session.
+    pub score: f64,
+}
+
+/// Suggests choices as a query is typed, one keystroke at a time.
+///
+/// The choices are processed once when the session is created, rather than on
+/// every call. Choices starting with the query are boosted, since they are
+/// usually what the user is typing: their score `s` becomes
+/// `s + prefix_boost * (1 - s)`. Distances are normalized by the length of the
+/// longer string first, so every score is between 0.0 and 1.0.
+///
+/// The session remembers the previous query. Calling [`update`] again with the
+/// same query returns the cached suggestions. When the query extends the
+/// previous one, as it does while typing, only the choices that started with
+/// the previous query are checked for the new prefix. Every choice is still
+/// scored again, since a choice scoring below the cutoff for a short query may
+/// score above it once the query is longer, so typing a query one keystroke
+/// at a time suggests the same choices as typing it at once.
+///
+/// [`update`]: AutocompleteSession::update
+///
+/// ```
+/// use fuzzt::{algorithms::NormalizedLevenshtein, AutocompleteSession};
+///
+/// let commands = ["open file", "open folder", "close file", "copy path"];
+/// let mut session = AutocompleteSession::new(&commands, None, Some(&NormalizedLevenshtein))
+///     .with_cutoff(0.5);
+///
+/// let suggestions = session.update("open f");
+/// let choices: Vec<&str> = suggestions.iter().map(|m| m.choice).collect();
+/// assert_eq!(choices, ["open file", "open folder"]);
+///
+/// let suggestions = session.update("open fo");
+/// assert_eq!(suggestions[0].choice, "open folder");
+/// assert_eq!(suggestions[0].index, 1);
+/// ```
+pub struct AutocompleteSession<'a, 'm> {
+    choices: Vec<&'a str>,
+    processed: Vec<String>,
+    processor: &'m dyn StringProcessor,
+    scorer: &'m dyn SimilarityMetric,
+    cutoff: f64,
+    n: usize,
+    prefix_boost: f64,
+    previous: Option<PreviousQuery<'a>>,
+}
+
+/// What an [`AutocompleteSession`] remembers of the previous query.
+struct PreviousQuery<'a> {
+    // the query once processed
+    query: String,
+    // the choices starting with the query
+    prefixed: Vec<usize>,
+    results: Vec<MatchResult<'a>>,
+}
+
+impl<'a, 'm> AutocompleteSession<'a, 'm> {
+    /// Creates a session suggesting `choices`.
+    ///
+    /// Queries and choices are passed through `processor` before they are
+    /// compared with `scorer`. If not provided, `NullStringProcessor` and
+    /// `SequenceMatcher` are used. The session returns at most 3 suggestions
+    /// scoring at least 0.7, and boosts the choices starting with the query
+    /// by 0.5, until configured otherwise.
+    pub fn new<S>(
+        choices: &'a [S],
+        processor: Option<&'m dyn StringProcessor>,
+        scorer: Option<&'m dyn SimilarityMetric>,
+    ) -> Self
+    where
+        S: AsRef<str>,
+    {
+        let processor = processor.unwrap_or(DEFAULT_PROCESSOR);
+        let choices: Vec<&str> = choices.iter().map(AsRef::as_ref).collect();
+        let processed = choices
+            .iter()
+            .map(|choice| processor.process(choice))
+            .collect();
+        AutocompleteSession {
+            choices,
+            processed,
+            processor,
+            scorer: scorer.unwrap_or(DEFAULT_SCORER),
+            cutoff: DEFAULT_CUTOFF,
+            n: DEFAULT_N,
+            prefix_boost: DEFAULT_PREFIX_BOOST,
+            previous: None,
+        }
+    }
+
+    /// Only suggests the choices scoring at least `cutoff`, once boosted.
+    pub fn with_cutoff(mut self, cutoff: f64) -> Self {
+        self.cutoff = cutoff;
+        self.previous = None;
+        self
+    }
+
+    /// Returns at most `n` suggestions.
+    pub fn with_n(mut self, n: usize) -> Self {
+        self.n = n;
+        self.previous = None;
+        self
+    }
+
+    /// Sets how much the choices starting with the query are boosted, from
+    /// 0.0 for no boost to 1.0 for a perfect score.
+    ///
+    /// Panics if `prefix_boost` isn't between 0.0 and 1.0.
+    pub fn with_prefix_boost(mut self, prefix_boost: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&prefix_boost),
+            "the prefix boost must be between 0.0 and 1.0"
+        );
+        self.prefix_boost = prefix_boost;
+        self.previous = None;
+        self
+    }
+
+    /// Returns the number of choices.
+    pub fn len(&self) -> usize {
+        self.choices.len()
+    }
+
+    /// Returns `true` if there are no choices.
+    pub fn is_empty(&self) -> bool {
+        self.choices.is_empty()
+    }
+
+    /// Returns the best suggestions for `query`, ordered by decreasing score.
+    /// Ties are ordered as described by [`TieBreak::Lexicographic`].
+    pub fn update(&mut self, query: &str) -> Vec<MatchResult<'a>> {
+        let query = self.processor.process(query);
+        let starts_with_query = |&index: &usize| self.processed[index].starts_with(query.as_str());
+        let prefixed: Vec<usize> = match &self.previous {
+            Some(previous) if previous.query == query => return previous.results.clone(),
+            // choices starting with the query also start with the previous one
+            Some(previous) if query.starts_with(previous.query.as_str()) => previous
+                .prefixed
+                .iter()
+                .copied()
+                .filter(starts_with_query)
+                .collect(),
+            _ => (0..self.choices.len()).filter(starts_with_query).collect(),
+        };
+
+        let mut boosted = vec![false; self.choices.len()];
+        for &index in &prefixed {
+            boosted[index] = true;
+        }
+        let query_len = query.chars().count();
+        let mut results: Vec<MatchResult<'a>> = (0..self.choices.len())
+            .filter_map(|index| {
+                let processed = &self.processed[index];
+                let max_len = query_len.max(processed.chars().count());
+                let mut score = self
+                    .scorer
+                    .compute_metric(&query, processed)
+                    .normalize(max_len);
+                if boosted[index] {
+                    score += self.prefix_boost * (1.0 - score);
+                }
+                (score >= self.cutoff).then_some(MatchResult {
+                    choice: self.choices[index],
+                    index,
+                    score,
+                })
+            })
+            .collect();
+        results.sort_by(|a, b| {
+            compare_matches(
+                TieBreak::Lexicographic,
+                (a.score, a.choice, a.index),
+                (b.score, b.choice, b.index),
+            )
+        });
+        results.truncate(self.n);
+
+        self.previous = Some(PreviousQuery {
+            query,
+            prefixed,
+            results: results.clone(),
+        });
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{Levenshtein, NormalizedLevenshtein};
+    use crate::processors::LowerAlphaNumStringProcessor;
+
+    const CITIES: [&str; 5] = ["Paris", "Parma", "Porto", "Prague", "Lisbon"];
+
+    fn choices<'a>(results: &[MatchResult<'a>]) -> Vec<&'a str> {
+        results.iter().map(|m| m.choice).collect()
+    }
+
+    #[test]
+    fn session_boosts_prefixes() {
+        let mut session = AutocompleteSession::new(&CITIES, None, Some(&NormalizedLevenshtein))
+            .with_cutoff(0.0)
+            .with_n(10);
+        let results = session.update("Par");
+        assert_eq!(
+            choices(&results),
+            ["Paris", "Parma", "Porto", "Prague", "Lisbon"]
+        );
+        // 0.6 boosted by half of the remaining 0.4
+        assert!((results[0].score - 0.8).abs() < 1e-9);
+        assert!((results[2].score - 0.4).abs() < 1e-9);
+
+        let mut session = AutocompleteSession::new(&CITIES, None, Some(&NormalizedLevenshtein))
+            .with_prefix_boost(0.0)
+            .with_cutoff(0.5);
+        assert_eq!(choices(&session.update("Par")), ["Paris", "Parma"]);
+        let mut session = AutocompleteSession::new(&CITIES, None, Some(&NormalizedLevenshtein))
+            .with_prefix_boost(1.0);
+        assert_eq!(choices(&session.update("P")), ["Paris", "Parma", "Porto"]);
+    }
+
+    #[test]
+    fn session_caches_previous_query() {
+        let mut session = AutocompleteSession::new(
+            &CITIES,
+            Some(&LowerAlphaNumStringProcessor),
+            Some(&Levenshtein),
+        )
+        .with_cutoff(0.0);
+        assert_eq!(choices(&session.update("p")), ["Paris", "Parma", "Porto"]);
+        assert_eq!(session.previous.as_ref().unwrap().prefixed, [0, 1, 2, 3]);
+        assert_eq!(choices(&session.update("pa")), ["Paris", "Parma", "Prague"]);
+        assert_eq!(session.previous.as_ref().unwrap().prefixed, [0, 1]);
+        // the processed query is the same
+        assert_eq!(choices(&session.update("PA")), ["Paris", "Parma", "Prague"]);
+        assert_eq!(choices(&session.update("pri")), ["Paris", "Parma", "Porto"]);
+        assert_eq!(
+            session.previous.as_ref().unwrap().prefixed,
+            [] as [usize; 0]
+        );
+        assert_eq!(choices(&session.update("")), ["Lisbon", "Paris", "Parma"]);
+    }
+
+    #[test]
+    fn session_rescores_dropped_choices() {
+        let mut session = AutocompleteSession::new(&CITIES, None, Some(&NormalizedLevenshtein))
+            .with_prefix_boost(0.0)
+            .with_cutoff(0.5)
+            .with_n(1);
+        assert_eq!(choices(&session.update("Par")), ["Paris"]);
+        assert_eq!(choices(&session.update("Parm")), ["Parma"]);
+
+        assert!(session.update("X").is_empty());
+        // "Lisbon" scores 0.83 even though it was dropped for "X"
+        assert_eq!(choices(&session.update("Xisbon")), ["Lisbon"]);
+    }
+
+    #[test]
+    fn session_typing_matches_fresh_query() {
+        let commands = ["the open file", "close all", "reopen file"];
+        let mut typed = AutocompleteSession::new(&commands, None, None);
+        let query = "open file";
+        let mut results = vec![];
+        for end in 1..=query.len() {
+            results = typed.update(&query[..end]);
+        }
+        let mut fresh = AutocompleteSession::new(&commands, None, None);
+        assert_eq!(results, fresh.update(query));
+        assert_eq!(choices(&results), ["reopen file", "the open file"]);
+    }
+
+    #[test]
+    fn session_without_choices() {
+        let choices: [&str; 0] = [];
+        let mut session = AutocompleteSession::new(&choices, None, None);
+        assert!(session.is_empty());
+        assert!(session.update("abc").is_empty());
+    }
+}
@@ -0,0 +1,210 @@
+//! Approximate search of a pattern in a text with the bitap algorithm.
+//!
+//! The metrics of the crate compare whole strings. Bitap instead finds where
+//! a short pattern occurs in a longer text, allowing up to `max_errors`
+//! insertions, deletions and substitutions, e.g. to build grep-like tools.
+//! It uses the shift-and formulation of Wu and Manber (1992), which keeps one
+//! bit vector per number of errors and processes each character of the text
+//! in `O(max_errors)` bit operations.
+//!
+//! ```
+//! use fuzzt::bitap::bitap;
+//!
+//! let text = "the quick brown fox";
+//! let ends = bitap("quikc", text, 2).unwrap();
+//! assert!(ends.contains(&9));
+//! assert_eq!(&text[4..9], "quick");
+//! ```
+
+use crate::utils::FuzztError;
+use std::collections::HashMap;
+
+/// The maximum number of characters of a pattern, which must fit in the bits
+/// of a machine word.
+pub const MAX_PATTERN_LEN: usize = 64;
+
+/// The end of an approximate occurrence of a pattern in a text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitapMatch {
+    /// The byte offset of the end of the occurrence, just past its last
+    /// character.
+    pub end: usize,
+    /// The smallest number of errors of an occurrence ending there.
+    pub errors: usize,
+}
+
+/// A pattern prepared for approximate searches.
+///
+/// ```
+/// use fuzzt::bitap::{Bitap, BitapMatch};
+///
+/// let bitap = Bitap::new("colour", 1).unwrap();
+/// let matches = bitap.search("what color is it");
+/// assert_eq!(matches, [BitapMatch { end: 10, errors: 1 }]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bitap {
+    // bit `i` of the mask of a character is set if it is the `i`th character
+    // of the pattern
+    masks: HashMap<char, u64>,
+    len: usize,
+    max_errors: usize,
+}
+
+impl Bitap {
+    /// Prepares the search of `pattern` with up to `max_errors` errors.
+    ///
+    /// Returns `FuzztError::InputTooLong` if the pattern is longer than
+    /// [`MAX_PATTERN_LEN`] characters.
+    pub fn new(pattern: &str, max_errors: usize) -> Result<Self, FuzztError> {
+        let mut masks: HashMap<char, u64> = HashMap::new();
+        let mut len = 0;
+        for (i, c) in pattern.chars().enumerate() {
+            if i == MAX_PATTERN_LEN {
+                return Err(FuzztError::InputTooLong);
+            }
+            *masks.entry(c).or_insert(0) |= 1 << i;
+            len += 1;
+        }
+        Ok(Bitap {
+            masks,
+            len,
+            // every occurrence needs at most one error per character
+            max_errors: max_errors.min(len),
+        })
+    }
+
+    /// Returns the ends of the occurrences of the pattern in `text`, in
+    /// increasing order. An occurrence can end after any character of the
+    /// text; an empty pattern ends after every character.
+    pub fn search(&self, text: &str) -> Vec<BitapMatch> {
+        let mut matches = vec![];
+        if self.len == 0 {
+            for (offset, c) in text.char_indices() {
+                matches.push(BitapMatch {
+                    end: offset + c.len_utf8(),
+                    errors: 0,
+                });
+            }
+            return matches;
+        }
+
+        let found = 1 << (self.len - 1);
+        // bit `i` of `states[d]` is set if the first `i + 1` characters of
+        // the pattern match the end of the text read so far with at most `d`
+        // errors. Before any character is read, only deletions match.
+        let mut states: Vec<u64> = (0..=self.max_errors)
+            .map(|d| if d >= 64 { !0 } else { (1 << d) - 1 })
+            .collect();
+
+        for (offset, c) in text.char_indices() {
+            let mask = self.masks.get(&c).copied().unwrap_or(0);
+            let mut previous = states[0];
+            states[0] = ((states[0] << 1) | 1) & mask;
+            for d in 1..states.len() {
+                let old = states[d];
+                // matches, insertions of `c`, substitutions and deletions
+                states[d] =
+                    (((old << 1) | 1) & mask) | previous | (((previous | states[d - 1]) << 1) | 1);
+                previous = old;
+            }
+
+            if let Some(errors) = states.iter().position(|state| state & found != 0) {
+                matches.push(BitapMatch {
+                    end: offset + c.len_utf8(),
+                    errors,
+                });
+            }
+        }
+        matches
+    }
+}
+
+/// Returns the byte offsets of the ends of the occurrences of `pattern` in
+/// `text` with up to `max_errors` errors, see [`Bitap`].
+///
+/// Returns `FuzztError::InputTooLong` if the pattern is longer than
+/// [`MAX_PATTERN_LEN`] characters.
+///
+/// ```
+/// use fuzzt::bitap::bitap;
+///
+/// assert_eq!(Ok(vec![7]), bitap("fox", "the fox, the box", 0));
+///
+/// let ends = bitap("fox", "the fox, the box", 1).unwrap();
+/// assert!(ends.contains(&16));
+/// ```
+pub fn bitap(pattern: &str, text: &str, max_errors: usize) -> Result<Vec<usize>, FuzztError> {
+    let bitap = Bitap::new(pattern, max_errors)?;
+    Ok(bitap
+        .search(text)
+        .into_iter()
+        .map(|found| found.end)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the smallest distance between the pattern and a substring of the text
+    // ending after each character, computed with the dynamic programming of
+    // Sellers (1980)
+    fn sellers(pattern: &str, text: &str) -> Vec<usize> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let mut column: Vec<usize> = (0..=pattern.len()).collect();
+        let mut distances = vec![];
+        for c in text.chars() {
+            let mut next = vec![0; pattern.len() + 1];
+            for i in 1..=pattern.len() {
+                let cost = usize::from(pattern[i - 1] != c);
+                next[i] = (column[i - 1] + cost)
+                    .min(column[i] + 1)
+                    .min(next[i - 1] + 1);
+            }
+            distances.push(next[pattern.len()]);
+            column = next;
+        }
+        distances
+    }
+
+    #[test]
+    fn bitap_matches_dynamic_programming() {
+        let cases = [
+            ("abc", "xxabcxxabxcxxaxc"),
+            ("hello", "say helo to the yellow fellow"),
+            ("öঙ香", "aöঙ香bö香c"),
+            ("aaaa", "aaabaaaaa"),
+            ("a", "bab"),
+        ];
+        for (pattern, text) in cases {
+            let distances = sellers(pattern, text);
+            for max_errors in 0..=pattern.chars().count() + 1 {
+                let bitap = Bitap::new(pattern, max_errors).unwrap();
+                let expected: Vec<BitapMatch> = text
+                    .char_indices()
+                    .zip(&distances)
+                    .filter(|(_, &distance)| distance <= max_errors)
+                    .map(|((offset, c), &distance)| BitapMatch {
+                        end: offset + c.len_utf8(),
+                        errors: distance,
+                    })
+                    .collect();
+                assert_eq!(expected, bitap.search(text), "{pattern} {max_errors}");
+            }
+        }
+    }
+
+    #[test]
+    fn bitap_edge_cases() {
+        assert_eq!(Ok(vec![]), bitap("abc", "", 1));
+        assert_eq!(Ok(vec![1, 3]), bitap("", "aö", 0));
+        let long = "a".repeat(MAX_PATTERN_LEN);
+        assert_eq!(Ok(vec![64]), bitap(&long, &"a".repeat(64), 0));
+        assert_eq!(Ok(vec![63, 64]), bitap(&long, &"a".repeat(64), 1));
+        assert_eq!(
+            Err(FuzztError::InputTooLong),
+            bitap(&format!("{long}a"), "a", 0)
+        );
+    }
+}
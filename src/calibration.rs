@@ -0,0 +1,366 @@
+//! Helpers for choosing the cutoff of a metric from example pairs, and for
+//! understanding how the scores of a query are distributed over a corpus.
+
+use crate::algorithms::{Similarity, SimilarityMetric};
+
+/// The number of pairs scored at or above a threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepPoint {
+    pub threshold: f64,
+    pub matches: usize,
+}
+
+/// The matches at a threshold, compared with the expected labels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabeledSweepPoint {
+    pub threshold: f64,
+    pub matches: usize,
+    pub true_positives: usize,
+    /// Fraction of the matches that are labelled as matching. 1.0 if nothing
+    /// matched.
+    pub precision: f64,
+    /// Fraction of the pairs labelled as matching that matched. 1.0 if no pair
+    /// is labelled as matching.
+    pub recall: f64,
+}
+
+fn thresholds(scores: &[f64], steps: usize) -> Vec<f64> {
+    let min = scores.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if scores.is_empty() {
+        return vec![];
+    }
+    if steps == 0 || min == max {
+        return vec![min];
+    }
+    // the last threshold is exactly the highest score, which rounding could
+    // otherwise overshoot
+    (0..=steps)
+        .map(|step| {
+            if step == steps {
+                max
+            } else {
+                min + (max - min) * step as f64 / steps as f64
+            }
+        })
+        .collect()
+}
+
+/// Scores every pair, returning the scores and whether they are distances.
+fn pair_scores<'p, I>(pairs: I, scorer: &dyn SimilarityMetric) -> (Vec<f64>, bool)
+where
+    I: IntoIterator<Item = (&'p str, &'p str)>,
+{
+    let results: Vec<Similarity> = pairs
+        .into_iter()
+        .map(|(a, b)| scorer.compute_metric(a, b))
+        .collect();
+    let distance = results.first().is_some_and(Similarity::is_distance);
+    (results.iter().map(Similarity::as_f64).collect(), distance)
+}
+
+/// Returns `true` if `score` matches `threshold`: if it is at least the
+/// threshold, or at most the threshold for distances.
+fn is_match(score: f64, threshold: f64, distance: bool) -> bool {
+    if distance {
+        score <= threshold
+    } else {
+        score >= threshold
+    }
+}
+
+/// Scores every pair and counts how many would match at `steps + 1`
+/// thresholds evenly spread between the lowest and the highest score.
+///
+/// A pair matches a threshold if its score is at least the threshold, or at
+/// most the threshold if the scorer returns distances, so a threshold can be
+/// used as is as the cutoff of `get_top_n`. The points are ordered by
+/// increasing threshold and are suitable for plotting.
+///
+/// ```
+/// use fuzzt::algorithms::NormalizedLevenshtein;
+/// use fuzzt::calibration::threshold_sweep;
+///
+/// let pairs = [("kitten", "sitting"), ("kitten", "kitten"), ("kitten", "mitten")];
+/// let sweep = threshold_sweep(&pairs, &NormalizedLevenshtein, 2);
+/// let matches: Vec<usize> = sweep.iter().map(|point| point.matches).collect();
+/// assert_eq!(matches, [3, 2, 1]);
+/// ```
+pub fn threshold_sweep(
+    pairs: &[(&str, &str)],
+    scorer: &dyn SimilarityMetric,
+    steps: usize,
+) -> Vec<SweepPoint> {
+    let (scores, distance) = pair_scores(pairs.iter().copied(), scorer);
+    thresholds(&scores, steps)
+        .into_iter()
+        .map(|threshold| SweepPoint {
+            threshold,
+            matches: scores
+                .iter()
+                .filter(|&&score| is_match(score, threshold, distance))
+                .count(),
+        })
+        .collect()
+}
+
+/// Like [`threshold_sweep`], but every pair carries a label telling whether it
+/// should match, and each point reports the precision and recall of the
+/// threshold.
+///
+/// ```
+/// use fuzzt::algorithms::NormalizedLevenshtein;
+/// use fuzzt::calibration::threshold_sweep_labeled;
+///
+/// let pairs = [
+///     ("kitten", "sitting", false),
+///     ("kitten", "kitten", true),
+///     ("kitten", "mitten", true),
+/// ];
+/// let sweep = threshold_sweep_labeled(&pairs, &NormalizedLevenshtein, 2);
+/// assert_eq!(2.0 / 3.0, sweep[0].precision);
+/// assert_eq!(1.0, sweep[1].precision);
+/// assert_eq!(1.0, sweep[1].recall);
+/// assert_eq!(0.5, sweep[2].recall);
+/// ```
+pub fn threshold_sweep_labeled(
+    pairs: &[(&str, &str, bool)],
+    scorer: &dyn SimilarityMetric,
+    steps: usize,
+) -> Vec<LabeledSweepPoint> {
+    let (scores, distance) = pair_scores(pairs.iter().map(|&(a, b, _)| (a, b)), scorer);
+    let positives = pairs.iter().filter(|(_, _, label)| *label).count();
+    thresholds(&scores, steps)
+        .into_iter()
+        .map(|threshold| {
+            let mut matches = 0;
+            let mut true_positives = 0;
+            for (score, (_, _, label)) in scores.iter().zip(pairs) {
+                if is_match(*score, threshold, distance) {
+                    matches += 1;
+                    true_positives += usize::from(*label);
+                }
+            }
+            LabeledSweepPoint {
+                threshold,
+                matches,
+                true_positives,
+                precision: if matches == 0 {
+                    1.0
+                } else {
+                    true_positives as f64 / matches as f64
+                },
+                recall: if positives == 0 {
+                    1.0
+                } else {
+                    true_positives as f64 / positives as f64
+                },
+            }
+        })
+        .collect()
+}
+
+/// The scores of a query against every string of a corpus, as returned by
+/// [`score_distribution`].
+///
+/// Comparing the score of a candidate with the rest of the distribution tells
+/// whether it's unusually similar to the query, e.g. to flag names resembling
+/// a watchlist entry much more than the background population does. Scores
+/// are the values of the metric as they are, so for distances a lower score
+/// means a closer match.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreDistribution {
+    // sorted in increasing order
+    scores: Vec<f64>,
+}
+
+impl ScoreDistribution {
+    /// Returns the number of scores.
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    /// Returns `true` if the corpus was empty.
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    /// Returns the lowest score.
+    pub fn min(&self) -> Option<f64> {
+        self.scores.first().copied()
+    }
+
+    /// Returns the highest score.
+    pub fn max(&self) -> Option<f64> {
+        self.scores.last().copied()
+    }
+
+    /// Returns the average score.
+    pub fn mean(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.scores.iter().sum::<f64>() / self.len() as f64)
+    }
+
+    /// Returns the score below which `p` percent of the scores fall,
+    /// interpolating linearly between the closest scores.
+    ///
+    /// Panics if `p` isn't between 0 and 100.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        assert!(
+            (0.0..=100.0).contains(&p),
+            "percentiles are between 0 and 100"
+        );
+        if self.is_empty() {
+            return None;
+        }
+        let position = p / 100.0 * (self.len() - 1) as f64;
+        let lower = self.scores[position.floor() as usize];
+        let upper = self.scores[position.ceil() as usize];
+        Some(lower + (upper - lower) * position.fract())
+    }
+
+    /// Returns the fraction of the scores that are lower than `score`, between
+    /// 0.0 and 1.0. Returns 0.0 if the corpus was empty.
+    pub fn rank(&self, score: f64) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        self.scores.partition_point(|&other| other < score) as f64 / self.len() as f64
+    }
+
+    /// Counts the scores in `bins` bins of equal width between the lowest and
+    /// the highest score. The highest score falls in the last bin, and all
+    /// scores fall in the first bin if they are equal.
+    ///
+    /// Panics if `bins` is 0.
+    pub fn histogram(&self, bins: usize) -> Vec<usize> {
+        assert!(bins > 0, "the histogram must have at least one bin");
+        let mut histogram = vec![0; bins];
+        let (Some(min), Some(max)) = (self.min(), self.max()) else {
+            return histogram;
+        };
+        for &score in &self.scores {
+            let bin = if max == min {
+                0
+            } else {
+                ((score - min) / (max - min) * bins as f64) as usize
+            };
+            histogram[bin.min(bins - 1)] += 1;
+        }
+        histogram
+    }
+}
+
+/// Scores `query` against every string of `corpus` and returns the
+/// distribution of the scores.
+///
+/// Scoring the same corpus against many queries is a good use case for
+/// `CachedMetric` if the corpus contains many repeated strings.
+///
+/// ```
+/// use fuzzt::algorithms::Levenshtein;
+/// use fuzzt::calibration::score_distribution;
+///
+/// let corpus = ["kitten", "sitten", "sitting", "mitten", "knitting"];
+/// let distribution = score_distribution("kitten", &corpus, &Levenshtein);
+/// assert_eq!(Some(1.6), distribution.mean());
+/// assert_eq!(Some(1.0), distribution.percentile(50.0));
+/// assert_eq!(distribution.histogram(3), [1, 2, 2]);
+/// // only the exact match is closer than a single edit
+/// assert_eq!(0.2, distribution.rank(1.0));
+/// ```
+pub fn score_distribution<S>(
+    query: &str,
+    corpus: &[S],
+    scorer: &dyn SimilarityMetric,
+) -> ScoreDistribution
+where
+    S: AsRef<str>,
+{
+    let mut scores: Vec<f64> = corpus
+        .iter()
+        .map(|choice| scorer.compute_metric(query, choice.as_ref()).as_f64())
+        .collect();
+    scores.sort_by(f64::total_cmp);
+    ScoreDistribution { scores }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{Levenshtein, NormalizedLevenshtein, SequenceMatcher};
+
+    #[test]
+    fn threshold_sweep_empty_and_constant() {
+        assert!(threshold_sweep(&[], &SequenceMatcher, 10).is_empty());
+        let sweep = threshold_sweep(&[("a", "a"), ("b", "b")], &SequenceMatcher, 10);
+        assert_eq!(
+            sweep,
+            [SweepPoint {
+                threshold: 1.0,
+                matches: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn threshold_sweep_distances() {
+        let pairs = [
+            ("kitten", "sitting"),
+            ("kitten", "kitten"),
+            ("ab", "abcdef"),
+        ];
+        let sweep = threshold_sweep(&pairs, &Levenshtein, 4);
+        let thresholds: Vec<f64> = sweep.iter().map(|point| point.threshold).collect();
+        assert_eq!(thresholds, [0.0, 1.0, 2.0, 3.0, 4.0]);
+        let matches: Vec<usize> = sweep.iter().map(|point| point.matches).collect();
+        assert_eq!(matches, [1, 1, 1, 2, 3]);
+    }
+
+    #[test]
+    fn threshold_sweep_ends_at_highest_score() {
+        let sweep = threshold_sweep(
+            &[("ab", "cd"), ("abcde", "abcdx")],
+            &NormalizedLevenshtein,
+            3,
+        );
+        assert_eq!(0.8, sweep[3].threshold);
+        assert_eq!(1, sweep[3].matches);
+    }
+
+    #[test]
+    fn threshold_sweep_labeled_without_positives() {
+        let sweep = threshold_sweep_labeled(&[("a", "b", false)], &SequenceMatcher, 1);
+        assert_eq!(1, sweep.len());
+        assert_eq!(0.0, sweep[0].precision);
+        assert_eq!(1.0, sweep[0].recall);
+    }
+
+    #[test]
+    fn score_distribution_empty() {
+        let distribution = score_distribution::<&str>("query", &[], &SequenceMatcher);
+        assert!(distribution.is_empty());
+        assert_eq!(None, distribution.mean());
+        assert_eq!(None, distribution.percentile(50.0));
+        assert_eq!(0.0, distribution.rank(1.0));
+        assert_eq!(distribution.histogram(2), [0, 0]);
+    }
+
+    #[test]
+    fn score_distribution_percentiles() {
+        let corpus = ["ab", "abcd", "abcdef", "abcdefgh"];
+        let distribution = score_distribution("", &corpus, &Levenshtein);
+        assert_eq!(Some(2.0), distribution.min());
+        assert_eq!(Some(8.0), distribution.max());
+        assert_eq!(Some(2.0), distribution.percentile(0.0));
+        assert_eq!(Some(3.0), distribution.percentile(50.0 / 3.0));
+        assert_eq!(Some(8.0), distribution.percentile(100.0));
+        assert_eq!(1.0, distribution.rank(9.0));
+        assert_eq!(distribution.histogram(1), [4]);
+
+        let constant = score_distribution("a", &["a", "a"], &Levenshtein);
+        assert_eq!(constant.histogram(3), [2, 0, 0]);
+    }
+}
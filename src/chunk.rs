@@ -0,0 +1,243 @@
+use crate::{
+    algorithms::NGramProfile,
+    matcher::{DEFAULT_CUTOFF, DEFAULT_N},
+};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::Range;
+
+// size of the n-grams chunks are indexed and compared by
+const NGRAM_LEN: usize = 3;
+
+/// A chunk of an indexed document matching a passage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkMatch {
+    /// The index of the document, in the order documents were added.
+    pub document: usize,
+    /// The byte range of the chunk in the document.
+    pub offsets: Range<usize>,
+    /// The Sørensen-Dice coefficient between the trigrams of the chunk and of
+    /// the passage.
+    pub score: f64,
+}
+
+struct Chunk {
+    document: usize,
+    offsets: Range<usize>,
+    profile: NGramProfile,
+}
+
+/// Finds the parts of long documents that are similar to a passage.
+///
+/// The metrics of the crate compare whole strings, which stops being useful
+/// once documents are much longer than the passages searched for. Documents
+/// are therefore split into chunks of `chunk_len` characters, each sharing
+/// `overlap` characters with the next one so passages straddling two chunks
+/// are still found. Chunks are indexed by their trigrams, and only the chunks
+/// sharing a trigram with the passage are scored.
+///
+/// ```
+/// use fuzzt::ChunkMatcher;
+///
+/// let documents = [
+///     "Call me Ishmael. Some years ago, never mind how long precisely, having \
+///      little or no money in my purse, I thought I would sail about a little.",
+///     "It was the best of times, it was the worst of times, it was the age of \
+///      wisdom, it was the age of foolishness.",
+/// ];
+/// let mut matcher = ChunkMatcher::new(40, 10);
+/// for document in documents {
+///     matcher.add(document);
+/// }
+///
+/// let matches = matcher.get_top_n("it was the worst of tmes", Some(0.5), Some(1));
+/// assert_eq!(matches[0].document, 1);
+/// assert!(documents[1][matches[0].offsets.clone()].contains("worst of times"));
+/// ```
+pub struct ChunkMatcher {
+    chunk_len: usize,
+    overlap: usize,
+    documents: usize,
+    chunks: Vec<Chunk>,
+    // chunks containing each trigram
+    postings: HashMap<String, Vec<usize>>,
+}
+
+impl ChunkMatcher {
+    /// Creates an empty matcher splitting documents into chunks of
+    /// `chunk_len` characters, consecutive chunks sharing `overlap` of them.
+    ///
+    /// Panics if `chunk_len` is 0 or `overlap` isn't shorter than
+    /// `chunk_len`.
+    pub fn new(chunk_len: usize, overlap: usize) -> Self {
+        assert!(chunk_len > 0, "chunks must contain at least one character");
+        assert!(
+            overlap < chunk_len,
+            "the overlap must be shorter than the chunks"
+        );
+        ChunkMatcher {
+            chunk_len,
+            overlap,
+            documents: 0,
+            chunks: vec![],
+            postings: HashMap::new(),
+        }
+    }
+
+    /// Splits `document` into chunks and indexes them. Returns the index of
+    /// the document, which identifies it in the matches.
+    pub fn add(&mut self, document: &str) -> usize {
+        let index = self.documents;
+        self.documents += 1;
+
+        let boundaries: Vec<usize> = document
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(document.len()))
+            .collect();
+        let char_count = boundaries.len() - 1;
+        let stride = self.chunk_len - self.overlap;
+
+        let mut start = 0;
+        while start < char_count {
+            let end = (start + self.chunk_len).min(char_count);
+            let offsets = boundaries[start]..boundaries[end];
+            let profile = NGramProfile::new(&document[offsets.clone()], NGRAM_LEN);
+            for (ngram, _) in profile.iter() {
+                self.postings
+                    .entry(ngram.to_owned())
+                    .or_default()
+                    .push(self.chunks.len());
+            }
+            self.chunks.push(Chunk {
+                document: index,
+                offsets,
+                profile,
+            });
+            if end == char_count {
+                break;
+            }
+            start += stride;
+        }
+        index
+    }
+
+    /// Returns the number of documents added to the matcher.
+    pub fn documents(&self) -> usize {
+        self.documents
+    }
+
+    /// Returns the number of indexed chunks.
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns `true` if no chunk was indexed.
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Returns the chunks that best match `passage`.
+    ///
+    /// Only chunks scoring at least `cutoff` (0.7 if not provided) are
+    /// returned, at most `n` of them (3 if not provided), ordered by
+    /// decreasing score and then by their position in the documents. Passages
+    /// shorter than three characters match nothing.
+    pub fn get_top_n(
+        &self,
+        passage: &str,
+        cutoff: Option<f64>,
+        n: Option<usize>,
+    ) -> Vec<ChunkMatch> {
+        let cutoff = cutoff.unwrap_or(DEFAULT_CUTOFF);
+        let profile = NGramProfile::new(passage, NGRAM_LEN);
+
+        let mut candidates = vec![false; self.chunks.len()];
+        for (ngram, _) in profile.iter() {
+            for &chunk in self.postings.get(ngram).into_iter().flatten() {
+                candidates[chunk] = true;
+            }
+        }
+
+        let mut matches: Vec<ChunkMatch> = candidates
+            .iter()
+            .zip(&self.chunks)
+            .filter(|(&candidate, _)| candidate)
+            .map(|(_, chunk)| ChunkMatch {
+                document: chunk.document,
+                offsets: chunk.offsets.clone(),
+                score: chunk.profile.dice(&profile),
+            })
+            .filter(|chunk_match| chunk_match.score >= cutoff)
+            .collect();
+        matches.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.document.cmp(&b.document))
+                .then_with(|| a.offsets.start.cmp(&b.offsets.start))
+        });
+        matches.truncate(n.unwrap_or(DEFAULT_N));
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_matcher_splits_documents() {
+        let mut matcher = ChunkMatcher::new(4, 1);
+        assert!(matcher.is_empty());
+        assert_eq!(0, matcher.add("abcdefghij"));
+        assert_eq!(1, matcher.add(""));
+        assert_eq!(2, matcher.add("öঙ香"));
+        assert_eq!(3, matcher.documents());
+        let offsets: Vec<Range<usize>> = matcher
+            .chunks
+            .iter()
+            .map(|chunk| chunk.offsets.clone())
+            .collect();
+        assert_eq!(offsets, [0..4, 3..7, 6..10, 0..8]);
+    }
+
+    #[test]
+    fn chunk_matcher_finds_passages() {
+        let documents = [
+            "the quick brown fox jumps over the lazy dog",
+            "pack my box with five dozen liquor jugs",
+            "the lazy dog sleeps while the quick fox runs",
+        ];
+        let mut matcher = ChunkMatcher::new(16, 8);
+        for document in documents {
+            matcher.add(document);
+        }
+
+        let matches = matcher.get_top_n("five dozen liquor", Some(0.5), None);
+        assert_eq!(2, matches.len());
+        assert_eq!(1, matches[0].document);
+        assert_eq!(
+            " five dozen liqu",
+            &documents[1][matches[0].offsets.clone()]
+        );
+        assert!((matches[0].score - 26.0 / 29.0).abs() < 1e-9);
+
+        let matches = matcher.get_top_n("the lazy dog", Some(0.5), Some(10));
+        let found: Vec<(usize, &str)> = matches
+            .iter()
+            .map(|m| (m.document, &documents[m.document][m.offsets.clone()]))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (0, "he lazy dog"),
+                (2, "the lazy dog sle"),
+                (0, "s over the lazy ")
+            ]
+        );
+
+        assert!(matcher.get_top_n("xyz", Some(0.0), None).is_empty());
+        assert!(matcher.get_top_n("th", Some(0.0), None).is_empty());
+    }
+}
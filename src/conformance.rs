@@ -0,0 +1,295 @@
+//! Checks the crate's metrics against published reference values.
+//!
+//! The bundled test vectors come from Winkler's name pairs for Jaro and
+//! Jaro-Winkler, Python's `difflib.SequenceMatcher` and the `RapidFuzz`
+//! documentation. [`run_conformance`] scores every vector and reports the ones
+//! that deviate from the reference implementation, which is useful to certify
+//! that scores stay compatible when switching between libraries.
+//!
+//! ```
+//! use fuzzt::conformance::{run_conformance, Source};
+//!
+//! let report = run_conformance(1e-4);
+//! assert_eq!(report.checked, fuzzt::conformance::test_vectors().len());
+//! assert!(report
+//!     .deviations
+//!     .iter()
+//!     .all(|deviation| deviation.vector.source != Source::Winkler));
+//! ```
+
+use crate::algorithms::{
+    DamerauLevenshtein, Jaro, JaroWinkler, Levenshtein, NormalizedLevenshtein, OSADistance,
+    SequenceMatcher, SimilarityMetric,
+};
+
+/// The reference implementation a test vector was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// Name pairs published by Winkler for the Jaro and Jaro-Winkler metrics.
+    Winkler,
+    /// Python's `difflib.SequenceMatcher.ratio`.
+    Difflib,
+    /// The `RapidFuzz` Python library.
+    RapidFuzz,
+}
+
+/// The metric of the crate a test vector is checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    DamerauLevenshtein,
+    Jaro,
+    JaroWinkler,
+    Levenshtein,
+    NormalizedLevenshtein,
+    OSADistance,
+    SequenceMatcher,
+}
+
+impl Metric {
+    fn scorer(self) -> &'static dyn SimilarityMetric {
+        match self {
+            Metric::DamerauLevenshtein => &DamerauLevenshtein,
+            Metric::Jaro => &Jaro,
+            Metric::JaroWinkler => &JaroWinkler,
+            Metric::Levenshtein => &Levenshtein,
+            Metric::NormalizedLevenshtein => &NormalizedLevenshtein,
+            Metric::OSADistance => &OSADistance,
+            Metric::SequenceMatcher => &SequenceMatcher,
+        }
+    }
+
+    /// Scores a pair of strings with the metric of the crate.
+    pub fn score(self, a: &str, b: &str) -> f64 {
+        self.scorer().compute_metric(a, b).as_f64()
+    }
+}
+
+/// A pair of strings and the score published by a reference implementation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestVector {
+    pub source: Source,
+    pub metric: Metric,
+    pub a: &'static str,
+    pub b: &'static str,
+    pub expected: f64,
+}
+
+/// A test vector whose score differs from the reference value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Deviation {
+    pub vector: TestVector,
+    pub actual: f64,
+}
+
+impl Deviation {
+    /// Returns the absolute difference between the actual and expected score.
+    pub fn difference(&self) -> f64 {
+        (self.actual - self.vector.expected).abs()
+    }
+}
+
+/// The result of running the metrics against the bundled test vectors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConformanceReport {
+    /// Number of test vectors that were scored.
+    pub checked: usize,
+    /// Test vectors whose score is not within the tolerance of the reference.
+    pub deviations: Vec<Deviation>,
+}
+
+impl ConformanceReport {
+    /// Returns `true` if every test vector matched its reference score.
+    pub fn is_conformant(&self) -> bool {
+        self.deviations.is_empty()
+    }
+
+    /// Returns the deviations for test vectors of a single source.
+    pub fn deviations_from(&self, source: Source) -> impl Iterator<Item = &Deviation> {
+        self.deviations
+            .iter()
+            .filter(move |deviation| deviation.vector.source == source)
+    }
+}
+
+const fn vector(
+    source: Source,
+    metric: Metric,
+    a: &'static str,
+    b: &'static str,
+    expected: f64,
+) -> TestVector {
+    TestVector {
+        source,
+        metric,
+        a,
+        b,
+        expected,
+    }
+}
+
+static TEST_VECTORS: &[TestVector] = &[
+    vector(Source::Winkler, Metric::Jaro, "MARTHA", "MARHTA", 0.944444),
+    vector(Source::Winkler, Metric::Jaro, "DWAYNE", "DUANE", 0.822222),
+    vector(Source::Winkler, Metric::Jaro, "DIXON", "DICKSONX", 0.766667),
+    vector(
+        Source::Winkler,
+        Metric::Jaro,
+        "JELLYFISH",
+        "SMELLYFISH",
+        0.896296,
+    ),
+    vector(
+        Source::Winkler,
+        Metric::JaroWinkler,
+        "MARTHA",
+        "MARHTA",
+        0.961111,
+    ),
+    vector(
+        Source::Winkler,
+        Metric::JaroWinkler,
+        "DWAYNE",
+        "DUANE",
+        0.84,
+    ),
+    vector(
+        Source::Winkler,
+        Metric::JaroWinkler,
+        "DIXON",
+        "DICKSONX",
+        0.813333,
+    ),
+    vector(
+        Source::Winkler,
+        Metric::JaroWinkler,
+        "JELLYFISH",
+        "SMELLYFISH",
+        0.896296,
+    ),
+    vector(
+        Source::Difflib,
+        Metric::SequenceMatcher,
+        "abcd",
+        "bcde",
+        0.75,
+    ),
+    vector(
+        Source::Difflib,
+        Metric::SequenceMatcher,
+        "abxcd",
+        "abcd",
+        0.888889,
+    ),
+    vector(
+        Source::Difflib,
+        Metric::SequenceMatcher,
+        "kitten",
+        "sitting",
+        0.615385,
+    ),
+    vector(
+        Source::Difflib,
+        Metric::SequenceMatcher,
+        "apple",
+        "apples",
+        0.909091,
+    ),
+    vector(
+        Source::Difflib,
+        Metric::SequenceMatcher,
+        "abcd",
+        "dcba",
+        0.25,
+    ),
+    vector(
+        Source::Difflib,
+        Metric::SequenceMatcher,
+        "tide",
+        "diet",
+        0.25,
+    ),
+    vector(
+        Source::Difflib,
+        Metric::SequenceMatcher,
+        "fuzzy wuzzy was a bear",
+        "wuzzy fuzzy was a bear",
+        0.909091,
+    ),
+    vector(
+        Source::RapidFuzz,
+        Metric::SequenceMatcher,
+        "this is a test",
+        "this is a test!",
+        0.965517,
+    ),
+    vector(
+        Source::RapidFuzz,
+        Metric::Levenshtein,
+        "lewenstein",
+        "levenshtein",
+        2.0,
+    ),
+    vector(
+        Source::RapidFuzz,
+        Metric::NormalizedLevenshtein,
+        "lewenstein",
+        "levenshtein",
+        0.818182,
+    ),
+    vector(Source::RapidFuzz, Metric::OSADistance, "CA", "ABC", 3.0),
+    vector(
+        Source::RapidFuzz,
+        Metric::DamerauLevenshtein,
+        "CA",
+        "ABC",
+        2.0,
+    ),
+];
+
+/// Returns the bundled test vectors.
+pub fn test_vectors() -> &'static [TestVector] {
+    TEST_VECTORS
+}
+
+/// Scores every bundled test vector and reports the ones that are not within
+/// `tolerance` of the reference score.
+pub fn run_conformance(tolerance: f64) -> ConformanceReport {
+    let deviations = TEST_VECTORS
+        .iter()
+        .map(|&vector| Deviation {
+            vector,
+            actual: vector.metric.score(vector.a, vector.b),
+        })
+        .filter(|deviation| deviation.difference() > tolerance)
+        .collect();
+
+    ConformanceReport {
+        checked: TEST_VECTORS.len(),
+        deviations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conformance_winkler_and_rapidfuzz() {
+        let report = run_conformance(1e-5);
+        assert_eq!(TEST_VECTORS.len(), report.checked);
+        assert_eq!(0, report.deviations_from(Source::Winkler).count());
+        assert_eq!(0, report.deviations_from(Source::RapidFuzz).count());
+    }
+
+    #[test]
+    fn conformance_difflib() {
+        let report = run_conformance(1e-5);
+        assert_eq!(0, report.deviations_from(Source::Difflib).count());
+        assert!(report.is_conformant());
+    }
+
+    #[test]
+    fn conformance_tolerance() {
+        assert!(run_conformance(1.0).is_conformant());
+    }
+}
@@ -0,0 +1,278 @@
+//! Tools for understanding how the metrics of the crate score a pair of
+//! strings.
+//!
+//! The `explain` example prints the table of [`compare_algorithms`] for two
+//! strings given on the command line:
+//!
+//! ```text
+//! cargo run --example explain -- kitten sitting
+//! ```
+
+#[cfg(feature = "palette")]
+use crate::algorithms::CommandPalette;
+#[cfg(feature = "containment")]
+use crate::algorithms::Containment;
+#[cfg(feature = "cosine")]
+use crate::algorithms::Cosine;
+#[cfg(feature = "fingerprint")]
+use crate::algorithms::Fingerprinter;
+#[cfg(feature = "fzf")]
+use crate::algorithms::Fzf;
+#[cfg(feature = "gotoh")]
+use crate::algorithms::Gotoh;
+#[cfg(feature = "optimal_string_alignment")]
+use crate::algorithms::OSADistance;
+#[cfg(feature = "sift4")]
+use crate::algorithms::Sift4;
+#[cfg(feature = "damerau_levenshtein")]
+use crate::algorithms::{DamerauLevenshtein, NormalizedDamerauLevenshtein};
+#[cfg(feature = "phonetic")]
+use crate::algorithms::{DoubleMetaphone, RefinedSoundex, Soundex};
+#[cfg(feature = "jaccard")]
+use crate::algorithms::{Jaccard, PaddedJaccard};
+#[cfg(feature = "jaro")]
+use crate::algorithms::{Jaro, JaroWinkler, Strcmp95};
+#[cfg(feature = "keyboard")]
+use crate::algorithms::{KeyboardLayout, KeyboardLevenshtein};
+#[cfg(feature = "ngram")]
+use crate::algorithms::{KondrakNGram, PositionalQGram};
+#[cfg(feature = "levenshtein")]
+use crate::algorithms::{Levenshtein, NormalizedLevenshtein};
+#[cfg(feature = "sorensen_dice")]
+use crate::algorithms::{PaddedSorensenDice, SorensenDice};
+use crate::algorithms::{SequenceMatcher, Similarity, SimilarityMetric};
+use std::fmt::{self, Display, Formatter};
+#[cfg(feature = "keyboard")]
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+// the layout of the keyboard metric, which borrows it
+#[cfg(feature = "keyboard")]
+static QWERTY: OnceLock<KeyboardLayout> = OnceLock::new();
+
+// the cost of substituting adjacent keys of the keyboard metric
+#[cfg(feature = "keyboard")]
+const ADJACENT_KEY_COST: f64 = 0.5;
+
+/// The score of a pair of strings under one metric, and the time it took to
+/// compute it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricScore {
+    pub name: &'static str,
+    pub score: Similarity,
+    pub elapsed: Duration,
+}
+
+/// The scores of a pair of strings under every enabled metric, as returned by
+/// [`compare_algorithms`]. Displaying it prints a table with one metric per
+/// row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlgorithmComparison {
+    pub scores: Vec<MetricScore>,
+}
+
+impl AlgorithmComparison {
+    /// Returns the score of the metric with the given name, if it's enabled.
+    pub fn get(&self, name: &str) -> Option<&MetricScore> {
+        self.scores.iter().find(|score| score.name == name)
+    }
+}
+
+impl Display for AlgorithmComparison {
+    fn fmt(&self, fmt: &mut Formatter) -> Result<(), fmt::Error> {
+        let width = self
+            .scores
+            .iter()
+            .map(|score| score.name.len())
+            .max()
+            .unwrap_or(0);
+        writeln!(fmt, "{:width$}  {:>10}  {:>12}", "metric", "score", "time")?;
+        for score in &self.scores {
+            let value = match score.score {
+                Similarity::Usize(r) => r.to_string(),
+                Similarity::Float(r) => format!("{r:.6}"),
+            };
+            writeln!(
+                fmt,
+                "{:width$}  {:>10}  {:>12}",
+                score.name,
+                value,
+                format!("{:?}", score.elapsed)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn enabled_metrics() -> Vec<(&'static str, Box<dyn SimilarityMetric>)> {
+    vec![
+        ("sequence_matcher", Box::new(SequenceMatcher)),
+        #[cfg(feature = "palette")]
+        ("command_palette", Box::new(CommandPalette)),
+        #[cfg(feature = "containment")]
+        ("containment", Box::new(Containment::default())),
+        #[cfg(feature = "cosine")]
+        ("cosine", Box::new(Cosine::default())),
+        #[cfg(feature = "damerau_levenshtein")]
+        ("damerau_levenshtein", Box::new(DamerauLevenshtein)),
+        #[cfg(feature = "damerau_levenshtein")]
+        (
+            "normalized_damerau_levenshtein",
+            Box::new(NormalizedDamerauLevenshtein),
+        ),
+        #[cfg(feature = "phonetic")]
+        ("double_metaphone", Box::new(DoubleMetaphone)),
+        #[cfg(feature = "fingerprint")]
+        ("fingerprint", Box::new(Fingerprinter::default())),
+        #[cfg(feature = "fzf")]
+        ("fzf", Box::new(Fzf)),
+        #[cfg(feature = "gotoh")]
+        ("gotoh", Box::new(Gotoh::default())),
+        #[cfg(feature = "jaccard")]
+        ("jaccard", Box::new(Jaccard)),
+        #[cfg(feature = "jaro")]
+        ("jaro", Box::new(Jaro)),
+        #[cfg(feature = "jaro")]
+        ("jaro_winkler", Box::new(JaroWinkler)),
+        #[cfg(feature = "keyboard")]
+        (
+            "keyboard_levenshtein",
+            Box::new(KeyboardLevenshtein::new(
+                QWERTY.get_or_init(KeyboardLayout::qwerty),
+                ADJACENT_KEY_COST,
+            )),
+        ),
+        #[cfg(feature = "ngram")]
+        ("kondrak_ngram", Box::new(KondrakNGram::default())),
+        #[cfg(feature = "levenshtein")]
+        ("levenshtein", Box::new(Levenshtein)),
+        #[cfg(feature = "levenshtein")]
+        ("normalized_levenshtein", Box::new(NormalizedLevenshtein)),
+        #[cfg(feature = "optimal_string_alignment")]
+        ("osa_distance", Box::new(OSADistance)),
+        #[cfg(feature = "jaccard")]
+        ("padded_jaccard", Box::new(PaddedJaccard::default())),
+        #[cfg(feature = "sorensen_dice")]
+        (
+            "padded_sorensen_dice",
+            Box::new(PaddedSorensenDice::default()),
+        ),
+        #[cfg(feature = "ngram")]
+        ("positional_qgram", Box::new(PositionalQGram::default())),
+        #[cfg(feature = "phonetic")]
+        ("refined_soundex", Box::new(RefinedSoundex)),
+        #[cfg(feature = "sift4")]
+        ("sift4", Box::new(Sift4::default())),
+        #[cfg(feature = "sorensen_dice")]
+        ("sorensen_dice", Box::new(SorensenDice)),
+        #[cfg(feature = "phonetic")]
+        ("soundex", Box::new(Soundex)),
+        #[cfg(feature = "jaro")]
+        ("strcmp95", Box::new(Strcmp95::default())),
+    ]
+}
+
+/// Scores a pair of strings with every metric enabled by the crate features
+/// that works without any input of its own, and measures how long each one
+/// takes.
+///
+/// This is meant for debugging a surprising match: a single call shows how
+/// each metric sees the pair. Metrics are used with their default settings,
+/// and the keyboard metric with the QWERTY layout. Metrics built on top of
+/// other metrics, tokenizers or corpora, such as `MongeElkan` or
+/// `TfIdfVectorizer`, are left out. The Hamming distance is only included when
+/// both strings have the same number of characters.
+///
+/// ```
+/// use fuzzt::algorithms::Similarity;
+/// use fuzzt::diagnostics::compare_algorithms;
+///
+/// let comparison = compare_algorithms("kitten", "sitting");
+/// assert_eq!(Similarity::Usize(3), comparison.get("levenshtein").unwrap().score);
+/// assert!(comparison.get("hamming").is_none());
+/// println!("{comparison}");
+/// ```
+pub fn compare_algorithms(a: &str, b: &str) -> AlgorithmComparison {
+    let mut scores: Vec<MetricScore> = enabled_metrics()
+        .into_iter()
+        .map(|(name, metric)| {
+            let start = Instant::now();
+            let score = metric.compute_metric(a, b);
+            MetricScore {
+                name,
+                score,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect();
+
+    #[cfg(feature = "hamming")]
+    {
+        let start = Instant::now();
+        if let Ok(distance) = crate::algorithms::hamming(a, b) {
+            scores.push(MetricScore {
+                name: "hamming",
+                score: Similarity::Usize(distance),
+                elapsed: start.elapsed(),
+            });
+        }
+    }
+
+    scores.sort_by_key(|score| score.name);
+    AlgorithmComparison { scores }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{jaro_winkler, normalized_levenshtein, sequence_matcher};
+
+    #[test]
+    fn compare_algorithms_scores() {
+        let comparison = compare_algorithms("martha", "marhta");
+        assert_eq!(
+            Similarity::Float(jaro_winkler("martha", "marhta")),
+            comparison.get("jaro_winkler").unwrap().score
+        );
+        assert_eq!(
+            Similarity::Float(normalized_levenshtein("martha", "marhta")),
+            comparison.get("normalized_levenshtein").unwrap().score
+        );
+        assert_eq!(
+            Similarity::Float(sequence_matcher("martha", "marhta")),
+            comparison.get("sequence_matcher").unwrap().score
+        );
+        assert_eq!(
+            Similarity::Usize(2),
+            comparison.get("hamming").unwrap().score
+        );
+        assert!(comparison.get("unknown").is_none());
+    }
+
+    #[test]
+    fn compare_algorithms_covers_enabled_metrics() {
+        let comparison = compare_algorithms("Git: Commit", "git commit");
+        for name in [
+            "command_palette",
+            "containment",
+            "double_metaphone",
+            "fzf",
+            "gotoh",
+            "keyboard_levenshtein",
+            "kondrak_ngram",
+            "sift4",
+            "soundex",
+            "strcmp95",
+        ] {
+            assert!(comparison.get(name).is_some(), "{name}");
+        }
+    }
+
+    #[test]
+    fn compare_algorithms_table() {
+        let comparison = compare_algorithms("abc", "abd");
+        let table = comparison.to_string();
+        assert_eq!(comparison.scores.len() + 1, table.lines().count());
+        assert!(table.lines().any(|line| line.starts_with("levenshtein ")));
+    }
+}
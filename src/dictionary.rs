@@ -0,0 +1,468 @@
+//! Word frequencies, and the tools built on top of them.
+//!
+//! A [`FrequencyDictionary`] knows how often every word occurs in some
+//! reference text, which tells how likely a word is to appear in new text.
+//!
+//! ```
+//! use fuzzt::dictionary::{word_segmentation, FrequencyDictionary};
+//!
+//! let mut dictionary = FrequencyDictionary::new();
+//! for (word, count) in [("ice", 30), ("cream", 20), ("shop", 25), ("i", 50), ("ce", 1)] {
+//!     dictionary.insert(word, count);
+//! }
+//! assert_eq!(word_segmentation("icecreamshop", &dictionary), ["ice", "cream", "shop"]);
+//! ```
+
+use crate::processors::Tokenizer;
+use crate::utils::FuzztError;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+// unknown words may be at most this long, unless the dictionary contains
+// longer words
+const MAX_UNKNOWN_WORD_LEN: usize = 20;
+
+/// How often words occur in a reference corpus.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrequencyDictionary {
+    counts: HashMap<String, u64>,
+    total: u64,
+    max_word_len: usize,
+}
+
+impl FrequencyDictionary {
+    /// Creates an empty dictionary.
+    pub fn new() -> Self {
+        FrequencyDictionary::default()
+    }
+
+    /// Reads a dictionary with one word and its count per line, separated by
+    /// whitespace such as a tab, e.g. `the\t23135851162`. Empty lines are
+    /// skipped, and counts of words listed more than once are added up.
+    ///
+    /// Any reader works, so compressed dictionaries can be read by wrapping
+    /// the file in a decoder, e.g. a `GzDecoder` of the flate2 crate.
+    ///
+    /// Returns an error if a line has no count, or reading fails.
+    ///
+    /// ```
+    /// use fuzzt::dictionary::FrequencyDictionary;
+    /// use fuzzt::FuzztError;
+    ///
+    /// let dictionary = FrequencyDictionary::from_reader("the\t500\nquick 20\n".as_bytes()).unwrap();
+    /// assert_eq!(500, dictionary.count("the"));
+    /// assert_eq!(Err(FuzztError::MalformedDictionary), FrequencyDictionary::from_reader("the".as_bytes()));
+    /// ```
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, FuzztError> {
+        let mut dictionary = FrequencyDictionary::new();
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (word, count) = line
+                .rsplit_once(char::is_whitespace)
+                .ok_or(FuzztError::MalformedDictionary)?;
+            let count = count.parse().map_err(|_| FuzztError::MalformedDictionary)?;
+            dictionary.insert(word.trim_end(), count);
+        }
+        Ok(dictionary)
+    }
+
+    /// Reads a dictionary file in the format of [`from_reader`](Self::from_reader).
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, FuzztError> {
+        FrequencyDictionary::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Adds `count` occurrences of `word` to the dictionary.
+    pub fn insert(&mut self, word: &str, count: u64) {
+        if word.is_empty() || count == 0 {
+            return;
+        }
+        let total = self.counts.entry(word.to_owned()).or_insert(0);
+        *total = total.saturating_add(count);
+        self.total = self.total.saturating_add(count);
+        self.max_word_len = self.max_word_len.max(word.chars().count());
+    }
+
+    /// Returns how often `word` occurs, 0 if it's not in the dictionary.
+    pub fn count(&self, word: &str) -> u64 {
+        self.counts.get(word).copied().unwrap_or(0)
+    }
+
+    /// Returns `true` if `word` is in the dictionary.
+    pub fn contains(&self, word: &str) -> bool {
+        self.counts.contains_key(word)
+    }
+
+    /// Returns the number of distinct words.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns `true` if the dictionary has no words.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Returns the number of occurrences of all words.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Returns the number of characters of the longest word.
+    pub fn max_word_len(&self) -> usize {
+        self.max_word_len
+    }
+
+    /// Returns the words and their counts, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> + '_ {
+        self.counts
+            .iter()
+            .map(|(word, &count)| (word.as_str(), count))
+    }
+
+    /// Returns the base 10 logarithm of the probability of `word`. Unknown
+    /// words get a probability that decreases tenfold with every character,
+    /// so they are only used when no known words fit. This assumes the
+    /// dictionary holds a reasonable corpus, at least more than 10 words.
+    pub fn log_probability(&self, word: &str) -> f64 {
+        let total = (self.total.max(1) as f64).log10();
+        match self.count(word) {
+            0 => 1.0 - total - word.chars().count() as f64,
+            count => (count as f64).log10() - total,
+        }
+    }
+}
+
+/// Counts the words of a corpus into a [`FrequencyDictionary`].
+///
+/// ```
+/// use fuzzt::dictionary::DictionaryBuilder;
+/// use fuzzt::processors::WordTokenizer;
+///
+/// let mut builder = DictionaryBuilder::new(&WordTokenizer).min_count(2);
+/// builder.add("The cat sat on the mat.");
+/// builder.add("The dog sat.");
+/// let dictionary = builder.build();
+/// assert_eq!(2, dictionary.count("The"));
+/// assert_eq!(2, dictionary.count("sat"));
+/// assert!(!dictionary.contains("cat"));
+/// ```
+pub struct DictionaryBuilder<'t> {
+    tokenizer: &'t dyn Tokenizer,
+    min_count: u64,
+    counts: HashMap<String, u64>,
+}
+
+impl<'t> DictionaryBuilder<'t> {
+    /// Creates a builder counting the tokens produced by `tokenizer`.
+    pub fn new(tokenizer: &'t dyn Tokenizer) -> Self {
+        DictionaryBuilder {
+            tokenizer,
+            min_count: 1,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Leaves out words occurring fewer than `min_count` times, which are
+    /// often typos in the corpus.
+    pub fn min_count(mut self, min_count: u64) -> Self {
+        self.min_count = min_count;
+        self
+    }
+
+    /// Counts the words of `text`.
+    pub fn add(&mut self, text: &str) {
+        for token in self.tokenizer.tokenize(text) {
+            let count = self.counts.entry(token.to_owned()).or_insert(0);
+            *count = count.saturating_add(1);
+        }
+    }
+
+    /// Returns the dictionary of all words counted so far.
+    pub fn build(&self) -> FrequencyDictionary {
+        let mut dictionary = FrequencyDictionary::new();
+        for (word, &count) in &self.counts {
+            if count >= self.min_count {
+                dictionary.insert(word, count);
+            }
+        }
+        dictionary
+    }
+}
+
+/// Splits text without spaces into its most probable sequence of words, such
+/// as hashtags or domain names.
+///
+/// The words are chosen to maximise the product of their probabilities in
+/// `dictionary`, so frequent words are preferred over rare ones and unknown
+/// words are only kept when no known words fit. Words are looked up as they
+/// appear in the text, so the dictionary and the text should use the same
+/// case. Text that already contains whitespace is segmented part by part.
+///
+/// ```
+/// use fuzzt::dictionary::{word_segmentation, FrequencyDictionary};
+///
+/// let mut dictionary = FrequencyDictionary::new();
+/// for (word, count) in [("expert", 10), ("experts", 5), ("exchange", 8), ("sex", 3), ("change", 9)] {
+///     dictionary.insert(word, count);
+/// }
+/// assert_eq!(word_segmentation("expertsexchange", &dictionary), ["experts", "exchange"]);
+/// ```
+pub fn word_segmentation<'a>(text: &'a str, dictionary: &FrequencyDictionary) -> Vec<&'a str> {
+    segment_parts(text, dictionary).1
+}
+
+/// Segments every whitespace-separated part of `text`, returning the log
+/// probability of the words along with them.
+fn segment_parts<'a>(text: &'a str, dictionary: &FrequencyDictionary) -> (f64, Vec<&'a str>) {
+    let mut log_probability = 0.0;
+    let mut words = vec![];
+    for part in text.split_whitespace() {
+        let (part_probability, part_words) = segment(part, dictionary);
+        log_probability += part_probability;
+        words.extend(part_words);
+    }
+    (log_probability, words)
+}
+
+/// Finds the most probable segmentation of `text` with dynamic programming
+/// over the character boundaries.
+fn segment<'a>(text: &'a str, dictionary: &FrequencyDictionary) -> (f64, Vec<&'a str>) {
+    let boundaries: Vec<usize> = text
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(text.len()))
+        .collect();
+    let max_len = dictionary.max_word_len().max(MAX_UNKNOWN_WORD_LEN);
+
+    // best[end] is the log probability of the best segmentation of the first
+    // `end` characters, and the start of its last word
+    let mut best: Vec<(f64, usize)> = vec![(0.0, 0); boundaries.len()];
+    for end in 1..boundaries.len() {
+        best[end] = (end.saturating_sub(max_len)..end)
+            .map(|start| {
+                let word = &text[boundaries[start]..boundaries[end]];
+                (best[start].0 + dictionary.log_probability(word), start)
+            })
+            .fold((f64::NEG_INFINITY, 0), |best, candidate| {
+                if candidate.0 > best.0 {
+                    candidate
+                } else {
+                    best
+                }
+            });
+    }
+
+    let mut words = vec![];
+    let mut end = boundaries.len() - 1;
+    while end > 0 {
+        let start = best[end].1;
+        words.push(&text[boundaries[start]..boundaries[end]]);
+        end = start;
+    }
+    words.reverse();
+    (best[boundaries.len() - 1].0, words)
+}
+
+/// Frequency dictionaries of several languages, identified by tags such as
+/// `"en"` or `"de"`.
+///
+/// Lookups go to the dictionary of a given language, or when the language of
+/// the text is unknown, to the dictionary under which the text is the most
+/// probable.
+///
+/// ```
+/// use fuzzt::dictionary::{FrequencyDictionary, MultiLanguageDictionary};
+///
+/// let english = FrequencyDictionary::from_reader("ice 30\ncream 20\n".as_bytes()).unwrap();
+/// let german = FrequencyDictionary::from_reader("eis 30\ncreme 20\n".as_bytes()).unwrap();
+/// let mut dictionaries = MultiLanguageDictionary::new();
+/// dictionaries.insert("en", english);
+/// dictionaries.insert("de", german);
+///
+/// assert_eq!(Some("de"), dictionaries.detect_language("eiscreme"));
+/// assert_eq!(Some(vec!["eis", "creme"]), dictionaries.word_segmentation("eiscreme", None));
+/// assert_eq!(Some(vec!["ice", "cream"]), dictionaries.word_segmentation("icecream", Some("en")));
+/// assert_eq!(None, dictionaries.word_segmentation("icecream", Some("fr")));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MultiLanguageDictionary {
+    dictionaries: Vec<(String, FrequencyDictionary)>,
+}
+
+impl MultiLanguageDictionary {
+    /// Creates a collection without languages.
+    pub fn new() -> Self {
+        MultiLanguageDictionary::default()
+    }
+
+    /// Adds the dictionary of `language`, replacing the previous one if there
+    /// was one.
+    pub fn insert(&mut self, language: &str, dictionary: FrequencyDictionary) {
+        match self.get_mut(language) {
+            Some(existing) => *existing = dictionary,
+            None => self.dictionaries.push((language.to_owned(), dictionary)),
+        }
+    }
+
+    /// Returns the dictionary of `language`.
+    pub fn get(&self, language: &str) -> Option<&FrequencyDictionary> {
+        self.dictionaries
+            .iter()
+            .find(|(tag, _)| tag == language)
+            .map(|(_, dictionary)| dictionary)
+    }
+
+    /// Returns the dictionary of `language` for modification.
+    pub fn get_mut(&mut self, language: &str) -> Option<&mut FrequencyDictionary> {
+        self.dictionaries
+            .iter_mut()
+            .find(|(tag, _)| tag == language)
+            .map(|(_, dictionary)| dictionary)
+    }
+
+    /// Returns the languages in the order they were added.
+    pub fn languages(&self) -> impl Iterator<Item = &str> + '_ {
+        self.dictionaries.iter().map(|(tag, _)| tag.as_str())
+    }
+
+    /// Returns the language whose dictionary makes `text` the most probable,
+    /// or `None` if there are no languages. Ties go to the language added
+    /// first.
+    pub fn detect_language(&self, text: &str) -> Option<&str> {
+        self.best_segmentation(text).map(|(language, _)| language)
+    }
+
+    /// Splits text without spaces into words like [`word_segmentation`],
+    /// using the dictionary of `language`, or of the detected language if
+    /// it's `None`. Returns `None` if there is no such language.
+    pub fn word_segmentation<'a>(
+        &self,
+        text: &'a str,
+        language: Option<&str>,
+    ) -> Option<Vec<&'a str>> {
+        match language {
+            Some(language) => self
+                .get(language)
+                .map(|dictionary| word_segmentation(text, dictionary)),
+            None => self.best_segmentation(text).map(|(_, words)| words),
+        }
+    }
+
+    fn best_segmentation<'a>(&self, text: &'a str) -> Option<(&str, Vec<&'a str>)> {
+        let mut best: Option<(f64, &str, Vec<&'a str>)> = None;
+        for (language, dictionary) in &self.dictionaries {
+            let (log_probability, words) = segment_parts(text, dictionary);
+            if best.as_ref().map_or(true, |(best_probability, _, _)| {
+                log_probability > *best_probability
+            }) {
+                best = Some((log_probability, language, words));
+            }
+        }
+        best.map(|(_, language, words)| (language, words))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary(words: &[(&str, u64)]) -> FrequencyDictionary {
+        let mut dictionary = FrequencyDictionary::new();
+        for &(word, count) in words {
+            dictionary.insert(word, count);
+        }
+        dictionary
+    }
+
+    #[test]
+    fn frequency_dictionary_counts() {
+        let mut dictionary = dictionary(&[("the", 5), ("fox", 1), ("", 3), ("zero", 0)]);
+        dictionary.insert("the", 2);
+        assert_eq!(7, dictionary.count("the"));
+        assert_eq!(0, dictionary.count("zero"));
+        assert_eq!(2, dictionary.len());
+        assert_eq!(8, dictionary.total());
+        assert!(dictionary.log_probability("the") > dictionary.log_probability("fox"));
+        assert!(dictionary.log_probability("fox") > dictionary.log_probability("cat"));
+    }
+
+    #[test]
+    fn frequency_dictionary_from_reader() {
+        let text = "the\t500\n\nquick brown 20\r\nfox 3\nthe 1\n";
+        let dictionary = FrequencyDictionary::from_reader(text.as_bytes()).unwrap();
+        assert_eq!(501, dictionary.count("the"));
+        assert_eq!(20, dictionary.count("quick brown"));
+        assert_eq!(3, dictionary.len());
+        for malformed in ["fox\n", "fox three\n", "fox -1\n"] {
+            assert_eq!(
+                Err(FuzztError::MalformedDictionary),
+                FrequencyDictionary::from_reader(malformed.as_bytes())
+            );
+        }
+        assert_eq!(
+            Err(FuzztError::Io(std::io::ErrorKind::InvalidData)),
+            FrequencyDictionary::from_reader(&b"fox \xff\n"[..])
+        );
+        assert!(matches!(
+            FrequencyDictionary::from_file("missing.txt"),
+            Err(FuzztError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn word_segmentation_unknown_words() {
+        let dictionary = dictionary(&[("new", 10), ("york", 5), ("times", 8)]);
+        assert_eq!(
+            word_segmentation("newyorktimes", &dictionary),
+            ["new", "york", "times"]
+        );
+        assert_eq!(
+            word_segmentation("newzealandtimes", &dictionary),
+            ["new", "zealand", "times"]
+        );
+        assert!(word_segmentation("", &dictionary).is_empty());
+        assert_eq!(word_segmentation("xyz", &dictionary), ["xyz"]);
+    }
+
+    #[test]
+    fn multi_language_dictionary() {
+        let mut dictionaries = MultiLanguageDictionary::new();
+        assert_eq!(None, dictionaries.detect_language("hello"));
+        assert_eq!(None, dictionaries.word_segmentation("hello", None));
+
+        dictionaries.insert("en", dictionary(&[("hello", 10), ("world", 5)]));
+        dictionaries.insert("fr", dictionary(&[("bonjour", 10), ("monde", 5)]));
+        dictionaries.insert("en", dictionary(&[("good", 10), ("morning", 5)]));
+        assert_eq!(
+            vec!["en", "fr"],
+            dictionaries.languages().collect::<Vec<_>>()
+        );
+        assert!(!dictionaries.get("en").unwrap().contains("hello"));
+
+        assert_eq!(Some("fr"), dictionaries.detect_language("bonjourmonde"));
+        assert_eq!(
+            Some("en"),
+            dictionaries.detect_language("goodmorning monde")
+        );
+        assert_eq!(Some("en"), dictionaries.detect_language(""));
+        assert_eq!(
+            Some(vec!["good", "morning"]),
+            dictionaries.word_segmentation("goodmorning", None)
+        );
+    }
+
+    #[test]
+    fn word_segmentation_parts() {
+        let dictionary = dictionary(&[("café", 3), ("au", 2), ("lait", 2)]);
+        assert_eq!(
+            word_segmentation("caféaulait aulait", &dictionary),
+            ["café", "au", "lait", "au", "lait"]
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use crate::{
+    algorithms::{Similarity, SimilarityMetric},
+    matcher::{compare_matches, TieBreak, DEFAULT_N, DEFAULT_PROCESSOR},
+    processors::StringProcessor,
+};
+
+/// How the scores of each metric are rescaled before they are combined.
+///
+/// Distances (metrics returning `Similarity::Usize`) are negated first, so for
+/// every metric a higher score means a better match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreNormalization {
+    /// Use the scores as they are. Only meaningful if all metrics share a scale.
+    Raw,
+    /// Rescale the scores over the candidate set to the range [0, 1].
+    MinMax,
+    /// Subtract the mean and divide by the standard deviation of the scores
+    /// over the candidate set.
+    ZScore,
+    /// Replace the scores by their rank among the candidates, scaled to [0, 1]
+    /// where 1 is the best rank. Tied scores share their average rank.
+    Rank,
+}
+
+impl ScoreNormalization {
+    fn normalize(self, scores: &mut [f64]) {
+        if scores.is_empty() {
+            return;
+        }
+        match self {
+            ScoreNormalization::Raw => {}
+            ScoreNormalization::MinMax => {
+                let min = scores.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                for score in scores.iter_mut() {
+                    *score = if max > min {
+                        (*score - min) / (max - min)
+                    } else {
+                        1.0
+                    };
+                }
+            }
+            ScoreNormalization::ZScore => {
+                let len = scores.len() as f64;
+                let mean = scores.iter().sum::<f64>() / len;
+                let variance = scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / len;
+                let std_dev = variance.sqrt();
+                for score in scores.iter_mut() {
+                    *score = if std_dev > 0.0 {
+                        (*score - mean) / std_dev
+                    } else {
+                        0.0
+                    };
+                }
+            }
+            ScoreNormalization::Rank => {
+                let ranks = average_ranks(scores);
+                let worst = (scores.len() - 1) as f64;
+                for (score, rank) in scores.iter_mut().zip(ranks) {
+                    *score = if worst > 0.0 { 1.0 - rank / worst } else { 1.0 };
+                }
+            }
+        }
+    }
+}
+
+/// Returns the 0-based rank of every score, where rank 0 is the highest
+/// score. Tied scores get the average of the ranks they span.
+pub(crate) fn average_ranks(scores: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&i, &j| scores[j].total_cmp(&scores[i]));
+
+    let mut ranks = vec![0.0; scores.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && scores[order[end]] == scores[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end - 1) as f64 / 2.0;
+        for &index in &order[start..end] {
+            ranks[index] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Scores every choice with every metric, so that higher is always better.
+pub(crate) fn score_matrix(
+    query: &str,
+    choices: &[&str],
+    scorers: &[&dyn SimilarityMetric],
+    processor: Option<&dyn StringProcessor>,
+) -> Vec<Vec<f64>> {
+    let processor = processor.unwrap_or(DEFAULT_PROCESSOR);
+    let processed_query = processor.process(query);
+    let processed_choices: Vec<String> = choices
+        .iter()
+        .map(|choice| processor.process(choice))
+        .collect();
+
+    scorers
+        .iter()
+        .map(|scorer| {
+            processed_choices
+                .iter()
+                .map(
+                    |choice| match scorer.compute_metric(&processed_query, choice) {
+                        Similarity::Usize(r) => -(r as f64),
+                        Similarity::Float(r) => r,
+                    },
+                )
+                .collect()
+        })
+        .collect()
+}
+
+/// Orders choices by descending score, then lexicographically, then by their
+/// original position, and returns the best `n` of them.
+pub(crate) fn best_n<'a>(
+    choices: &[&'a str],
+    scores: &[f64],
+    cutoff: Option<f64>,
+    n: Option<usize>,
+) -> Vec<&'a str> {
+    let mut ranked: Vec<usize> = (0..choices.len())
+        .filter(|&i| cutoff.map_or(true, |cutoff| scores[i] >= cutoff))
+        .collect();
+    ranked.sort_by(|&i, &j| {
+        compare_matches(
+            TieBreak::Lexicographic,
+            (scores[i], choices[i], i),
+            (scores[j], choices[j], j),
+        )
+    });
+    ranked
+        .into_iter()
+        .take(n.unwrap_or(DEFAULT_N))
+        .map(|i| choices[i])
+        .collect()
+}
+
+/// Returns a list of the best matches to a collection of choices, scored by a
+/// weighted combination of several metrics.
+///
+/// The scores of each metric are rescaled with `normalization` over the whole
+/// set of choices before they are combined, so metrics on different scales
+/// (e.g. Jaro similarities and Levenshtein distances) can be mixed.
+///
+/// # Arguments
+///
+/// * `query` - A string to match against.
+/// * `choices` - A list of choices to compare against the query, e.g. `&str` or `String`.
+/// * `scorers` - The metrics to combine and their weights.
+/// * `normalization` - How the scores of each metric are rescaled before they are combined.
+/// * `cutoff` - Optional threshold for the combined score. If not provided, no choice is filtered out.
+/// * `n` - Optional maximum for the number of elements returned. Defaults to 3.
+/// * `processor` - Optional function for transforming choices before matching. If not provided, `NullStringProcessor` is used.
+///
+/// # Returns
+///
+/// * A vector of the top 'n' matches from the given choices.
+///
+/// # Example
+///
+/// ```
+/// extern crate fuzzt;
+/// use fuzzt::{
+///     algorithms::{JaroWinkler, Levenshtein},
+///     get_top_n_ensemble, ScoreNormalization,
+/// };
+///
+/// let matches = get_top_n_ensemble(
+///     "apple",
+///     &["apply", "apples", "ape", "applet", "applesauce"],
+///     &[(&JaroWinkler, 1.0), (&Levenshtein, 1.0)],
+///     ScoreNormalization::MinMax,
+///     None,
+///     Some(3),
+///     None,
+/// );
+/// assert_eq!(matches, ["apples", "applet", "apply"]);
+/// ```
+pub fn get_top_n_ensemble<'a, S>(
+    query: &str,
+    choices: &'a [S],
+    scorers: &[(&dyn SimilarityMetric, f64)],
+    normalization: ScoreNormalization,
+    cutoff: Option<f64>,
+    n: Option<usize>,
+    processor: Option<&dyn StringProcessor>,
+) -> Vec<&'a str>
+where
+    S: AsRef<str>,
+{
+    let choices: Vec<&str> = choices.iter().map(AsRef::as_ref).collect();
+    let metrics: Vec<&dyn SimilarityMetric> = scorers.iter().map(|(scorer, _)| *scorer).collect();
+    let mut matrix = score_matrix(query, &choices, &metrics, processor);
+    for scores in matrix.iter_mut() {
+        normalization.normalize(scores);
+    }
+
+    let total_weight: f64 = scorers.iter().map(|(_, weight)| weight).sum();
+    let combined: Vec<f64> = (0..choices.len())
+        .map(|i| {
+            let weighted: f64 = matrix
+                .iter()
+                .zip(scorers)
+                .map(|(scores, (_, weight))| scores[i] * weight)
+                .sum();
+            if total_weight != 0.0 {
+                weighted / total_weight
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    best_n(&choices, &combined, cutoff, n)
+}
+
+/// How the rankings produced by several metrics are merged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RankFusion {
+    /// Reciprocal rank fusion: every metric contributes `1 / (k + rank)`,
+    /// where the best rank is 1. `k = 60.0` is the customary choice.
+    Reciprocal { k: f64 },
+    /// Borda count: every metric awards a choice as many points as there are
+    /// choices ranked below it.
+    Borda,
+}
+
+/// Returns a list of the best matches to a collection of choices, merging the
+/// rankings produced by several metrics instead of their raw scores.
+///
+/// Each metric ranks all choices on its own, and the rankings are combined
+/// with `fusion`. Tied scores share their average rank.
+///
+/// # Arguments
+///
+/// * `query` - A string to match against.
+/// * `choices` - A list of choices to compare against the query, e.g. `&str` or `String`.
+/// * `scorers` - The metrics whose rankings are merged.
+/// * `fusion` - How the rankings are merged.
+/// * `n` - Optional maximum for the number of elements returned. Defaults to 3.
+/// * `processor` - Optional function for transforming choices before matching. If not provided, `NullStringProcessor` is used.
+///
+/// # Returns
+///
+/// * A vector of the top 'n' matches from the given choices.
+///
+/// # Example
+///
+/// ```
+/// extern crate fuzzt;
+/// use fuzzt::{
+///     algorithms::{Jaro, Levenshtein},
+///     get_top_n_rank_fusion, RankFusion,
+/// };
+///
+/// let matches = get_top_n_rank_fusion(
+///     "apple",
+///     &["apply", "apples", "ape", "applet", "applesauce"],
+///     &[&Jaro, &Levenshtein],
+///     RankFusion::Reciprocal { k: 60.0 },
+///     Some(3),
+///     None,
+/// );
+/// assert_eq!(matches, ["apples", "applet", "apply"]);
+/// ```
+pub fn get_top_n_rank_fusion<'a, S>(
+    query: &str,
+    choices: &'a [S],
+    scorers: &[&dyn SimilarityMetric],
+    fusion: RankFusion,
+    n: Option<usize>,
+    processor: Option<&dyn StringProcessor>,
+) -> Vec<&'a str>
+where
+    S: AsRef<str>,
+{
+    let choices: Vec<&str> = choices.iter().map(AsRef::as_ref).collect();
+    let matrix = score_matrix(query, &choices, scorers, processor);
+    let mut fused = vec![0.0; choices.len()];
+    for scores in &matrix {
+        for (total, rank) in fused.iter_mut().zip(average_ranks(scores)) {
+            *total += match fusion {
+                RankFusion::Reciprocal { k } => 1.0 / (k + rank + 1.0),
+                RankFusion::Borda => (choices.len() - 1) as f64 - rank,
+            };
+        }
+    }
+
+    best_n(&choices, &fused, None, n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{
+        Jaro, JaroWinkler, Levenshtein, NormalizedLevenshtein, SequenceMatcher,
+    };
+
+    #[test]
+    fn normalization_min_max() {
+        let mut scores = [1.0, 3.0, 2.0];
+        ScoreNormalization::MinMax.normalize(&mut scores);
+        assert_eq!(scores, [0.0, 1.0, 0.5]);
+
+        let mut constant = [2.0, 2.0];
+        ScoreNormalization::MinMax.normalize(&mut constant);
+        assert_eq!(constant, [1.0, 1.0]);
+    }
+
+    #[test]
+    fn normalization_z_score() {
+        let mut scores = [1.0, 3.0];
+        ScoreNormalization::ZScore.normalize(&mut scores);
+        assert_eq!(scores, [-1.0, 1.0]);
+    }
+
+    #[test]
+    fn normalization_rank() {
+        let mut scores = [0.2, 0.9, 0.5, 0.5, 0.1];
+        ScoreNormalization::Rank.normalize(&mut scores);
+        assert_eq!(scores, [0.25, 1.0, 0.625, 0.625, 0.0]);
+    }
+
+    #[test]
+    fn ensemble_distances_are_inverted() {
+        let choices = &["trazil", "BRA ZIL", "brazil", "spain", "braziu"][..];
+        let matches = get_top_n_ensemble(
+            "brazil",
+            choices,
+            &[(&Levenshtein, 1.0)],
+            ScoreNormalization::Raw,
+            None,
+            Some(3),
+            None,
+        );
+        assert_eq!(matches, ["brazil", "braziu", "trazil"]);
+    }
+
+    #[test]
+    fn ensemble_weights_and_cutoff() {
+        let choices = &["trazil", "BRA ZIL", "brazil", "spain", "braziu"][..];
+        let matches = get_top_n_ensemble(
+            "brazil",
+            choices,
+            &[(&JaroWinkler, 2.0), (&NormalizedLevenshtein, 1.0)],
+            ScoreNormalization::MinMax,
+            Some(0.8),
+            Some(5),
+            None,
+        );
+        assert_eq!(matches, ["brazil", "braziu", "trazil"]);
+    }
+
+    #[test]
+    fn rank_fusion_borda() {
+        let choices = &["abcd", "abce", "xbcd", "wxyz"][..];
+        // Levenshtein and Jaro tie "abce" and "xbcd", the prefix boost of
+        // Jaro-Winkler breaks the tie
+        let matches = get_top_n_rank_fusion(
+            "abcd",
+            choices,
+            &[&Levenshtein, &Jaro, &JaroWinkler],
+            RankFusion::Borda,
+            Some(4),
+            None,
+        );
+        assert_eq!(matches, ["abcd", "abce", "xbcd", "wxyz"]);
+    }
+
+    #[test]
+    fn rank_fusion_reciprocal() {
+        let choices = &["trazil", "BRA ZIL", "brazil", "spain", "braziu"][..];
+        let matches = get_top_n_rank_fusion(
+            "brazil",
+            choices,
+            &[&SequenceMatcher, &NormalizedLevenshtein],
+            RankFusion::Reciprocal { k: 60.0 },
+            Some(3),
+            None,
+        );
+        assert_eq!(matches, ["brazil", "braziu", "trazil"]);
+    }
+}
@@ -0,0 +1,185 @@
+//! Metrics for evaluating the results of a matcher against the expected
+//! matches of each query.
+//!
+//! Where [`crate::calibration`] helps choosing the cutoff of a single
+//! scorer, these metrics compare whole configurations, e.g. two scorers, or a
+//! matcher with and without an index, on queries whose relevant choices are
+//! known.
+//!
+//! ```
+//! use fuzzt::{algorithms::NormalizedLevenshtein, eval::evaluate, get_top_n};
+//!
+//! let choices = ["apple", "apples", "maple", "grape", "pineapple"];
+//! let queries = [("appel", vec!["apple"]), ("grap", vec!["grape"])];
+//! let evaluation = evaluate(&queries, 2, |query| {
+//!     get_top_n(query, &choices, Some(0.5), Some(2), None, Some(&NormalizedLevenshtein))
+//! });
+//! assert_eq!(evaluation.queries, 2);
+//! // "apples" is ranked above "apple" for the first query
+//! assert_eq!(evaluation.mean_reciprocal_rank, 0.75);
+//! assert_eq!(evaluation.recall_at_k, 1.0);
+//! ```
+
+/// The metrics of a matcher averaged over a set of queries.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Evaluation {
+    /// The number of queries evaluated.
+    pub queries: usize,
+    /// The mean of the [`reciprocal_rank`] of the queries, over their first
+    /// `k` results.
+    pub mean_reciprocal_rank: f64,
+    /// The mean of the [`recall_at_k`] of the queries.
+    pub recall_at_k: f64,
+    /// The mean of the [`precision_at_k`] of the queries.
+    pub precision_at_k: f64,
+}
+
+/// Returns the inverse of the rank of the first relevant result, e.g. 0.5 if
+/// it is the second result, or 0.0 if no result is relevant.
+///
+/// ```
+/// use fuzzt::eval::reciprocal_rank;
+///
+/// assert_eq!(0.5, reciprocal_rank(&["maple", "apple"], &["apple"]));
+/// assert_eq!(0.0, reciprocal_rank(&["maple"], &["apple"]));
+/// ```
+pub fn reciprocal_rank<T>(results: &[T], relevant: &[T]) -> f64
+where
+    T: PartialEq,
+{
+    results
+        .iter()
+        .position(|result| relevant.contains(result))
+        .map_or(0.0, |rank| 1.0 / (rank + 1) as f64)
+}
+
+/// Returns the fraction of the relevant items found among the first `k`
+/// results, or 1.0 if there are no relevant items.
+///
+/// ```
+/// use fuzzt::eval::recall_at_k;
+///
+/// let results = ["apple", "maple", "apples"];
+/// assert_eq!(0.5, recall_at_k(&results, &["apple", "apples"], 2));
+/// assert_eq!(1.0, recall_at_k(&results, &["apple", "apples"], 3));
+/// ```
+pub fn recall_at_k<T>(results: &[T], relevant: &[T], k: usize) -> f64
+where
+    T: PartialEq,
+{
+    if relevant.is_empty() {
+        return 1.0;
+    }
+    let top = &results[..k.min(results.len())];
+    let found = relevant.iter().filter(|item| top.contains(item)).count();
+    found as f64 / relevant.len() as f64
+}
+
+/// Returns the fraction of the first `k` results that are relevant. Missing
+/// results count as irrelevant, so a matcher returning fewer than `k` results
+/// can't reach 1.0.
+///
+/// Panics if `k` is 0.
+///
+/// ```
+/// use fuzzt::eval::precision_at_k;
+///
+/// let results = ["apple", "maple", "apples"];
+/// assert_eq!(0.5, precision_at_k(&results, &["apple", "apples"], 2));
+/// assert_eq!(0.25, precision_at_k(&results[..1], &["apple"], 4));
+/// ```
+pub fn precision_at_k<T>(results: &[T], relevant: &[T], k: usize) -> f64
+where
+    T: PartialEq,
+{
+    assert!(k > 0, "precision is computed over at least one result");
+    let relevant_results = results
+        .iter()
+        .take(k)
+        .filter(|result| relevant.contains(result))
+        .count();
+    relevant_results as f64 / k as f64
+}
+
+/// Runs `matcher` on every query and averages its metrics over the first `k`
+/// results. Each query comes with the choices relevant to it.
+///
+/// All metrics are 0.0 if there are no queries.
+///
+/// Panics if `k` is 0.
+///
+/// ```
+/// use fuzzt::eval::evaluate;
+///
+/// let queries = [("a", vec!["x"]), ("b", vec!["y", "z"])];
+/// let evaluation = evaluate(&queries, 2, |query| match query {
+///     "a" => vec!["w", "x"],
+///     _ => vec!["y"],
+/// });
+/// assert_eq!(evaluation.mean_reciprocal_rank, 0.75);
+/// assert_eq!(evaluation.recall_at_k, 0.75);
+/// assert_eq!(evaluation.precision_at_k, 0.5);
+/// ```
+pub fn evaluate<'a, S, F>(queries: &[(S, Vec<&'a str>)], k: usize, mut matcher: F) -> Evaluation
+where
+    S: AsRef<str>,
+    F: FnMut(&str) -> Vec<&'a str>,
+{
+    assert!(k > 0, "precision is computed over at least one result");
+    let mut total = Evaluation {
+        queries: queries.len(),
+        mean_reciprocal_rank: 0.0,
+        recall_at_k: 0.0,
+        precision_at_k: 0.0,
+    };
+    if queries.is_empty() {
+        return total;
+    }
+    for (query, relevant) in queries {
+        let results = matcher(query.as_ref());
+        let top = &results[..k.min(results.len())];
+        total.mean_reciprocal_rank += reciprocal_rank(top, relevant);
+        total.recall_at_k += recall_at_k(&results, relevant, k);
+        total.precision_at_k += precision_at_k(&results, relevant, k);
+    }
+    let count = queries.len() as f64;
+    total.mean_reciprocal_rank /= count;
+    total.recall_at_k /= count;
+    total.precision_at_k /= count;
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranking_metrics() {
+        let results = [3, 1, 4, 1, 5];
+        assert_eq!(1.0 / 3.0, reciprocal_rank(&results, &[4, 9]));
+        assert_eq!(0.0, reciprocal_rank(&[], &[4]));
+        assert_eq!(0.5, recall_at_k(&results, &[4, 9], 10));
+        assert_eq!(0.0, recall_at_k(&results, &[4, 9], 0));
+        assert_eq!(1.0, recall_at_k(&results, &[], 1));
+        // repeated results count each time
+        assert_eq!(0.5, precision_at_k(&results, &[1], 4));
+        assert_eq!(0.0, precision_at_k(&results, &[], 4));
+    }
+
+    #[test]
+    fn evaluate_cuts_reciprocal_rank_at_k() {
+        let queries = [("a", vec!["z"])];
+        let evaluation = evaluate(&queries, 2, |_| vec!["x", "y", "z"]);
+        assert_eq!(0.0, evaluation.mean_reciprocal_rank);
+        let evaluation = evaluate(&queries, 3, |_| vec!["x", "y", "z"]);
+        assert_eq!(1.0 / 3.0, evaluation.mean_reciprocal_rank);
+    }
+
+    #[test]
+    fn evaluate_without_queries() {
+        let queries: [(&str, Vec<&str>); 0] = [];
+        let evaluation = evaluate(&queries, 3, |_| vec![]);
+        assert_eq!(0, evaluation.queries);
+        assert_eq!(0.0, evaluation.mean_reciprocal_rank);
+    }
+}
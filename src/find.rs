@@ -0,0 +1,160 @@
+//! Approximate search of a passage inside a longer text.
+//!
+//! [`fuzzy_find`] locates the parts of a haystack that are closest to a
+//! needle in Levenshtein distance, e.g. to find where an approximate quote
+//! occurs in a document. It uses the dynamic programming of Sellers (1980),
+//! in which a match may start anywhere in the haystack for free, and in
+//! `O(len(needle) * len(haystack))` time. Unlike [`crate::bitap`], the needle
+//! can be of any length.
+//!
+//! ```
+//! use fuzzt::find::fuzzy_find;
+//!
+//! let document = "It is a truth universally acknowledged, that a single man in \
+//!                 possession of a good fortune, must be in want of a wife.";
+//! let found = fuzzy_find("a single man in posession", document, 3);
+//! assert_eq!(found.len(), 1);
+//! assert_eq!(found[0].distance, 1);
+//! assert_eq!(&document[found[0].start..found[0].end], "a single man in possession");
+//! ```
+
+/// A window of a haystack matching a needle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// The byte offset of the start of the window.
+    pub start: usize,
+    /// The byte offset of the end of the window, just past its last
+    /// character.
+    pub end: usize,
+    /// The Levenshtein distance between the needle and the window.
+    pub distance: usize,
+}
+
+/// Finds the windows of `haystack` with the smallest Levenshtein distance to
+/// `needle`, if it is at most `max_dist`.
+///
+/// Every window returned has that smallest distance. Among the windows ending
+/// at the same position, the shortest one is kept, and windows overlapping an
+/// earlier one are dropped, so the matches are disjoint and ordered by
+/// position. An empty needle matches nothing.
+///
+/// ```
+/// use fuzzt::find::{fuzzy_find, FuzzyMatch};
+///
+/// let found = fuzzy_find("colour", "the color of the colours", 1);
+/// assert_eq!(found, [FuzzyMatch { start: 17, end: 23, distance: 0 }]);
+///
+/// let found = fuzzy_find("colour", "the color", 1);
+/// assert_eq!(found, [FuzzyMatch { start: 4, end: 9, distance: 1 }]);
+/// assert!(fuzzy_find("colour", "the cat", 1).is_empty());
+/// ```
+pub fn fuzzy_find(needle: &str, haystack: &str, max_dist: usize) -> Vec<FuzzyMatch> {
+    let needle: Vec<char> = needle.chars().collect();
+    if needle.is_empty() {
+        return vec![];
+    }
+
+    // the distance of the best alignment of the needle ending at the current
+    // position of the haystack, along with the byte offset of its start,
+    // preferring the latest start among the alignments with that distance
+    let mut column: Vec<(usize, usize)> = (0..=needle.len()).map(|i| (i, 0)).collect();
+    let mut ends: Vec<FuzzyMatch> = vec![];
+    for (offset, c) in haystack.char_indices() {
+        let end = offset + c.len_utf8();
+        // a match may start after any character
+        let mut next = vec![(0, end); needle.len() + 1];
+        for i in 1..=needle.len() {
+            let (diagonal, diagonal_start) = column[i - 1];
+            let (up, up_start) = column[i];
+            let (left, left_start) = next[i - 1];
+            let candidates = [
+                (diagonal + usize::from(needle[i - 1] != c), diagonal_start),
+                (up + 1, up_start),
+                (left + 1, left_start),
+            ];
+            next[i] = candidates
+                .into_iter()
+                .min_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+                .unwrap_or(next[i]);
+        }
+        let (distance, start) = next[needle.len()];
+        if distance <= max_dist {
+            ends.push(FuzzyMatch {
+                start,
+                end,
+                distance,
+            });
+        }
+        column = next;
+    }
+
+    let Some(best) = ends.iter().map(|found| found.distance).min() else {
+        return vec![];
+    };
+    let mut matches: Vec<FuzzyMatch> = vec![];
+    for found in ends.into_iter().filter(|found| found.distance == best) {
+        if matches.last().map_or(true, |last| found.start >= last.end) {
+            matches.push(found);
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::levenshtein;
+
+    #[test]
+    fn fuzzy_find_best_distance() {
+        let cases = [
+            ("abc", "xxabcxxabxcxxaxc"),
+            ("hello", "say helo to the yellow fellow"),
+            ("öঙ香", "aöঙbö香c"),
+            ("kitten", "sitting"),
+            ("abc", "ab"),
+        ];
+        for (needle, haystack) in cases {
+            let offsets: Vec<usize> = haystack
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain([haystack.len()])
+                .collect();
+            let mut best = usize::MAX;
+            for &start in &offsets {
+                for &end in offsets.iter().filter(|&&end| end >= start) {
+                    best = best.min(levenshtein(needle, &haystack[start..end]));
+                }
+            }
+            let found = fuzzy_find(needle, haystack, best);
+            assert!(!found.is_empty(), "{needle} {haystack}");
+            for window in &found {
+                assert_eq!(best, window.distance);
+                assert_eq!(
+                    best,
+                    levenshtein(needle, &haystack[window.start..window.end])
+                );
+            }
+            if best > 0 {
+                assert!(fuzzy_find(needle, haystack, best - 1).is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn fuzzy_find_windows() {
+        let window = |start, end, distance| FuzzyMatch {
+            start,
+            end,
+            distance,
+        };
+        assert_eq!(fuzzy_find("abc", "xxabcxxabxcxxaxc", 1), [window(2, 5, 0)]);
+        assert_eq!(
+            fuzzy_find("abc", "xxabxcxxaxc", 1),
+            [window(2, 4, 1), window(8, 11, 1)]
+        );
+        assert_eq!(fuzzy_find("ab", "öab", 0), [window(2, 4, 0)]);
+        assert!(fuzzy_find("", "abc", 3).is_empty());
+        assert!(fuzzy_find("abc", "", 3).is_empty());
+    }
+}
@@ -0,0 +1,639 @@
+//! Scores on a 0 to 100 scale, compatible with the Python `RapidFuzz` and
+//! `fuzzywuzzy` libraries.
+//!
+//! Both libraries score strings with their indel distance, the number of
+//! insertions and deletions needed to turn one into the other. The scores
+//! therefore differ from the metrics of [`crate::algorithms`]: a
+//! substitution costs two edits rather than one, so
+//! `ratio("kitten", "sitting")` is 61.5 while `normalized_levenshtein` gives
+//! 0.571. The functions return the unrounded scores of `RapidFuzz`;
+//! `fuzzywuzzy` rounds them to integers, which [`round_score`] does too, so
+//! thresholds migrated from Python code, such as 87, can be used as is.
+//!
+//! ```
+//! use fuzzt::fuzz;
+//!
+//! let score = fuzz::ratio("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear");
+//! assert_eq!(score, 100.0 * (1.0 - 4.0 / 44.0));
+//! assert_eq!(91, fuzz::round_score(score));
+//! ```
+
+use crate::processors::{LowerAlphaNumStringProcessor, StringProcessor};
+
+/// Calculates the length of the longest common subsequence of both strings.
+fn longest_common_subsequence(a: &[char], b: &[char]) -> usize {
+    let mut previous = vec![0; b.len() + 1];
+    let mut current = vec![0; b.len() + 1];
+    for a_char in a {
+        for (j, b_char) in b.iter().enumerate() {
+            current[j + 1] = if a_char == b_char {
+                previous[j] + 1
+            } else {
+                previous[j + 1].max(current[j])
+            };
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Calculates the number of insertions and deletions needed to turn one
+/// string into the other.
+fn indel_distance(a: &[char], b: &[char]) -> usize {
+    a.len() + b.len() - 2 * longest_common_subsequence(a, b)
+}
+
+// rounds like Python's `round`, which rounds halfway cases to the even
+// integer
+fn round_half_even(x: f64) -> f64 {
+    let rounded = x.round();
+    if (x - x.trunc()).abs() == 0.5 {
+        2.0 * (x / 2.0).round()
+    } else {
+        rounded
+    }
+}
+
+/// Rounds a score to an integer like `fuzzywuzzy`, i.e. like Python's
+/// `round`, which rounds halfway cases to the even integer.
+///
+/// ```
+/// use fuzzt::fuzz::{ratio, round_score};
+///
+/// assert_eq!(62, round_score(ratio("kitten", "sitting"))); // 61.538
+/// assert_eq!(88, round_score(ratio("abcdefgh", "abcdefgz"))); // 87.5
+/// assert_eq!(62, round_score(62.5));
+/// ```
+pub fn round_score(score: f64) -> u8 {
+    round_half_even(score.clamp(0.0, 100.0)) as u8
+}
+
+// the indel similarity of two strings from 0 to 100, 100 for two empty
+// strings
+fn indel_ratio(a: &[char], b: &[char]) -> f64 {
+    let total = a.len() + b.len();
+    if total == 0 {
+        return 100.0;
+    }
+    100.0 * (1.0 - indel_distance(a, b) as f64 / total as f64)
+}
+
+/// Calculates the similarity of two strings from their indel distance, from
+/// 0.0 to 100.0, exactly like `fuzz.ratio` of `RapidFuzz`.
+///
+/// The score is `100 * (1 - distance / (len(a) + len(b)))`. Equal strings,
+/// including two empty strings, score 100, and an empty string scores 0
+/// against any other string. Characters are compared as they are;
+/// `RapidFuzz` doesn't preprocess strings in `ratio` either.
+///
+/// ```
+/// use fuzzt::fuzz::ratio;
+///
+/// assert_eq!(100.0 * (1.0 - 5.0 / 13.0), ratio("kitten", "sitting"));
+/// assert_eq!(87.5, ratio("abcdefgh", "abcdefgz"));
+/// assert_eq!(100.0, ratio("", ""));
+/// assert_eq!(0.0, ratio("", "abc"));
+/// ```
+pub fn ratio(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 100.0;
+    }
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    indel_ratio(&a, &b)
+}
+
+// the best score of `needle` against the windows of `haystack`, along with
+// the window, in the order `RapidFuzz` tries them: the prefixes of
+// `haystack` shorter than `needle`, the windows as long as `needle` and the
+// shorter suffixes. Windows ending, or for the suffixes starting, with a
+// character that isn't in `needle` are skipped, since dropping that
+// character can only raise the score.
+fn best_window(needle: &[char], haystack: &[char]) -> (f64, usize, usize) {
+    let (len, hay_len) = (needle.len(), haystack.len());
+    let in_needle = |c: &char| needle.contains(c);
+    let prefixes = (1..len).map(|end| (0, end));
+    let windows = (0..=hay_len - len).map(|start| (start, start + len));
+    let suffixes = (hay_len - len + 1..hay_len).map(|start| (start, hay_len));
+    let mut best = (0.0, 0, len);
+    for (start, end) in prefixes.chain(windows).chain(suffixes) {
+        let edge = if end - start < len && start > 0 {
+            &haystack[start]
+        } else {
+            &haystack[end - 1]
+        };
+        if !in_needle(edge) {
+            continue;
+        }
+        let score = indel_ratio(needle, &haystack[start..end]);
+        if score > best.0 {
+            best = (score, start, end);
+            if score == 100.0 {
+                break;
+            }
+        }
+    }
+    best
+}
+
+/// Calculates the best [`ratio`] of the shorter string against the windows
+/// of the longer one, from 0.0 to 100.0, like `fuzz.partial_ratio` of
+/// `RapidFuzz`.
+///
+/// The shorter string is slid over the longer one, including the positions
+/// where it only overlaps its start or its end, so a string contained in the
+/// other scores 100. When both strings have the same length, each is also
+/// slid over the other. An empty string scores 0 against any other string,
+/// and 100 against another empty string.
+///
+/// Every window is compared, whereas `RapidFuzz` only tries the windows
+/// aligned with the matching blocks of both strings when the shorter one has
+/// more than 64 characters, so for such strings the scores of `RapidFuzz` can
+/// be lower.
+///
+/// ```
+/// use fuzzt::fuzz::{partial_ratio, ratio};
+///
+/// assert_eq!(100.0, partial_ratio("this is a test", "this is a test!"));
+/// assert_eq!(100.0, partial_ratio("YANKEES", "NEW YORK YANKEES"));
+/// assert!(ratio("YANKEES", "NEW YORK YANKEES") < 65.0);
+/// // "certai" is the best window
+/// assert_eq!(100.0 * (1.0 - 2.0 / 12.0), partial_ratio("a certain string", "cetain"));
+/// ```
+pub fn partial_ratio(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    partial_ratio_alignment_chars(&a, &b).0
+}
+
+/// The score of [`partial_ratio_alignment`] along with the windows of both
+/// strings it compares, as byte offsets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreAlignment {
+    /// The score of the windows, from 0.0 to 100.0.
+    pub score: f64,
+    /// The byte offset of the start of the window of the first string.
+    pub src_start: usize,
+    /// The byte offset of the end of the window of the first string, just
+    /// past its last character.
+    pub src_end: usize,
+    /// The byte offset of the start of the window of the second string.
+    pub dest_start: usize,
+    /// The byte offset of the end of the window of the second string, just
+    /// past its last character.
+    pub dest_end: usize,
+}
+
+/// Calculates the [`partial_ratio`] of both strings along with the windows
+/// it compares, like `fuzz.partial_ratio_alignment` of `RapidFuzz`.
+///
+/// The window of the shorter string covers all of it, and the window of the
+/// longer one is the part of it closest to the shorter string, which tells
+/// where the partial match occurs. When both strings have the same length,
+/// either can be the one that is slid over the other.
+///
+/// ```
+/// use fuzzt::fuzz::{partial_ratio_alignment, ScoreAlignment};
+///
+/// let (a, b) = ("a certain string", "cetain");
+/// let alignment = partial_ratio_alignment(a, b);
+/// let expected = ScoreAlignment {
+///     score: 100.0 * (1.0 - 2.0 / 12.0),
+///     src_start: 2,
+///     src_end: 8,
+///     dest_start: 0,
+///     dest_end: 6,
+/// };
+/// assert_eq!(expected, alignment);
+/// assert_eq!("certai", &a[alignment.src_start..alignment.src_end]);
+/// ```
+pub fn partial_ratio_alignment(a: &str, b: &str) -> ScoreAlignment {
+    let a_chars: Vec<char> = a.chars().collect();
+    let b_chars: Vec<char> = b.chars().collect();
+    let (score, (src_start, src_end), (dest_start, dest_end)) =
+        partial_ratio_alignment_chars(&a_chars, &b_chars);
+    // turns a character offset into a byte offset
+    let byte_offset =
+        |s: &str, offset: usize| s.char_indices().nth(offset).map_or(s.len(), |(i, _)| i);
+    ScoreAlignment {
+        score,
+        src_start: byte_offset(a, src_start),
+        src_end: byte_offset(a, src_end),
+        dest_start: byte_offset(b, dest_start),
+        dest_end: byte_offset(b, dest_end),
+    }
+}
+
+// the best partial ratio of `a` and `b`, along with the window of each string
+// that is compared, as character offsets
+fn partial_ratio_alignment_chars(a: &[char], b: &[char]) -> (f64, (usize, usize), (usize, usize)) {
+    if a.is_empty() || b.is_empty() {
+        let score = if a.len() == b.len() { 100.0 } else { 0.0 };
+        return (score, (0, a.len()), (0, b.len()));
+    }
+    if a.len() > b.len() {
+        let (score, b_window, a_window) = partial_ratio_alignment_chars(b, a);
+        return (score, a_window, b_window);
+    }
+    let (score, start, end) = best_window(a, b);
+    let mut best = (score, (0, a.len()), (start, end));
+    if score < 100.0 && a.len() == b.len() {
+        let (score, start, end) = best_window(b, a);
+        if score > best.0 {
+            best = (score, (start, end), (0, b.len()));
+        }
+    }
+    best
+}
+
+// the whitespace-separated tokens of `s`, sorted
+fn sorted_tokens(s: &str) -> Vec<&str> {
+    let mut tokens: Vec<&str> = s.split_whitespace().collect();
+    tokens.sort_unstable();
+    tokens
+}
+
+// the distinct whitespace-separated tokens of `s`, sorted
+fn distinct_tokens(s: &str) -> Vec<&str> {
+    let mut tokens = sorted_tokens(s);
+    tokens.dedup();
+    tokens
+}
+
+/// Calculates the [`ratio`] of both strings once their tokens are sorted,
+/// like `fuzz.token_sort_ratio` of `RapidFuzz`, so the order of the words
+/// doesn't matter.
+///
+/// Tokens are separated by whitespace and joined back with single spaces.
+/// Like `RapidFuzz`, strings aren't preprocessed, so tokens are compared with
+/// their case and punctuation.
+///
+/// ```
+/// use fuzzt::fuzz::{ratio, token_sort_ratio};
+///
+/// assert_eq!(100.0, token_sort_ratio("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear"));
+/// assert_eq!(100.0, token_sort_ratio("ACME Ltd", "Ltd  ACME"));
+/// assert_eq!(50.0, ratio("ACME Ltd", "Ltd ACME"));
+/// ```
+pub fn token_sort_ratio(a: &str, b: &str) -> f64 {
+    ratio(&sorted_tokens(a).join(" "), &sorted_tokens(b).join(" "))
+}
+
+/// Calculates a [`ratio`] that ignores both the order of the tokens and the
+/// tokens only one string has, like `fuzz.token_set_ratio` of `RapidFuzz`.
+///
+/// The distinct tokens of both strings are split into the ones they share,
+/// and the ones only `a` or only `b` has, each sorted and joined with spaces.
+/// The score is the best ratio between the shared tokens alone, the shared
+/// tokens followed by the tokens of `a`, and the shared tokens followed by
+/// the tokens of `b`. As a result, a string whose tokens all occur in the
+/// other scores 100. Like in `fuzzywuzzy`, a string without tokens scores 0,
+/// even against another one.
+///
+/// ```
+/// use fuzzt::fuzz::{token_set_ratio, token_sort_ratio};
+///
+/// assert_eq!(100.0, token_set_ratio("fuzzy was a bear", "fuzzy fuzzy was a bear"));
+/// assert_eq!(100.0, token_set_ratio("ACME", "ACME Holdings Ltd"));
+/// assert!(token_sort_ratio("ACME", "ACME Holdings Ltd") < 50.0);
+/// assert_eq!(0.0, token_set_ratio("", ""));
+/// ```
+pub fn token_set_ratio(a: &str, b: &str) -> f64 {
+    token_set_score(&distinct_tokens(a), &distinct_tokens(b))
+}
+
+// the token set ratio of strings with the distinct, sorted tokens `a_tokens`
+// and `b_tokens`
+fn token_set_score(a_tokens: &[&str], b_tokens: &[&str]) -> f64 {
+    if a_tokens.is_empty() || b_tokens.is_empty() {
+        return 0.0;
+    }
+    let (shared, a_only): (Vec<&str>, Vec<&str>) =
+        a_tokens.iter().partition(|token| b_tokens.contains(token));
+    let b_only: Vec<&str> = b_tokens
+        .iter()
+        .copied()
+        .filter(|token| !shared.contains(token))
+        .collect();
+    // one string is part of the other one
+    if !shared.is_empty() && (a_only.is_empty() || b_only.is_empty()) {
+        return 100.0;
+    }
+
+    let a_only = a_only.join(" ").chars().collect::<Vec<char>>();
+    let b_only = b_only.join(" ").chars().collect::<Vec<char>>();
+    let shared_len = shared.join(" ").chars().count();
+    // the shared tokens followed by a space and the tokens of each string
+    let separator = usize::from(shared_len > 0);
+    let shared_a_len = shared_len + separator + a_only.len();
+    let shared_b_len = shared_len + separator + b_only.len();
+    // the shared tokens don't change the distance between both strings
+    let distance = indel_distance(&a_only, &b_only);
+    // rounded like `RapidFuzz`, which computes this score differently from
+    // `indel_ratio`
+    let score = |distance: usize, total: usize| 100.0 - 100.0 * distance as f64 / total as f64;
+    let mut best = score(distance, shared_a_len + shared_b_len);
+    if shared_len > 0 {
+        // only the tokens of one string are missing from the shared tokens
+        let shared_a = score(separator + a_only.len(), shared_len + shared_a_len);
+        let shared_b = score(separator + b_only.len(), shared_len + shared_b_len);
+        best = best.max(shared_a).max(shared_b);
+    }
+    best
+}
+
+/// Calculates the best of [`token_sort_ratio`] and [`token_set_ratio`],
+/// like `fuzz.token_ratio` of `RapidFuzz`, splitting and sorting the tokens
+/// of both strings once.
+///
+/// Like [`token_set_ratio`], a string without tokens scores 0, even against
+/// another one.
+///
+/// ```
+/// use fuzzt::fuzz::{token_ratio, token_set_ratio, token_sort_ratio};
+///
+/// let (a, b) = ("fuzzy was a bear", "fuzzy fuzzy was a bear");
+/// assert_eq!(100.0, token_ratio(a, b));
+/// let (a, b) = ("great bear", "bear great grizzly");
+/// assert_eq!(token_set_ratio(a, b).max(token_sort_ratio(a, b)), token_ratio(a, b));
+/// ```
+pub fn token_ratio(a: &str, b: &str) -> f64 {
+    let a_tokens = sorted_tokens(a);
+    let b_tokens = sorted_tokens(b);
+    if a_tokens.is_empty() || b_tokens.is_empty() {
+        return 0.0;
+    }
+    let sorted = ratio(&a_tokens.join(" "), &b_tokens.join(" "));
+    let (mut a_distinct, mut b_distinct) = (a_tokens, b_tokens);
+    a_distinct.dedup();
+    b_distinct.dedup();
+    sorted.max(token_set_score(&a_distinct, &b_distinct))
+}
+
+/// Calculates the [`partial_ratio`] of both strings once their tokens are
+/// sorted, like `fuzz.partial_token_sort_ratio` of `RapidFuzz`.
+///
+/// ```
+/// use fuzzt::fuzz::partial_token_sort_ratio;
+///
+/// assert_eq!(100.0, partial_token_sort_ratio("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear"));
+/// // "acme ltd" is part of "acme ltd uk"
+/// assert_eq!(100.0, partial_token_sort_ratio("ltd acme", "uk ltd acme"));
+/// ```
+pub fn partial_token_sort_ratio(a: &str, b: &str) -> f64 {
+    partial_ratio(&sorted_tokens(a).join(" "), &sorted_tokens(b).join(" "))
+}
+
+/// Like [`token_set_ratio`], but scores any shared token 100 and otherwise
+/// compares the tokens only each string has with [`partial_ratio`], like
+/// `fuzz.partial_token_set_ratio` of `RapidFuzz`.
+///
+/// A string without tokens scores 0, even against another one.
+///
+/// ```
+/// use fuzzt::fuzz::partial_token_set_ratio;
+///
+/// assert_eq!(100.0, partial_token_set_ratio("ACME Ltd", "Ltd"));
+/// // "holdings" and "hold" share no token, but one contains the other
+/// assert_eq!(100.0, partial_token_set_ratio("ACME holdings", "hold"));
+/// assert_eq!(0.0, partial_token_set_ratio("", "ACME"));
+/// ```
+pub fn partial_token_set_ratio(a: &str, b: &str) -> f64 {
+    let a_tokens = distinct_tokens(a);
+    let b_tokens = distinct_tokens(b);
+    if a_tokens.is_empty() || b_tokens.is_empty() {
+        return 0.0;
+    }
+    if a_tokens.iter().any(|token| b_tokens.contains(token)) {
+        return 100.0;
+    }
+    partial_ratio(&a_tokens.join(" "), &b_tokens.join(" "))
+}
+
+/// Combines the ratios of this module into the best score for strings of
+/// any length, like `fuzz.WRatio` of `RapidFuzz`.
+///
+/// Strings of similar lengths, whose longer string has less than 1.5 times as
+/// many characters as the shorter one, get the best of their [`ratio`] and of
+/// their [`token_sort_ratio`] and [`token_set_ratio`] scaled by 0.95.
+/// Otherwise, a partial variant of these ratios is used, scaled by 0.9, or by
+/// 0.6 when the longer string has 8 or more times as many characters, since
+/// a short string is easily found in a long one. An empty string scores 0.
+///
+/// ```
+/// use fuzzt::fuzz::wratio;
+///
+/// // the tokens of the first string are part of the second one
+/// assert_eq!(100.0 * 0.95 * 0.9, wratio("this is a test", "this is a new test!!!"));
+/// assert_eq!(100.0 * 0.95, wratio("fuzzy was a bear", "fuzzy fuzzy was a bear"));
+/// assert_eq!(0.0, wratio("", "fuzzy"));
+/// ```
+pub fn wratio(a: &str, b: &str) -> f64 {
+    const UNBASE_SCALE: f64 = 0.95;
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let (a_len, b_len) = (a.chars().count(), b.chars().count());
+    let len_ratio = a_len.max(b_len) as f64 / a_len.min(b_len) as f64;
+    let score = ratio(a, b);
+    if len_ratio < 1.5 {
+        return score.max(token_ratio(a, b) * UNBASE_SCALE);
+    }
+    let partial_scale = if len_ratio < 8.0 { 0.9 } else { 0.6 };
+    let partial = partial_ratio(a, b) * partial_scale;
+    let partial_token = partial_token_sort_ratio(a, b).max(partial_token_set_ratio(a, b));
+    score
+        .max(partial)
+        .max(partial_token * UNBASE_SCALE * partial_scale)
+}
+
+/// Calculates the [`ratio`] of both strings, scoring 0 if either of them is
+/// empty, like `fuzz.QRatio` of `fuzzywuzzy`.
+///
+/// With `full_process`, both strings are first processed with
+/// [`LowerAlphaNumStringProcessor`], which lowercases them, trims them and
+/// drops the characters that are neither alphanumeric nor whitespace, and
+/// they score 0 if nothing is left. Unlike `fuzzywuzzy`, which replaces such
+/// characters with spaces, the processor removes them, and non-ASCII letters
+/// are kept.
+///
+/// ```
+/// use fuzzt::fuzz::{qratio, ratio};
+///
+/// let (a, b) = ("This is a test!", "this is a test");
+/// assert_eq!(100.0, qratio(a, b, true));
+/// assert_eq!(ratio(a, b), qratio(a, b, false));
+/// assert_eq!(0.0, qratio("!!!", "!!!", true));
+/// assert_eq!(0.0, qratio("", "", false));
+/// ```
+pub fn qratio(a: &str, b: &str, full_process: bool) -> f64 {
+    let (a, b) = if full_process {
+        let processor = LowerAlphaNumStringProcessor;
+        (processor.process(a), processor.process(b))
+    } else {
+        (a.to_owned(), b.to_owned())
+    };
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    ratio(&a, &b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indel_distances() {
+        let chars = |s: &str| s.chars().collect::<Vec<char>>();
+        assert_eq!(0, indel_distance(&[], &[]));
+        assert_eq!(3, indel_distance(&[], &chars("öঙ香")));
+        assert_eq!(5, indel_distance(&chars("kitten"), &chars("sitting")));
+        assert_eq!(2, indel_distance(&chars("ab"), &chars("ba")));
+    }
+
+    #[test]
+    fn ratio_rounds_like_python() {
+        assert_eq!(62, round_score(62.5));
+        assert_eq!(64, round_score(63.5));
+        assert_eq!(62, round_score(61.538));
+        assert_eq!(100, round_score(100.0));
+        // 62.5 and 87.5 are halfway cases
+        assert_eq!(62, round_score(ratio("abcdefgh", "abcdexyz")));
+        assert_eq!(88, round_score(ratio("abcdefgh", "abcdefgz")));
+        assert_eq!(67, round_score(ratio("a", "ab")));
+        assert_eq!(50, round_score(ratio("ab", "abcdef")));
+    }
+
+    #[test]
+    fn ratio_edge_cases() {
+        assert_eq!(100.0, ratio("", ""));
+        assert_eq!(0.0, ratio("abc", ""));
+        assert_eq!(0.0, ratio("abc", "xyz"));
+        assert_eq!(100.0, ratio("öঙ香", "öঙ香"));
+        assert_eq!(80.0, ratio("öঙ香", "öঙ"));
+        assert_eq!(
+            ratio("fuzzy bear", "wuzzy pear"),
+            ratio("wuzzy pear", "fuzzy bear")
+        );
+    }
+
+    #[test]
+    fn scores_round_like_rapidfuzz() {
+        // `RapidFuzz` computes `100 * (1 - d / total)` in `ratio` but
+        // `100 - 100 * d / total` in `token_set_ratio`, which round
+        // differently
+        assert_eq!(19.999999999999996, ratio("abcde", "fghia"));
+        assert_eq!(85.71428571428572, ratio("abcd", "abc"));
+        assert_eq!(20.0, token_set_ratio("abcde", "fghia"));
+        assert_eq!(85.71428571428571, token_set_ratio("abcd", "abc"));
+    }
+
+    #[test]
+    fn partial_ratio_windows() {
+        let chars = |s: &str| s.chars().collect::<Vec<char>>();
+        let alignment = |a: &str, b: &str| partial_ratio_alignment_chars(&chars(a), &chars(b));
+        assert_eq!((100.0, (0, 3), (4, 7)), alignment("abc", "xyz abc def"));
+        assert_eq!((100.0, (4, 7), (0, 3)), alignment("xyz abc def", "abc"));
+        // windows overlapping the start or the end of the longer string
+        assert_eq!(
+            (100.0 * 4.0 / 5.0, (0, 3), (0, 2)),
+            alignment("abc", "bcxyz")
+        );
+        assert_eq!(
+            (100.0 * 4.0 / 5.0, (0, 3), (3, 5)),
+            alignment("abc", "xyzab")
+        );
+        // both strings are slid over each other when they have the same length
+        assert_eq!(partial_ratio("abcd", "bcda"), partial_ratio("bcda", "abcd"));
+        assert_eq!(100.0, partial_ratio("", ""));
+        assert_eq!(0.0, partial_ratio("", "abc"));
+        assert_eq!(0.0, partial_ratio("abc", "xyz"));
+        assert_eq!(100.0, partial_ratio("ঙ香", "öঙ香"));
+    }
+
+    #[test]
+    fn token_sort_ignores_order() {
+        assert_eq!(100.0, token_sort_ratio("c b a", " a\tb\nc "));
+        assert_eq!(ratio("a b c", "a b d"), token_sort_ratio("b a c", "d b a"));
+        assert_eq!(100.0, token_sort_ratio("", "   "));
+        assert_eq!(0.0, token_sort_ratio("", "abc"));
+        assert!(token_sort_ratio("Ltd ACME", "ltd acme") < 100.0);
+    }
+
+    #[test]
+    fn token_set_constructions() {
+        // the shared tokens followed by the tokens of each string
+        assert_eq!(ratio("a b c", "a b d"), token_set_ratio("b a c", "d b a"));
+        // the shared tokens alone against the shared tokens and those of `a`
+        assert_eq!(
+            ratio("a b", "a b ccc"),
+            token_set_ratio("a b ccc", "a b ddd")
+        );
+        // without shared tokens, the tokens only each string has are compared
+        assert_eq!(
+            ratio("bear fuzzy", "pear wuzzy"),
+            token_set_ratio("fuzzy bear", "wuzzy pear")
+        );
+        assert_eq!(100.0, token_set_ratio("a a b", "b a"));
+        assert_eq!(0.0, token_set_ratio("abc", " "));
+    }
+
+    #[test]
+    fn partial_token_ratios() {
+        assert_eq!(
+            partial_ratio("a bc", "a bc d"),
+            partial_token_sort_ratio("bc a", "d a bc")
+        );
+        assert_eq!(
+            partial_ratio("bear fuzzy", "pear"),
+            partial_token_set_ratio("fuzzy bear bear", "pear")
+        );
+        assert_eq!(100.0, partial_token_set_ratio("a x", "y a"));
+        assert_eq!(0.0, partial_token_set_ratio(" ", ""));
+        assert_eq!(100.0, partial_token_sort_ratio("", " "));
+    }
+
+    #[test]
+    fn wratio_scales() {
+        // similar lengths
+        assert_eq!(
+            ratio("this is a word", "THIS IS A WORD"),
+            wratio("this is a word", "THIS IS A WORD")
+        );
+        assert_eq!(95.0, wratio("b a", "a b"));
+        // the partial ratios, scaled by 0.9 and then by 0.6
+        assert_eq!(90.0, wratio("abc", "xabcx"));
+        assert_eq!(100.0 * 0.95 * 0.9, wratio("b a", "a b xyzw"));
+        assert_eq!(60.0, wratio("abc", "xyzabcxyzxyzxyzxyzxyzxyzxyz"));
+        // exactly 8 times as long
+        assert_eq!(60.0, wratio("abc", "xyzabcxyzxyzxyzxyzxyzxyz"));
+        assert_eq!(wratio("abc", "abcd"), wratio("abcd", "abc"));
+    }
+
+    #[test]
+    fn partial_ratio_alignment_offsets() {
+        let (a, b) = ("öঙ香 abc", "ঙ香");
+        let alignment = partial_ratio_alignment(a, b);
+        assert_eq!(100.0, alignment.score);
+        assert_eq!("ঙ香", &a[alignment.src_start..alignment.src_end]);
+        assert_eq!((0, b.len()), (alignment.dest_start, alignment.dest_end));
+        let alignment = partial_ratio_alignment("", "abc");
+        assert_eq!(0.0, alignment.score);
+        assert_eq!((0, 3), (alignment.dest_start, alignment.dest_end));
+    }
+
+    #[test]
+    fn token_ratio_is_best_of_both() {
+        for (a, b) in [
+            ("a b c", "c b a d"),
+            ("new york mets", "new york yankees"),
+            ("x y", "y x x"),
+            ("öঙ 香", "香 ö"),
+        ] {
+            let best = token_sort_ratio(a, b).max(token_set_ratio(a, b));
+            assert_eq!(best, token_ratio(a, b), "{a} {b}");
+        }
+        assert_eq!(0.0, token_ratio("", " "));
+    }
+}
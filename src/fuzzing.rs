@@ -0,0 +1,146 @@
+//! Entry points for fuzz targets, e.g. with `cargo fuzz`.
+//!
+//! Every function takes arbitrary bytes, feeds them to a part of the crate and
+//! panics if an invariant is violated, so a fuzz target only has to forward
+//! its input:
+//!
+//! ```
+//! fuzzt::fuzzing::metrics(b"kitten\0sitting");
+//! ```
+//!
+//! Inputs are truncated to [`MAX_INPUT_LEN`] bytes, since most metrics are
+//! quadratic and long inputs would only slow the fuzzer down.
+
+#[cfg(any(
+    feature = "confusion",
+    feature = "containment",
+    feature = "gotoh",
+    feature = "hamming",
+    feature = "keyboard",
+    feature = "sift4"
+))]
+use crate::algorithms::SimilarityMetric;
+use crate::algorithms::{NGramProfile, Similarity};
+use crate::diagnostics::compare_algorithms;
+use crate::get_top_n_bytes;
+use std::borrow::Cow;
+
+/// The number of bytes of each input that are used.
+pub const MAX_INPUT_LEN: usize = 4096;
+
+fn truncate(data: &[u8]) -> &[u8] {
+    &data[..data.len().min(MAX_INPUT_LEN)]
+}
+
+/// Splits the input at its first NUL byte, or in the middle if there is none,
+/// and decodes both halves lossily.
+fn split(data: &[u8]) -> (Cow<'_, str>, Cow<'_, str>) {
+    let (a, b) = match data.iter().position(|&byte| byte == 0) {
+        Some(index) => (&data[..index], &data[index + 1..]),
+        None => data.split_at(data.len() / 2),
+    };
+    (String::from_utf8_lossy(a), String::from_utf8_lossy(b))
+}
+
+fn check_score(name: &str, score: Similarity) {
+    if let Similarity::Float(r) = score {
+        assert!((0.0..=1.0).contains(&r), "{name} scored {r}");
+    }
+}
+
+/// Scores a pair of strings with every enabled metric and checks that
+/// similarities are between 0.0 and 1.0.
+pub fn metrics(data: &[u8]) {
+    let (a, b) = split(truncate(data));
+    for score in compare_algorithms(&a, &b).scores {
+        check_score(score.name, score.score);
+    }
+
+    #[cfg(feature = "hamming")]
+    crate::algorithms::Hamming.compute_metric(&a, &b);
+    #[cfg(feature = "gotoh")]
+    crate::algorithms::Gotoh::default().compute_metric(&a, &b);
+    #[cfg(feature = "sift4")]
+    crate::algorithms::Sift4::default().compute_metric(&a, &b);
+    #[cfg(feature = "keyboard")]
+    check_score(
+        "keyboard",
+        crate::algorithms::KeyboardLevenshtein::new(
+            &crate::algorithms::KeyboardLayout::qwerty(),
+            0.5,
+        )
+        .compute_metric(&a, &b),
+    );
+    #[cfg(feature = "confusion")]
+    check_score(
+        "confusion",
+        crate::algorithms::ConfusionMatrix::ocr().compute_metric(&a, &b),
+    );
+    #[cfg(feature = "containment")]
+    check_score(
+        "containment",
+        crate::algorithms::Containment::default().compute_metric(&a, &b),
+    );
+}
+
+/// Restores an n-gram profile from the input and checks that it round-trips
+/// and compares with itself like a valid profile.
+pub fn ngram_profile(data: &[u8]) {
+    if let Ok(profile) = NGramProfile::from_bytes(truncate(data)) {
+        let bytes = profile.to_bytes();
+        let restored = NGramProfile::from_bytes(&bytes).expect("serialized profiles are valid");
+        assert_eq!(bytes, restored.to_bytes());
+
+        for score in [
+            profile.dice(&restored),
+            profile.jaccard(&restored),
+            profile.cosine(&restored),
+        ] {
+            assert!(
+                (0.0..=1.0 + 1e-9).contains(&score),
+                "profile scored {score}"
+            );
+        }
+    }
+}
+
+/// Matches the first line of the input against the other lines and checks
+/// that only choices are returned.
+pub fn matcher(data: &[u8]) {
+    let mut lines = truncate(data).split(|&byte| byte == b'\n');
+    let query = lines.next().unwrap_or_default();
+    let choices: Vec<&[u8]> = lines.collect();
+
+    let matches = get_top_n_bytes(query, &choices, Some(0.0), Some(choices.len()), None, None);
+    assert!(matches.len() <= choices.len());
+    assert!(matches.iter().all(|choice| choices.contains(choice)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUTS: [&[u8]; 6] = [
+        b"",
+        b"\0",
+        b"\xff\xfe\0\xc3",
+        b"kitten\0sitting",
+        "öঙ香\0香ö".as_bytes(),
+        b"\n\n\xe9\nab",
+    ];
+
+    #[test]
+    fn fuzzing_entry_points() {
+        for input in INPUTS {
+            metrics(input);
+            ngram_profile(input);
+            matcher(input);
+        }
+    }
+
+    #[test]
+    fn fuzzing_long_input() {
+        metrics(&[b'a'; 3 * MAX_INPUT_LEN]);
+        ngram_profile(&NGramProfile::new("the quick brown fox", 2).to_bytes());
+    }
+}
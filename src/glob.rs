@@ -0,0 +1,127 @@
+//! Wildcard patterns tolerating typos in their literal parts.
+//!
+//! Patterns typed by humans, such as log routing rules, often contain typos
+//! that make exact glob matching miss. [`fuzzy_glob`] matches `*` with any
+//! sequence of characters and `?` with any single character, like a regular
+//! glob, and additionally allows up to `max_errors` insertions, deletions and
+//! substitutions of characters.
+//!
+//! ```
+//! use fuzzt::glob::fuzzy_glob;
+//!
+//! assert!(fuzzy_glob("payments.*.error", "payments.db.error", 0));
+//! assert!(fuzzy_glob("payments.*.error", "paymnets.db.eror", 3));
+//! assert!(!fuzzy_glob("payments.*.error", "billing.db.error", 3));
+//! ```
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    AnyChar,
+    AnySequence,
+}
+
+fn tokenize(pattern: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        tokens.push(match c {
+            '*' => Token::AnySequence,
+            '?' => Token::AnyChar,
+            // a trailing backslash matches itself
+            '\\' => Token::Literal(chars.next().unwrap_or('\\')),
+            c => Token::Literal(c),
+        });
+    }
+    tokens
+}
+
+/// Calculates the minimal number of insertions, deletions and substitutions
+/// of characters needed for `candidate` to match the wildcard `pattern`.
+///
+/// `*` matches any sequence of characters, and `?` any single character. A
+/// `?` can also be deleted, or have characters inserted next to it, each
+/// costing one error like for the other characters. Wildcards are matched
+/// literally when escaped with a backslash, e.g. `\*`.
+///
+/// ```
+/// use fuzzt::glob::fuzzy_glob_distance;
+///
+/// assert_eq!(0, fuzzy_glob_distance("*.log", "server.log"));
+/// assert_eq!(1, fuzzy_glob_distance("*.log", "server.lg"));
+/// assert_eq!(2, fuzzy_glob_distance("???", "a"));
+/// assert_eq!(0, fuzzy_glob_distance(r"what\?", "what?"));
+/// ```
+pub fn fuzzy_glob_distance(pattern: &str, candidate: &str) -> usize {
+    let tokens = tokenize(pattern);
+    let candidate: Vec<char> = candidate.chars().collect();
+
+    // costs of matching the tokens read so far with the first `j` characters
+    // of the candidate
+    let mut previous: Vec<usize> = (0..=candidate.len()).collect();
+    let mut current = vec![0; candidate.len() + 1];
+    for token in tokens {
+        current[0] = match token {
+            Token::AnySequence => previous[0],
+            _ => previous[0] + 1,
+        };
+        for (j, &c) in candidate.iter().enumerate() {
+            current[j + 1] = match token {
+                Token::AnySequence => previous[j + 1].min(current[j]),
+                Token::AnyChar | Token::Literal(_) => {
+                    let substitution = usize::from(matches!(token, Token::Literal(l) if l != c));
+                    (previous[j] + substitution)
+                        .min(previous[j + 1] + 1)
+                        .min(current[j] + 1)
+                }
+            };
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[candidate.len()]
+}
+
+/// Returns `true` if `candidate` matches the wildcard `pattern` with at most
+/// `max_errors` errors, see [`fuzzy_glob_distance`].
+///
+/// ```
+/// use fuzzt::glob::fuzzy_glob;
+///
+/// assert!(fuzzy_glob("user-?-login", "usr-7-login", 1));
+/// assert!(!fuzzy_glob("user-?-login", "usr-7-logn", 1));
+/// ```
+pub fn fuzzy_glob(pattern: &str, candidate: &str, max_errors: usize) -> bool {
+    fuzzy_glob_distance(pattern, candidate) <= max_errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_without_errors() {
+        assert_eq!(0, fuzzy_glob_distance("", ""));
+        assert_eq!(0, fuzzy_glob_distance("*", ""));
+        assert_eq!(0, fuzzy_glob_distance("*", "anything"));
+        assert_eq!(0, fuzzy_glob_distance("a*b*c", "aXXbYYc"));
+        assert_eq!(0, fuzzy_glob_distance("a*b*c", "abc"));
+        assert_eq!(0, fuzzy_glob_distance("?ö?", "香ö香"));
+        assert_eq!(0, fuzzy_glob_distance(r"\*\\", r"*\"));
+        assert_eq!(0, fuzzy_glob_distance("a\\", "a\\"));
+    }
+
+    #[test]
+    fn glob_with_errors() {
+        // without wildcards, the distance is the Levenshtein distance
+        assert_eq!(3, fuzzy_glob_distance("kitten", "sitting"));
+        assert_eq!(3, fuzzy_glob_distance("abc", ""));
+        assert_eq!(3, fuzzy_glob_distance("", "abc"));
+        assert_eq!(1, fuzzy_glob_distance("a?c", "ac"));
+        assert_eq!(1, fuzzy_glob_distance("a?c", "axyc"));
+        assert_eq!(1, fuzzy_glob_distance(r"\*", "a"));
+        assert_eq!(1, fuzzy_glob_distance("app.*.error", "app.db.eror"));
+        assert_eq!(2, fuzzy_glob_distance("app.*.error", "ap.db.eror"));
+        assert!(fuzzy_glob("app.*.error", "ap.db.eror", 2));
+        assert!(!fuzzy_glob("app.*.error", "ap.db.eror", 1));
+    }
+}
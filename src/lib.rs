@@ -20,9 +20,37 @@
 )]
 
 pub mod algorithms;
+pub mod bitap;
+pub mod calibration;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+pub mod diagnostics;
+pub mod dictionary;
+pub mod eval;
+pub mod find;
+pub mod fuzz;
+#[doc(hidden)]
+pub mod fuzzing;
+pub mod glob;
+pub mod process;
 pub mod processors;
+pub mod snapshot;
+pub mod utils;
+#[cfg(feature = "wide")]
+pub mod wide;
 pub use utils::FuzztError;
+mod autocomplete;
+mod chunk;
+mod ensemble;
 mod matcher;
-mod utils;
+mod record;
 
-pub use matcher::get_top_n;
+pub use autocomplete::{AutocompleteSession, MatchResult};
+pub use chunk::{ChunkMatch, ChunkMatcher};
+pub use ensemble::{get_top_n_ensemble, get_top_n_rank_fusion, RankFusion, ScoreNormalization};
+pub use matcher::{
+    get_top_n, get_top_n_bytes, get_top_n_ordered, get_top_n_per_category, get_top_n_preprocessed,
+    get_top_n_weighted, get_top_n_with_payload, get_top_n_with_scores, CharMaskIndex, LossyCorpus,
+    RerankPipeline, TieBreak, DEFAULT_CUTOFF, DEFAULT_N, DEFAULT_PROCESSOR, DEFAULT_SCORER,
+};
+pub use record::RecordMatcher;
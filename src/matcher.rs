@@ -1,26 +1,64 @@
 use crate::{
     algorithms::{SequenceMatcher, Similarity, SimilarityMetric},
+    ensemble::best_n,
     processors::{NullStringProcessor, StringProcessor},
+    utils::{char_mask, mask_overlap},
 };
-use std::cmp::Reverse;
-use std::collections::BinaryHeap;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// Score threshold used by the matchers when no cutoff is given.
+pub const DEFAULT_CUTOFF: f64 = 0.7;
+
+/// Number of matches returned by the matchers when no `n` is given.
+pub const DEFAULT_N: usize = 3;
+
+/// Scorer used by the matchers when no scorer is given.
+pub const DEFAULT_SCORER: &dyn SimilarityMetric = &SequenceMatcher;
+
+/// Processor used by the matchers when no processor is given.
+pub const DEFAULT_PROCESSOR: &dyn StringProcessor = &NullStringProcessor;
+
+/// How matches with the same score are ordered.
+///
+/// Matches are always ordered by decreasing score first, or by increasing
+/// distance for scorers returning distances. Matches with the
+/// same score are ordered by the tie-break, and the remaining ties are
+/// resolved by the position of the choices in the collection, so the same
+/// query and choices always produce the same results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// Order equally scored matches lexicographically. This is what every
+    /// matcher uses unless stated otherwise.
+    #[default]
+    Lexicographic,
+    /// Keep equally scored matches in the order of the choices.
+    OriginalOrder,
+}
 
 /// Returns a list of the best matches to a collection of choices.
 ///
 /// This is a convenience function for getting the choices with the highest scores.
+/// Every matcher of the crate shares the same defaults for omitted arguments,
+/// which are available as [`DEFAULT_CUTOFF`], [`DEFAULT_N`],
+/// [`DEFAULT_PROCESSOR`] and [`DEFAULT_SCORER`].
 ///
 /// # Arguments
 ///
 /// * `query` - A string to match against.
-/// * `choices` - A list of choices to compare against the query.
-/// * `cutoff` - A score threshold. No matches with a score less than this number will be returned. Defaults to 0.7.
+/// * `choices` - A list of choices to compare against the query, e.g. `&str` or `String`.
+/// * `cutoff` - A score threshold. No matches with a score less than this number, or a distance greater than it for scorers returning distances, will be returned. Defaults to 0.7.
 /// * `n` - Optional maximum for the number of elements returned. Defaults to 3.
 /// * `processor` - Optional function for transforming choices before matching. If not provided, `NullStringProcessor` is used.
 /// * `scorer` - Optional scoring function for extract(). If not provided, `SequenceMatcher` is used.
 ///
 /// # Returns
 ///
-/// * A vector of the top 'n' matches from the given choices.
+/// * A vector of the top 'n' matches from the given choices, ordered by
+///   decreasing score, or increasing distance if the scorer returns distances.
+///   Ties are ordered as described by [`TieBreak::Lexicographic`].
 ///
 /// # Example
 ///
@@ -38,48 +76,835 @@ use std::collections::BinaryHeap;
 /// );
 /// assert_eq!(matches, ["apples", "applet", "apply"]);
 /// ```
-pub fn get_top_n<'a>(
+pub fn get_top_n<'a, S>(
     query: &str,
-    choices: &[&'a str],
+    choices: &'a [S],
     cutoff: Option<f64>,
     n: Option<usize>,
     processor: Option<&dyn StringProcessor>,
     scorer: Option<&dyn SimilarityMetric>,
-) -> Vec<&'a str> {
-    let mut matches = BinaryHeap::new();
-    let n = n.unwrap_or(3);
-    let cutoff = cutoff.unwrap_or(0.7);
-    let scorer = match scorer {
-        Some(scorer_trait) => scorer_trait,
-        None => &SequenceMatcher,
-    };
-    let processor = match processor {
-        Some(some_processor) => some_processor,
-        None => &NullStringProcessor,
-    };
-    let processed_query = processor.process(query);
+) -> Vec<&'a str>
+where
+    S: AsRef<str>,
+{
+    let matches = ranked_matches(
+        query,
+        choices.iter().map(AsRef::as_ref),
+        cutoff.or(Some(DEFAULT_CUTOFF)),
+        processor,
+        scorer,
+        false,
+        TieBreak::Lexicographic,
+    );
+    pop_n(matches, n)
+}
+
+/// Returns a list of the best matches to a collection of choices, along with
+/// their scores.
+///
+/// Works like [`get_top_n`], returning the score of each match as given by
+/// the scorer, e.g. to show how confident a match is without scoring it
+/// again.
+///
+/// # Example
+///
+/// ```
+/// extern crate fuzzt;
+/// use fuzzt::{algorithms::NormalizedLevenshtein, get_top_n_with_scores};
+///
+/// let matches = get_top_n_with_scores(
+///     "apple",
+///     &["apply", "apples", "ape", "applesauce"],
+///     Some(0.8),
+///     None,
+///     None,
+///     Some(&NormalizedLevenshtein),
+/// );
+/// assert_eq!(matches, [("apples", 1.0 - 1.0 / 6.0), ("apply", 0.8)]);
+/// ```
+pub fn get_top_n_with_scores<'a, S>(
+    query: &str,
+    choices: &'a [S],
+    cutoff: Option<f64>,
+    n: Option<usize>,
+    processor: Option<&dyn StringProcessor>,
+    scorer: Option<&dyn SimilarityMetric>,
+) -> Vec<(&'a str, f64)>
+where
+    S: AsRef<str>,
+{
+    let matches = ranked_matches(
+        query,
+        choices.iter().map(AsRef::as_ref),
+        cutoff.or(Some(DEFAULT_CUTOFF)),
+        processor,
+        scorer,
+        false,
+        TieBreak::Lexicographic,
+    );
+    pop_n_with_scores(matches, n)
+}
+
+/// Returns a list of the best matches to a collection of choices, ordering
+/// equally scored matches with `tie_break`.
+///
+/// Works like [`get_top_n`], which is the same as passing
+/// `TieBreak::Lexicographic`. With `TieBreak::OriginalOrder`, equally scored
+/// matches keep the order of `choices`, e.g. to favour the choices listed
+/// first when they are sorted by popularity.
+///
+/// # Example
+///
+/// ```
+/// extern crate fuzzt;
+/// use fuzzt::{algorithms::NormalizedLevenshtein, get_top_n_ordered, TieBreak};
+///
+/// let choices = ["cat", "bat", "rat"];
+/// let scorer = Some(&NormalizedLevenshtein as &dyn fuzzt::algorithms::SimilarityMetric);
+/// let lexicographic =
+///     get_top_n_ordered("hat", &choices, Some(0.5), None, None, scorer, TieBreak::Lexicographic);
+/// assert_eq!(lexicographic, ["bat", "cat", "rat"]);
+/// let original =
+///     get_top_n_ordered("hat", &choices, Some(0.5), None, None, scorer, TieBreak::OriginalOrder);
+/// assert_eq!(original, ["cat", "bat", "rat"]);
+/// ```
+pub fn get_top_n_ordered<'a, S>(
+    query: &str,
+    choices: &'a [S],
+    cutoff: Option<f64>,
+    n: Option<usize>,
+    processor: Option<&dyn StringProcessor>,
+    scorer: Option<&dyn SimilarityMetric>,
+    tie_break: TieBreak,
+) -> Vec<&'a str>
+where
+    S: AsRef<str>,
+{
+    let matches = ranked_matches(
+        query,
+        choices.iter().map(AsRef::as_ref),
+        cutoff.or(Some(DEFAULT_CUTOFF)),
+        processor,
+        scorer,
+        false,
+        tie_break,
+    );
+    pop_n(matches, n)
+}
+
+/// Returns a list of the best matches to a collection of choices that were
+/// already transformed by the processor.
+///
+/// Works like [`get_top_n`], but only the query is passed through the
+/// processor. This avoids processing every choice again on each query when the
+/// choices were processed once ahead of time, e.g. with
+/// `LowerAlphaNumStringProcessor` when building the corpus.
+///
+/// # Arguments
+///
+/// * `query` - A string to match against.
+/// * `choices` - A list of processed choices to compare against the query.
+/// * `cutoff` - A score threshold. No matches with a score less than this number, or a distance greater than it for scorers returning distances, will be returned. Defaults to 0.7.
+/// * `n` - Optional maximum for the number of elements returned. Defaults to 3.
+/// * `processor` - Optional function for transforming the query before matching. If not provided, `NullStringProcessor` is used.
+/// * `scorer` - Optional scoring function for extract(). If not provided, `SequenceMatcher` is used.
+///
+/// # Returns
+///
+/// * A vector of the top 'n' matches from the given choices.
+///
+/// # Example
+///
+/// ```
+/// extern crate fuzzt;
+/// use fuzzt::{get_top_n_preprocessed, processors::{LowerAlphaNumStringProcessor, StringProcessor}};
+///
+/// let corpus: Vec<String> = ["BRAZIL", "Brazil!", "Trazil"]
+///     .iter()
+///     .map(|choice| LowerAlphaNumStringProcessor.process(choice))
+///     .collect();
+/// let choices: Vec<&str> = corpus.iter().map(String::as_str).collect();
+///
+/// let matches = get_top_n_preprocessed(
+///     "Brazil",
+///     &choices,
+///     Some(0.9),
+///     None,
+///     Some(&LowerAlphaNumStringProcessor),
+///     None,
+/// );
+/// assert_eq!(matches, ["brazil", "brazil"]);
+/// ```
+pub fn get_top_n_preprocessed<'a, S>(
+    query: &str,
+    choices: &'a [S],
+    cutoff: Option<f64>,
+    n: Option<usize>,
+    processor: Option<&dyn StringProcessor>,
+    scorer: Option<&dyn SimilarityMetric>,
+) -> Vec<&'a str>
+where
+    S: AsRef<str>,
+{
+    let matches = ranked_matches(
+        query,
+        choices.iter().map(AsRef::as_ref),
+        cutoff.or(Some(DEFAULT_CUTOFF)),
+        processor,
+        scorer,
+        true,
+        TieBreak::Lexicographic,
+    );
+    pop_n(matches, n)
+}
+
+/// Returns a list of the best matches to a collection of labelled choices,
+/// keeping at most `per_category` matches for each label.
+///
+/// Works like [`get_top_n`], but every choice carries a category (e.g. a
+/// product family) and matches beyond the quota of their category are skipped
+/// in favour of the next best match of another category.
+///
+/// # Arguments
+///
+/// * `query` - A string to match against.
+/// * `choices` - A list of choices and their categories to compare against the query.
+/// * `cutoff` - A score threshold. No matches with a score less than this number, or a distance greater than it for scorers returning distances, will be returned. Defaults to 0.7.
+/// * `n` - Optional maximum for the number of elements returned. Defaults to 3.
+/// * `per_category` - Maximum number of matches returned for each category.
+/// * `processor` - Optional function for transforming choices before matching. If not provided, `NullStringProcessor` is used.
+/// * `scorer` - Optional scoring function for extract(). If not provided, `SequenceMatcher` is used.
+///
+/// # Returns
+///
+/// * A vector of the top 'n' matches from the given choices.
+///
+/// # Example
+///
+/// ```
+/// extern crate fuzzt;
+/// use fuzzt::{algorithms::NormalizedLevenshtein, get_top_n_per_category};
+///
+/// let matches = get_top_n_per_category(
+///     "apple",
+///     &[("apples", "fruit"), ("applet", "software"), ("apply", "fruit"), ("applesauce", "sauce")],
+///     Some(0.5),
+///     Some(3),
+///     1,
+///     None,
+///     Some(&NormalizedLevenshtein),
+/// );
+/// assert_eq!(matches, ["apples", "applet", "applesauce"]);
+/// ```
+pub fn get_top_n_per_category<'a, S, C>(
+    query: &str,
+    choices: &'a [(S, C)],
+    cutoff: Option<f64>,
+    n: Option<usize>,
+    per_category: usize,
+    processor: Option<&dyn StringProcessor>,
+    scorer: Option<&dyn SimilarityMetric>,
+) -> Vec<&'a str>
+where
+    S: AsRef<str>,
+    C: Eq + Hash,
+{
+    let mut matches = ranked_matches(
+        query,
+        choices.iter().map(|(choice, _)| choice.as_ref()),
+        cutoff.or(Some(DEFAULT_CUTOFF)),
+        processor,
+        scorer,
+        false,
+        TieBreak::Lexicographic,
+    );
+    let n = n.unwrap_or(DEFAULT_N);
+    let mut taken: HashMap<&C, usize> = HashMap::new();
+    let mut rv = vec![];
+    while rv.len() < n {
+        if let Some(RankedMatch { choice, index, .. }) = matches.pop() {
+            let count = taken.entry(&choices[index].1).or_insert(0);
+            if *count < per_category {
+                *count += 1;
+                rv.push(choice);
+            }
+        } else {
+            break;
+        }
+    }
+    rv
+}
+
+/// Returns a list of the best matches to a collection of choices, along with
+/// the payload attached to each of them.
+///
+/// Works like [`get_top_n`], but every choice carries an arbitrary payload
+/// (e.g. the record it was taken from) which is returned with the match. This
+/// avoids looking records up by their string, which is ambiguous when several
+/// records share the same string.
+///
+/// # Arguments
+///
+/// * `query` - A string to match against.
+/// * `choices` - A list of choices and their payloads to compare against the query.
+/// * `cutoff` - A score threshold. No matches with a score less than this number, or a distance greater than it for scorers returning distances, will be returned. Defaults to 0.7.
+/// * `n` - Optional maximum for the number of elements returned. Defaults to 3.
+/// * `processor` - Optional function for transforming choices before matching. If not provided, `NullStringProcessor` is used.
+/// * `scorer` - Optional scoring function for extract(). If not provided, `SequenceMatcher` is used.
+///
+/// # Returns
+///
+/// * A vector of the top 'n' matches from the given choices and their payloads.
+///
+/// # Example
+///
+/// ```
+/// extern crate fuzzt;
+/// use fuzzt::get_top_n_with_payload;
+///
+/// let cities = [("Paris", "FR"), ("Paris", "US"), ("Parma", "IT")];
+/// let matches = get_top_n_with_payload("Paris", &cities, Some(0.9), None, None, None);
+/// assert_eq!(matches, [("Paris", &"FR"), ("Paris", &"US")]);
+/// ```
+pub fn get_top_n_with_payload<'a, S, T>(
+    query: &str,
+    choices: &'a [(S, T)],
+    cutoff: Option<f64>,
+    n: Option<usize>,
+    processor: Option<&dyn StringProcessor>,
+    scorer: Option<&dyn SimilarityMetric>,
+) -> Vec<(&'a str, &'a T)>
+where
+    S: AsRef<str>,
+{
+    let mut matches = ranked_matches(
+        query,
+        choices.iter().map(|(choice, _)| choice.as_ref()),
+        cutoff.or(Some(DEFAULT_CUTOFF)),
+        processor,
+        scorer,
+        false,
+        TieBreak::Lexicographic,
+    );
+    let n = n.unwrap_or(DEFAULT_N);
+    let mut rv = vec![];
+    for _ in 0..n {
+        if let Some(RankedMatch { choice, index, .. }) = matches.pop() {
+            rv.push((choice, &choices[index].1));
+        } else {
+            break;
+        }
+    }
+    rv
+}
+
+/// Returns a list of the best matches to a collection of byte strings, which
+/// may contain invalid UTF-8.
+///
+/// Works like [`get_top_n`], but the query and the choices are decoded with
+/// `String::from_utf8_lossy`, replacing invalid sequences with `U+FFFD`, and
+/// the matches are returned as the original bytes. Use a [`LossyCorpus`] to
+/// only decode the choices once when running several queries.
+///
+/// # Example
+///
+/// ```
+/// extern crate fuzzt;
+/// use fuzzt::get_top_n_bytes;
+///
+/// // "café" in Latin-1 and in UTF-8
+/// let choices = [b"caf\xe9".as_slice(), "café".as_bytes(), b"tea"];
+/// let matches = get_top_n_bytes(b"cafe", &choices, None, None, None, None);
+/// assert_eq!(matches, ["café".as_bytes(), b"caf\xe9"]);
+/// ```
+pub fn get_top_n_bytes<'a, B>(
+    query: &[u8],
+    choices: &'a [B],
+    cutoff: Option<f64>,
+    n: Option<usize>,
+    processor: Option<&dyn StringProcessor>,
+    scorer: Option<&dyn SimilarityMetric>,
+) -> Vec<&'a [u8]>
+where
+    B: AsRef<[u8]>,
+{
+    LossyCorpus::new(choices).get_top_n(query, cutoff, n, processor, scorer)
+}
+
+/// A collection of byte strings decoded once for matching.
+///
+/// Invalid UTF-8 is replaced with `U+FFFD` when the corpus is created, so
+/// datasets with mixed encodings can be matched without cleaning them first.
+/// Choices that are valid UTF-8 are borrowed rather than copied.
+///
+/// # Example
+///
+/// ```
+/// extern crate fuzzt;
+/// use fuzzt::LossyCorpus;
+///
+/// let scraped: [&[u8]; 3] = [b"Z\xfcrich", b"Zurich", b"Geneva"];
+/// let corpus = LossyCorpus::new(&scraped);
+/// assert_eq!(corpus.decoded()[0], "Z\u{FFFD}rich");
+/// assert_eq!(corpus.get_top_n(b"zurich", Some(0.8), None, None, None), [b"Zurich"]);
+/// ```
+pub struct LossyCorpus<'a> {
+    raw: Vec<&'a [u8]>,
+    decoded: Vec<Cow<'a, str>>,
+}
+
+impl<'a> LossyCorpus<'a> {
+    /// Decodes every choice.
+    pub fn new<B>(choices: &'a [B]) -> Self
+    where
+        B: AsRef<[u8]>,
+    {
+        let raw: Vec<&[u8]> = choices.iter().map(AsRef::as_ref).collect();
+        let decoded = raw
+            .iter()
+            .map(|choice| String::from_utf8_lossy(choice))
+            .collect();
+        LossyCorpus { raw, decoded }
+    }
+
+    /// Returns the decoded choices, in the order they were given.
+    pub fn decoded(&self) -> &[Cow<'a, str>] {
+        &self.decoded
+    }
 
-    for &choice in choices {
-        let processed_choice = processor.process(choice);
-        let raw_ratio = scorer.compute_metric(processed_query.as_str(), processed_choice.as_str());
-        let ratio = match raw_ratio {
-            Similarity::Usize(r) => r as f64,
-            Similarity::Float(r) => r,
-        };
-        if ratio >= cutoff {
-            let int_ratio = match raw_ratio {
-                Similarity::Usize(r) => r as i64,
-                Similarity::Float(r) => (r * std::u32::MAX as f64) as i64,
+    /// Returns a list of the best matches to the query, as the original bytes.
+    ///
+    /// The query is decoded like the choices, and the other arguments behave
+    /// like the ones of [`get_top_n`].
+    pub fn get_top_n(
+        &self,
+        query: &[u8],
+        cutoff: Option<f64>,
+        n: Option<usize>,
+        processor: Option<&dyn StringProcessor>,
+        scorer: Option<&dyn SimilarityMetric>,
+    ) -> Vec<&'a [u8]> {
+        let query = String::from_utf8_lossy(query);
+        let mut matches = ranked_matches(
+            &query,
+            self.decoded.iter().map(AsRef::as_ref),
+            cutoff.or(Some(DEFAULT_CUTOFF)),
+            processor,
+            scorer,
+            false,
+            TieBreak::Lexicographic,
+        );
+        let n = n.unwrap_or(DEFAULT_N);
+        let mut rv = vec![];
+        while rv.len() < n {
+            match matches.pop() {
+                Some(RankedMatch { index, .. }) => rv.push(self.raw[index]),
+                None => break,
+            }
+        }
+        rv
+    }
+}
+
+/// A collection of choices that can skip the ones sharing too few characters
+/// with the query before scoring them.
+///
+/// The set of characters of every choice is stored as a bitmask built by
+/// [`char_mask`](crate::utils::char_mask) when the index is created, so
+/// checking how many distinct characters a choice shares with the query takes
+/// a few instructions. For alphabetic data, requiring a few shared characters
+/// discards most choices before the much more expensive scorer runs.
+/// Characters are compared ignoring case and whitespace. Only ASCII letters
+/// and digits are counted exactly: a choice sharing any other character with
+/// the query may share any number of them, so it is always kept. The filter
+/// can therefore keep too many choices, but never drops one that shares
+/// enough characters.
+///
+/// # Example
+///
+/// ```
+/// extern crate fuzzt;
+/// use fuzzt::{algorithms::NormalizedLevenshtein, CharMaskIndex};
+///
+/// let choices = ["apple", "maple", "grape", "kiwi"];
+/// let index = CharMaskIndex::new(&choices);
+/// assert_eq!(index.candidates("appel", 4), ["apple", "maple"]);
+///
+/// let matches = index.get_top_n("appel", 4, Some(0.4), None, None, Some(&NormalizedLevenshtein));
+/// assert_eq!(matches, ["apple", "maple"]);
+/// ```
+pub struct CharMaskIndex<'a> {
+    choices: Vec<&'a str>,
+    masks: Vec<u64>,
+}
+
+impl<'a> CharMaskIndex<'a> {
+    /// Computes the character mask of every choice.
+    pub fn new<S>(choices: &'a [S]) -> Self
+    where
+        S: AsRef<str>,
+    {
+        let choices: Vec<&str> = choices.iter().map(AsRef::as_ref).collect();
+        let masks = choices.iter().map(|choice| char_mask(choice)).collect();
+        CharMaskIndex { choices, masks }
+    }
+
+    /// Returns the number of choices.
+    pub fn len(&self) -> usize {
+        self.choices.len()
+    }
+
+    /// Returns `true` if there are no choices.
+    pub fn is_empty(&self) -> bool {
+        self.choices.is_empty()
+    }
+
+    /// Returns the choices sharing at least `min_shared` distinct characters
+    /// with the query, in the order they were given.
+    pub fn candidates(&self, query: &str, min_shared: u32) -> Vec<&'a str> {
+        let query_mask = char_mask(query);
+        self.choices
+            .iter()
+            .zip(&self.masks)
+            .filter(|(_, &mask)| mask_overlap(mask, query_mask) >= min_shared)
+            .map(|(&choice, _)| choice)
+            .collect()
+    }
+
+    /// Returns a list of the best matches to the query among the choices
+    /// sharing at least `min_shared` distinct characters with it.
+    ///
+    /// The other arguments behave like the ones of [`get_top_n`]. The
+    /// characters are compared before the strings are processed.
+    pub fn get_top_n(
+        &self,
+        query: &str,
+        min_shared: u32,
+        cutoff: Option<f64>,
+        n: Option<usize>,
+        processor: Option<&dyn StringProcessor>,
+        scorer: Option<&dyn SimilarityMetric>,
+    ) -> Vec<&'a str> {
+        let matches = ranked_matches(
+            query,
+            self.candidates(query, min_shared),
+            cutoff.or(Some(DEFAULT_CUTOFF)),
+            processor,
+            scorer,
+            false,
+            TieBreak::Lexicographic,
+        );
+        pop_n(matches, n)
+    }
+}
+
+/// Returns a list of the best matches to a collection of choices that carry a
+/// prior weight, such as their popularity.
+///
+/// Works like [`get_top_n`], but the matches are ranked by
+/// `(1 - prior_factor) * score + prior_factor * prior`, so commonly used
+/// choices are preferred among equally similar candidates. The `cutoff` is
+/// applied to the blended score, so it is a minimum between 0.0 and 1.0 even
+/// for scorers returning distances. Priors are expected to
+/// be in the same range as the scores of `scorer`, and distances are turned
+/// into similarities between 0.0 and 1.0 with [`Similarity::normalize`] first,
+/// so their priors should be between 0.0 and 1.0 too.
+///
+/// # Arguments
+///
+/// * `query` - A string to match against.
+/// * `choices` - A list of choices and their priors to compare against the query.
+/// * `cutoff` - A threshold on the blended score. No matches with a blended score less than this number will be returned. Defaults to 0.7.
+/// * `n` - Optional maximum for the number of elements returned. Defaults to 3.
+/// * `prior_factor` - How much the prior contributes to the final score, between 0.0 and 1.0.
+/// * `processor` - Optional function for transforming choices before matching. If not provided, `NullStringProcessor` is used.
+/// * `scorer` - Optional scoring function for extract(). If not provided, `SequenceMatcher` is used.
+///
+/// # Returns
+///
+/// * A vector of the top 'n' matches from the given choices.
+///
+/// # Example
+///
+/// ```
+/// extern crate fuzzt;
+/// use fuzzt::{algorithms::NormalizedLevenshtein, get_top_n_weighted};
+///
+/// let matches = get_top_n_weighted(
+///     "paris",
+///     &[("parma", 0.2), ("pariz", 0.1), ("parks", 0.9)],
+///     Some(0.5),
+///     Some(2),
+///     0.3,
+///     None,
+///     Some(&NormalizedLevenshtein),
+/// );
+/// assert_eq!(matches, ["parks", "pariz"]);
+/// ```
+pub fn get_top_n_weighted<'a, S>(
+    query: &str,
+    choices: &'a [(S, f64)],
+    cutoff: Option<f64>,
+    n: Option<usize>,
+    prior_factor: f64,
+    processor: Option<&dyn StringProcessor>,
+    scorer: Option<&dyn SimilarityMetric>,
+) -> Vec<&'a str>
+where
+    S: AsRef<str>,
+{
+    let matches = scored_matches(
+        query,
+        choices.iter().map(|(choice, _)| choice.as_ref()),
+        None,
+        processor,
+        scorer,
+        false,
+    );
+    // distances are normalized by the length of the longer processed string,
+    // so they blend with the priors like similarities
+    let processor = processor.unwrap_or(DEFAULT_PROCESSOR);
+    let query_len = processor.process(query).chars().count();
+    let (candidates, scores): (Vec<&str>, Vec<f64>) = matches
+        .into_iter()
+        .map(|(index, choice, raw_ratio)| {
+            let ratio = if raw_ratio.is_distance() {
+                let choice_len = processor.process(choice).chars().count();
+                raw_ratio.normalize(query_len.max(choice_len))
+            } else {
+                raw_ratio.as_f64()
             };
-            // we're putting the word itself in reverse in so that matches with
-            // the same ratio are ordered lexicographically.
-            matches.push((int_ratio, Reverse(choice)));
+            let prior = choices[index].1;
+            (choice, (1.0 - prior_factor) * ratio + prior_factor * prior)
+        })
+        .unzip();
+    best_n(
+        &candidates,
+        &scores,
+        Some(cutoff.unwrap_or(DEFAULT_CUTOFF)),
+        n,
+    )
+}
+
+/// A two-stage matcher: a fast `retriever` metric selects the best
+/// `candidates` choices, which are then reranked by a slower but more accurate
+/// `reranker` metric.
+///
+/// This makes it affordable to use expensive metrics over large collections
+/// of choices. When used directly as a `SimilarityMetric`, the pipeline scores
+/// pairs with the reranker.
+///
+/// # Example
+///
+/// ```
+/// extern crate fuzzt;
+/// use fuzzt::{
+///     algorithms::{NormalizedDamerauLevenshtein, SorensenDice},
+///     RerankPipeline,
+/// };
+///
+/// let pipeline = RerankPipeline::new(&SorensenDice, &NormalizedDamerauLevenshtein, 3);
+/// let matches = pipeline.get_top_n(
+///     "apple",
+///     &["apply", "apples", "ape", "applet", "applesauce"],
+///     Some(0.8),
+///     Some(2),
+///     None,
+/// );
+/// assert_eq!(matches, ["apples", "applet"]);
+/// ```
+pub struct RerankPipeline<'m> {
+    retriever: &'m dyn SimilarityMetric,
+    reranker: &'m dyn SimilarityMetric,
+    candidates: usize,
+}
+
+impl<'m> RerankPipeline<'m> {
+    /// Creates a pipeline that reranks the best `candidates` choices found by
+    /// `retriever` with `reranker`.
+    pub fn new(
+        retriever: &'m dyn SimilarityMetric,
+        reranker: &'m dyn SimilarityMetric,
+        candidates: usize,
+    ) -> Self {
+        RerankPipeline {
+            retriever,
+            reranker,
+            candidates,
         }
     }
+
+    /// Returns a list of the best matches to a collection of choices.
+    ///
+    /// The arguments behave like the ones of [`get_top_n`]. The `cutoff` is
+    /// applied to the scores of the reranker, the retrieval stage keeps the
+    /// best candidates regardless of their score.
+    pub fn get_top_n<'a, S>(
+        &self,
+        query: &str,
+        choices: &'a [S],
+        cutoff: Option<f64>,
+        n: Option<usize>,
+        processor: Option<&dyn StringProcessor>,
+    ) -> Vec<&'a str>
+    where
+        S: AsRef<str>,
+    {
+        let mut retrieved = ranked_matches(
+            query,
+            choices.iter().map(AsRef::as_ref),
+            None,
+            processor,
+            Some(self.retriever),
+            false,
+            TieBreak::Lexicographic,
+        );
+        let mut candidates = Vec::with_capacity(self.candidates.min(retrieved.len()));
+        while candidates.len() < self.candidates {
+            match retrieved.pop() {
+                Some(RankedMatch { choice, .. }) => candidates.push(choice),
+                None => break,
+            }
+        }
+
+        let reranked = ranked_matches(
+            query,
+            candidates,
+            cutoff.or(Some(DEFAULT_CUTOFF)),
+            processor,
+            Some(self.reranker),
+            false,
+            TieBreak::Lexicographic,
+        );
+        pop_n(reranked, n)
+    }
+}
+
+impl SimilarityMetric for RerankPipeline<'_> {
+    fn compute_metric(&self, a: &str, b: &str) -> Similarity {
+        self.reranker.compute_metric(a, b)
+    }
+}
+
+/// A scored choice, ordered so that the best match is the greatest.
+struct RankedMatch<'a> {
+    score: f64,
+    // the score, negated for distances so that the highest one is the best
+    rank: f64,
+    choice: &'a str,
+    index: usize,
+    tie_break: TieBreak,
+}
+
+impl PartialEq for RankedMatch<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for RankedMatch<'_> {}
+
+impl PartialOrd for RankedMatch<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RankedMatch<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_matches(
+            self.tie_break,
+            (other.rank, other.choice, other.index),
+            (self.rank, self.choice, self.index),
+        )
+    }
+}
+
+/// Returns the score of a result such that higher is better, negating
+/// distances.
+pub(crate) fn rank_key(result: Similarity) -> f64 {
+    if result.is_distance() {
+        -result.as_f64()
+    } else {
+        result.as_f64()
+    }
+}
+
+/// Returns `true` if the result is at least `cutoff`, or at most `cutoff` for
+/// distances.
+pub(crate) fn meets_cutoff(result: Similarity, cutoff: f64) -> bool {
+    if result.is_distance() {
+        result.as_f64() <= cutoff
+    } else {
+        result.as_f64() >= cutoff
+    }
+}
+
+/// Compares two `(score, choice, index)` matches, returning `Less` if `a`
+/// ranks before `b`. This is the ordering documented on [`TieBreak`], shared
+/// by every matcher so they all agree on how ties are resolved.
+pub(crate) fn compare_matches(
+    tie_break: TieBreak,
+    a: (f64, &str, usize),
+    b: (f64, &str, usize),
+) -> Ordering {
+    let (a_score, a_choice, a_index) = a;
+    let (b_score, b_choice, b_index) = b;
+    b_score
+        .total_cmp(&a_score)
+        .then_with(|| match tie_break {
+            TieBreak::Lexicographic => a_choice.cmp(b_choice),
+            TieBreak::OriginalOrder => Ordering::Equal,
+        })
+        .then(a_index.cmp(&b_index))
+}
+
+/// Scores every choice against the query and returns the ones meeting the
+/// cutoff, if any, in a heap that pops the best match first. Each entry holds
+/// the original index of the choice.
+fn ranked_matches<'a, I>(
+    query: &str,
+    choices: I,
+    cutoff: Option<f64>,
+    processor: Option<&dyn StringProcessor>,
+    scorer: Option<&dyn SimilarityMetric>,
+    preprocessed: bool,
+    tie_break: TieBreak,
+) -> BinaryHeap<RankedMatch<'a>>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    scored_matches(query, choices, cutoff, processor, scorer, preprocessed)
+        .into_iter()
+        .map(|(index, choice, raw_ratio)| RankedMatch {
+            score: raw_ratio.as_f64(),
+            rank: rank_key(raw_ratio),
+            choice,
+            index,
+            tie_break,
+        })
+        .collect()
+}
+
+/// Pops the `n` best matches off the heap, defaulting to `DEFAULT_N`.
+fn pop_n(matches: BinaryHeap<RankedMatch<'_>>, n: Option<usize>) -> Vec<&str> {
+    pop_n_with_scores(matches, n)
+        .into_iter()
+        .map(|(choice, _)| choice)
+        .collect()
+}
+
+/// Pops the `n` best matches off the heap along with their scores,
+/// defaulting to `DEFAULT_N`.
+fn pop_n_with_scores(
+    mut matches: BinaryHeap<RankedMatch<'_>>,
+    n: Option<usize>,
+) -> Vec<(&str, f64)> {
+    let n = n.unwrap_or(DEFAULT_N);
     let mut rv = vec![];
     for _ in 0..n {
-        if let Some((_, elt)) = matches.pop() {
-            rv.push(elt.0);
+        if let Some(RankedMatch { choice, score, .. }) = matches.pop() {
+            rv.push((choice, score));
         } else {
             break;
         }
@@ -87,13 +912,72 @@ pub fn get_top_n<'a>(
     rv
 }
 
+/// Scores every choice against the query and returns the ones meeting the
+/// cutoff, if any, along with their original index. Choices are only passed
+/// through the processor if they aren't `preprocessed`.
+fn scored_matches<'a, I>(
+    query: &str,
+    choices: I,
+    cutoff: Option<f64>,
+    processor: Option<&dyn StringProcessor>,
+    scorer: Option<&dyn SimilarityMetric>,
+    preprocessed: bool,
+) -> Vec<(usize, &'a str, Similarity)>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    scored_iter(query, choices, cutoff, processor, scorer, preprocessed).collect()
+}
+
+/// Like [`scored_matches`], but scores the choices lazily, in order, as the
+/// iterator is advanced.
+pub(crate) fn scored_iter<'a, 'm, I>(
+    query: &str,
+    choices: I,
+    cutoff: Option<f64>,
+    processor: Option<&'m dyn StringProcessor>,
+    scorer: Option<&'m dyn SimilarityMetric>,
+    preprocessed: bool,
+) -> impl Iterator<Item = (usize, &'a str, Similarity)> + 'm
+where
+    'a: 'm,
+    I: IntoIterator<Item = &'a str>,
+    I::IntoIter: 'm,
+{
+    let scorer = scorer.unwrap_or(DEFAULT_SCORER);
+    let processor = processor.unwrap_or(DEFAULT_PROCESSOR);
+    let processed_query = processor.process(query);
+
+    choices
+        .into_iter()
+        .enumerate()
+        .filter_map(move |(index, choice)| {
+            let raw_ratio = if preprocessed {
+                scorer.compute_metric(processed_query.as_str(), choice)
+            } else {
+                let processed_choice = processor.process(choice);
+                scorer.compute_metric(processed_query.as_str(), processed_choice.as_str())
+            };
+            cutoff
+                .map_or(true, |cutoff| meets_cutoff(raw_ratio, cutoff))
+                .then_some((index, choice, raw_ratio))
+        })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::get_top_n;
+    use super::{
+        get_top_n, get_top_n_bytes, get_top_n_ordered, get_top_n_per_category,
+        get_top_n_preprocessed, get_top_n_weighted, get_top_n_with_payload, get_top_n_with_scores,
+        CharMaskIndex, LossyCorpus, RerankPipeline, TieBreak, DEFAULT_CUTOFF, DEFAULT_N,
+        DEFAULT_PROCESSOR, DEFAULT_SCORER,
+    };
     use crate::algorithms::jaro::JaroWinkler;
     use crate::algorithms::SimilarityMetric;
+    use crate::algorithms::{Levenshtein, NormalizedDamerauLevenshtein, SorensenDice};
     use crate::processors::{LowerAlphaNumStringProcessor, StringProcessor};
     use rstest::rstest;
+    use std::borrow::Cow;
 
     #[rstest]
     #[case(Some(0.7), Some(3), None, None, &["brazil", "braziu", "trazil"])]
@@ -112,4 +996,285 @@ mod tests {
         let matches = get_top_n(query, choices, cutoff, n, processor, scorer);
         assert_eq!(matches, expected);
     }
+
+    #[test]
+    fn test_get_top_n_with_scores() {
+        let choices = &["trazil", "BRA ZIL", "brazil", "spain", "braziu"][..];
+        for n in [None, Some(1), Some(5)] {
+            let matches = get_top_n_with_scores("brazil", choices, Some(0.5), n, None, None);
+            let (found, scores): (Vec<&str>, Vec<f64>) = matches.into_iter().unzip();
+            assert_eq!(
+                get_top_n("brazil", choices, Some(0.5), n, None, None),
+                found
+            );
+            for (choice, score) in found.into_iter().zip(scores) {
+                assert_eq!(
+                    DEFAULT_SCORER.compute_metric("brazil", choice).as_f64(),
+                    score
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(1, Some(3), &["brazil", "trazil"])]
+    #[case(2, Some(3), &["brazil", "braziu", "trazil"])]
+    #[case(1, Some(1), &["brazil"])]
+    fn test_get_top_n_per_category<'a>(
+        #[case] per_category: usize,
+        #[case] n: Option<usize>,
+        #[case] expected: &[&'a str],
+    ) {
+        let choices = &[
+            ("trazil", 2),
+            ("BRA ZIL", 1),
+            ("brazil", 1),
+            ("spain", 2),
+            ("braziu", 1),
+        ][..];
+        let matches =
+            get_top_n_per_category("brazil", choices, Some(0.7), n, per_category, None, None);
+        assert_eq!(matches, expected);
+    }
+
+    #[test]
+    fn test_rerank_pipeline() {
+        let choices = &["trazil", "BRA ZIL", "brazil", "spain", "braziu"][..];
+        let pipeline = RerankPipeline::new(&SorensenDice, &NormalizedDamerauLevenshtein, 2);
+        // the retriever only keeps "brazil" and one of the "razil" matches
+        let matches = pipeline.get_top_n("brazil", choices, Some(0.0), Some(5), None);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0], "brazil");
+
+        let pipeline = RerankPipeline::new(&SorensenDice, &NormalizedDamerauLevenshtein, 5);
+        let matches = pipeline.get_top_n("brazil", choices, Some(0.8), Some(5), None);
+        assert_eq!(matches, ["brazil", "braziu", "trazil"]);
+    }
+
+    #[rstest]
+    #[case(0.0, &["brazil", "braziu", "trazil"])]
+    #[case(0.5, &["trazil", "brazil"])]
+    fn test_get_top_n_weighted<'a>(#[case] prior_factor: f64, #[case] expected: &[&'a str]) {
+        let choices = &[
+            ("trazil", 1.0),
+            ("BRA ZIL", 1.0),
+            ("brazil", 0.5),
+            ("spain", 1.0),
+            ("braziu", 0.2),
+        ][..];
+        let matches = get_top_n_weighted(
+            "brazil",
+            choices,
+            Some(0.7),
+            Some(3),
+            prior_factor,
+            None,
+            None,
+        );
+        assert_eq!(matches, expected);
+    }
+
+    #[test]
+    fn test_get_top_n_weighted_normalizes_distances() {
+        let choices = &[("kitten", 0.0), ("sitting", 1.0), ("mitten", 0.0)][..];
+        let matches = get_top_n_weighted(
+            "kitten",
+            choices,
+            Some(0.0),
+            None,
+            0.5,
+            None,
+            Some(&Levenshtein),
+        );
+        assert_eq!(matches, ["sitting", "kitten", "mitten"]);
+    }
+
+    #[test]
+    fn test_get_top_n_with_payload() {
+        let choices = &[
+            ("trazil", 0),
+            ("BRA ZIL", 1),
+            ("brazil", 2),
+            ("spain", 3),
+            ("brazil", 4),
+        ][..];
+        let matches = get_top_n_with_payload("brazil", choices, Some(0.7), Some(3), None, None);
+        assert_eq!(matches, [("brazil", &2), ("brazil", &4), ("trazil", &0)]);
+    }
+
+    #[test]
+    fn test_get_top_n_preprocessed() {
+        // the processor is only applied to the query, so "BRA ZIL" is compared
+        // as is
+        let choices = &["trazil", "BRA ZIL", "brazil", "spain", "braziu"][..];
+        let matches = get_top_n_preprocessed(
+            "BRAZIL",
+            choices,
+            Some(0.7),
+            Some(3),
+            Some(&LowerAlphaNumStringProcessor),
+            None,
+        );
+        assert_eq!(matches, ["brazil", "braziu", "trazil"]);
+    }
+
+    #[test]
+    fn test_get_top_n_defaults() {
+        let choices = &["trazil", "BRA ZIL", "brazil", "spain", "braziu"][..];
+        assert_eq!(
+            get_top_n("brazil", choices, None, None, None, None),
+            get_top_n(
+                "brazil",
+                choices,
+                Some(DEFAULT_CUTOFF),
+                Some(DEFAULT_N),
+                Some(DEFAULT_PROCESSOR),
+                Some(DEFAULT_SCORER),
+            )
+        );
+    }
+
+    #[test]
+    fn test_get_top_n_owned_choices() {
+        let choices: Vec<String> = ["trazil", "BRA ZIL", "brazil", "spain", "braziu"]
+            .iter()
+            .map(|choice| choice.to_string())
+            .collect();
+        let matches = get_top_n("brazil", &choices, None, None, None, None);
+        assert_eq!(matches, ["brazil", "braziu", "trazil"]);
+
+        let labelled: Vec<(String, u8)> = choices.iter().cloned().zip(0..).collect();
+        let matches = get_top_n_with_payload("brazil", &labelled, None, Some(1), None, None);
+        assert_eq!(matches, [("brazil", &2)]);
+    }
+
+    #[test]
+    fn test_tie_break() {
+        let choices = ["dab", "cab", "dab", "bab"];
+        let lexicographic = get_top_n_ordered(
+            "aab",
+            &choices,
+            Some(0.0),
+            Some(4),
+            None,
+            None,
+            TieBreak::Lexicographic,
+        );
+        assert_eq!(lexicographic, ["bab", "cab", "dab", "dab"]);
+        assert_eq!(
+            lexicographic,
+            get_top_n("aab", &choices, Some(0.0), Some(4), None, None)
+        );
+
+        let original = get_top_n_ordered(
+            "aab",
+            &choices,
+            Some(0.0),
+            Some(4),
+            None,
+            None,
+            TieBreak::OriginalOrder,
+        );
+        assert_eq!(original, ["dab", "cab", "dab", "bab"]);
+    }
+
+    #[test]
+    fn test_ties_ordered_by_index() {
+        let choices = [("paris", 'a'), ("paris", 'b'), ("paris", 'c')];
+        let matches = get_top_n_with_payload("paris", &choices, None, None, None, None);
+        assert_eq!(matches, [("paris", &'a'), ("paris", &'b'), ("paris", &'c')]);
+    }
+
+    #[test]
+    fn test_ties_ordered_consistently() {
+        let choices = ["dab", "cab", "dab", "bab"];
+        let weighted: Vec<(&str, f64)> = choices.iter().map(|&choice| (choice, 0.5)).collect();
+        assert_eq!(
+            get_top_n("aab", &choices, Some(0.0), Some(4), None, None),
+            get_top_n_weighted("aab", &weighted, Some(0.0), Some(4), 0.2, None, None)
+        );
+    }
+
+    #[test]
+    fn test_lossy_corpus() {
+        let choices: Vec<Vec<u8>> = vec![vec![0xff, 0xfe], b"abc".to_vec(), b"ab\xc3".to_vec()];
+        let corpus = LossyCorpus::new(&choices);
+        assert!(matches!(corpus.decoded()[1], Cow::Borrowed("abc")));
+        assert_eq!(corpus.decoded()[2], "ab\u{FFFD}");
+
+        let matches = corpus.get_top_n(b"abc", Some(0.5), None, None, None);
+        assert_eq!(matches, [b"abc".as_slice(), b"ab\xc3"]);
+        assert_eq!(
+            matches,
+            get_top_n_bytes(b"abc", &choices, Some(0.5), None, None, None)
+        );
+    }
+
+    #[test]
+    fn test_get_top_n_ranks_distances_lowest_first() {
+        let choices = ["kitten", "sitting", "mitten"];
+        let matches = get_top_n(
+            "kitten",
+            &choices,
+            Some(3.0),
+            None,
+            None,
+            Some(&Levenshtein),
+        );
+        assert_eq!(matches, ["kitten", "mitten", "sitting"]);
+        let matches = get_top_n_with_scores(
+            "kitten",
+            &choices,
+            Some(3.0),
+            None,
+            None,
+            Some(&Levenshtein),
+        );
+        assert_eq!(
+            matches,
+            [("kitten", 0.0), ("mitten", 1.0), ("sitting", 3.0)]
+        );
+    }
+
+    #[test]
+    fn test_get_top_n_distance_cutoff_is_a_maximum() {
+        let choices = ["kitten", "mitten", "sitting", "banana"];
+        let scorer = Some(&Levenshtein as &dyn SimilarityMetric);
+        assert_eq!(
+            get_top_n("kitten", &choices, None, None, None, scorer),
+            ["kitten"]
+        );
+        assert_eq!(
+            get_top_n("kitten", &choices, Some(2.0), None, None, scorer),
+            ["kitten", "mitten"]
+        );
+        assert_eq!(
+            get_top_n("kitten", &choices, Some(6.0), Some(4), None, scorer),
+            ["kitten", "mitten", "sitting", "banana"]
+        );
+    }
+
+    #[test]
+    fn test_char_mask_index() {
+        let choices = ["Paris", "parís", "Lyon", "", "Pa ris"];
+        let index = CharMaskIndex::new(&choices);
+        assert_eq!(5, index.len());
+        assert_eq!(index.candidates("paris", 5), ["Paris", "Pa ris"]);
+        assert_eq!(index.candidates("paris", 0), choices);
+        assert!(index.candidates("", 1).is_empty());
+        // other characters are never required
+        assert_eq!(index.candidates("íß", 5), ["parís"]);
+        let punctuation = CharMaskIndex::new(&["!=", "<=", "->"]);
+        assert_eq!(punctuation.candidates("!=", 2), ["!=", "<="]);
+
+        let matches = index.get_top_n(
+            "paris",
+            4,
+            Some(0.0),
+            Some(5),
+            Some(&LowerAlphaNumStringProcessor),
+            None,
+        );
+        assert_eq!(matches, ["Paris", "Pa ris", "parís"]);
+    }
 }
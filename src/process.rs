@@ -0,0 +1,281 @@
+//! Matching a query against a collection of choices like the `process` module
+//! of the Python `RapidFuzz` library.
+//!
+//! [`extract`], [`extract_one`] and [`extract_iter`] take the same arguments as
+//! their `RapidFuzz` counterparts, in the same order, and return each match as
+//! a [`MatchResult`] holding the choice, its score and its index, like the
+//! `(choice, score, index)` tuples of `RapidFuzz`. Matches are ordered by
+//! decreasing score, or increasing distance for scorers returning distances,
+//! and equally scored matches keep the order of the choices.
+//!
+//! Omitted arguments default to the ones of the other matchers of the crate,
+//! [`DEFAULT_SCORER`], [`DEFAULT_PROCESSOR`], [`DEFAULT_N`] and
+//! [`DEFAULT_CUTOFF`], rather than to the ones of `RapidFuzz`. Scores are on the
+//! scale of the scorer, so a cutoff is between 0.0 and 1.0 for the normalized
+//! metrics of [`crate::algorithms`]. Like in `RapidFuzz`, the cutoff is a
+//! maximum for scorers returning distances.
+//!
+//! ```
+//! use fuzzt::algorithms::NormalizedLevenshtein;
+//! use fuzzt::process::extract_one;
+//!
+//! let choices = ["Atlanta Falcons", "New York Jets", "New York Giants", "Dallas Cowboys"];
+//! let best = extract_one("new york jets", &choices, Some(&NormalizedLevenshtein), None, None)
+//!     .unwrap();
+//! assert_eq!((best.choice, best.index), ("New York Jets", 1));
+//! ```
+//!
+//! [`DEFAULT_SCORER`]: crate::DEFAULT_SCORER
+//! [`DEFAULT_PROCESSOR`]: crate::DEFAULT_PROCESSOR
+//! [`DEFAULT_N`]: crate::DEFAULT_N
+//! [`DEFAULT_CUTOFF`]: crate::DEFAULT_CUTOFF
+
+use crate::{
+    algorithms::SimilarityMetric,
+    matcher::{compare_matches, rank_key, scored_iter, TieBreak, DEFAULT_CUTOFF, DEFAULT_N},
+    processors::StringProcessor,
+    MatchResult,
+};
+use std::cmp::Ordering;
+
+/// Returns the best `limit` matches of `query` among `choices` scoring at
+/// least `score_cutoff`, or at most for distances, ordered by decreasing
+/// score, like `process.extract`.
+///
+/// `limit` defaults to [`DEFAULT_N`](crate::DEFAULT_N); pass
+/// `Some(choices.len())` to get every match, like `limit=None` in `RapidFuzz`.
+///
+/// ```
+/// use fuzzt::algorithms::{NormalizedLevenshtein, SimilarityMetric};
+/// use fuzzt::process::extract;
+///
+/// let choices = ["apply", "apples", "ape", "applet"];
+/// let scorer = Some(&NormalizedLevenshtein as &dyn SimilarityMetric);
+/// let matches = extract("apple", &choices, scorer, None, Some(2), Some(0.8));
+/// let found: Vec<(&str, usize)> = matches.iter().map(|m| (m.choice, m.index)).collect();
+/// assert_eq!(found, [("apples", 1), ("applet", 3)]);
+/// ```
+pub fn extract<'a, S>(
+    query: &str,
+    choices: &'a [S],
+    scorer: Option<&dyn SimilarityMetric>,
+    processor: Option<&dyn StringProcessor>,
+    limit: Option<usize>,
+    score_cutoff: Option<f64>,
+) -> Vec<MatchResult<'a>>
+where
+    S: AsRef<str>,
+{
+    let mut matches: Vec<(f64, MatchResult<'a>)> =
+        ranked_iter(query, choices, scorer, processor, score_cutoff).collect();
+    matches.sort_by(|a, b| compare_ranked(a, b));
+    matches.truncate(limit.unwrap_or(DEFAULT_N));
+    matches.into_iter().map(|(_, found)| found).collect()
+}
+
+/// Returns the best match of `query` among `choices` scoring at least
+/// `score_cutoff`, or at most for distances, or `None` if there is none, like
+/// `process.extractOne`.
+///
+/// Of equally scored matches, the first choice is returned.
+///
+/// ```
+/// use fuzzt::algorithms::NormalizedLevenshtein;
+/// use fuzzt::process::extract_one;
+///
+/// let choices = ["cat", "bat", "rat"];
+/// let best = extract_one("hat", &choices, Some(&NormalizedLevenshtein), None, Some(0.5)).unwrap();
+/// assert_eq!((best.choice, best.index), ("cat", 0));
+/// assert_eq!(None, extract_one("dog", &choices, Some(&NormalizedLevenshtein), None, Some(0.5)));
+/// ```
+pub fn extract_one<'a, S>(
+    query: &str,
+    choices: &'a [S],
+    scorer: Option<&dyn SimilarityMetric>,
+    processor: Option<&dyn StringProcessor>,
+    score_cutoff: Option<f64>,
+) -> Option<MatchResult<'a>>
+where
+    S: AsRef<str>,
+{
+    ranked_iter(query, choices, scorer, processor, score_cutoff)
+        .min_by(compare_ranked)
+        .map(|(_, found)| found)
+}
+
+/// Returns an iterator over the matches of `query` among `choices` scoring at
+/// least `score_cutoff`, or at most for distances, in the order of the
+/// choices, like `process.extract_iter`.
+///
+/// Choices are scored lazily as the iterator is advanced, so the search can
+/// stop early, e.g. at the first good enough match.
+///
+/// ```
+/// use fuzzt::algorithms::NormalizedLevenshtein;
+/// use fuzzt::process::extract_iter;
+///
+/// let choices = ["kitten", "sitting", "mitten", "smitten"];
+/// let mut matches = extract_iter("mitten", &choices, Some(&NormalizedLevenshtein), None, None);
+/// assert_eq!(matches.next().map(|m| (m.choice, m.index)), Some(("kitten", 0)));
+/// assert_eq!(matches.next().map(|m| m.score), Some(1.0));
+/// ```
+pub fn extract_iter<'a, 'm, S>(
+    query: &str,
+    choices: &'a [S],
+    scorer: Option<&'m dyn SimilarityMetric>,
+    processor: Option<&'m dyn StringProcessor>,
+    score_cutoff: Option<f64>,
+) -> impl Iterator<Item = MatchResult<'a>> + 'm
+where
+    'a: 'm,
+    S: AsRef<str>,
+{
+    ranked_iter(query, choices, scorer, processor, score_cutoff).map(|(_, found)| found)
+}
+
+/// Like [`extract_iter`], along with the score of each match negated for
+/// distances, so that the best match has the highest one.
+fn ranked_iter<'a, 'm, S>(
+    query: &str,
+    choices: &'a [S],
+    scorer: Option<&'m dyn SimilarityMetric>,
+    processor: Option<&'m dyn StringProcessor>,
+    score_cutoff: Option<f64>,
+) -> impl Iterator<Item = (f64, MatchResult<'a>)> + 'm
+where
+    'a: 'm,
+    S: AsRef<str>,
+{
+    scored_iter(
+        query,
+        choices.iter().map(AsRef::as_ref),
+        score_cutoff.or(Some(DEFAULT_CUTOFF)),
+        processor,
+        scorer,
+        false,
+    )
+    .map(|(index, choice, score)| {
+        let found = MatchResult {
+            choice,
+            index,
+            score: score.as_f64(),
+        };
+        (rank_key(score), found)
+    })
+}
+
+/// Orders ranked matches like [`compare_matches`], keeping the order of ties.
+fn compare_ranked(a: &(f64, MatchResult<'_>), b: &(f64, MatchResult<'_>)) -> Ordering {
+    compare_matches(
+        TieBreak::OriginalOrder,
+        (a.0, a.1.choice, a.1.index),
+        (b.0, b.1.choice, b.1.index),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{Levenshtein, NormalizedLevenshtein, SequenceMatcher};
+    use crate::get_top_n_ordered;
+    use crate::processors::LowerAlphaNumStringProcessor;
+
+    const CHOICES: [&str; 5] = ["trazil", "BRA ZIL", "brazil", "spain", "braziu"];
+
+    #[test]
+    fn extract_matches_get_top_n_ordered() {
+        for limit in [None, Some(1), Some(5)] {
+            for cutoff in [None, Some(0.5), Some(0.9)] {
+                let matches = extract("brazil", &CHOICES, None, None, limit, cutoff);
+                let choices: Vec<&str> = matches.iter().map(|m| m.choice).collect();
+                assert_eq!(
+                    get_top_n_ordered(
+                        "brazil",
+                        &CHOICES,
+                        cutoff,
+                        limit,
+                        None,
+                        None,
+                        TieBreak::OriginalOrder
+                    ),
+                    choices
+                );
+                for m in matches {
+                    assert_eq!(CHOICES[m.index], m.choice);
+                    assert_eq!(
+                        SequenceMatcher.compute_metric("brazil", m.choice).as_f64(),
+                        m.score
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn extract_keeps_order_of_ties() {
+        let choices = ["rat", "cat", "bat", "hat"];
+        let matches = extract(
+            "hat",
+            &choices,
+            Some(&NormalizedLevenshtein),
+            None,
+            Some(4),
+            Some(0.5),
+        );
+        let indices: Vec<usize> = matches.iter().map(|m| m.index).collect();
+        assert_eq!(indices, [3, 0, 1, 2]);
+        let best = extract_one(
+            "zat",
+            &choices,
+            Some(&NormalizedLevenshtein),
+            None,
+            Some(0.5),
+        );
+        assert_eq!(Some(0), best.map(|m| m.index));
+    }
+
+    #[test]
+    fn extract_ranks_distances_lowest_first() {
+        let choices = ["sitting", "kitten", "mitten"];
+        let scorer = Some(&Levenshtein as &dyn SimilarityMetric);
+        let matches = extract("kitten", &choices, scorer, None, None, Some(3.0));
+        let indices: Vec<usize> = matches.iter().map(|m| m.index).collect();
+        assert_eq!(indices, [1, 2, 0]);
+        let best = extract_one("kitten", &choices, scorer, None, Some(3.0));
+        assert_eq!(Some(("kitten", 0.0)), best.map(|m| (m.choice, m.score)));
+    }
+
+    #[test]
+    fn extract_distance_cutoff_is_a_maximum() {
+        let choices = ["kitten", "mitten", "sitting", "banana"];
+        let scorer = Some(&Levenshtein as &dyn SimilarityMetric);
+        let matches = extract("kitten", &choices, scorer, None, None, Some(2.0));
+        let found: Vec<(&str, f64)> = matches.iter().map(|m| (m.choice, m.score)).collect();
+        assert_eq!(found, [("kitten", 0.0), ("mitten", 1.0)]);
+        let best = extract_one("dog", &choices, scorer, None, Some(2.0));
+        assert_eq!(None, best);
+        let all: Vec<usize> = extract_iter("kitten", &choices, scorer, None, Some(3.0))
+            .map(|m| m.index)
+            .collect();
+        assert_eq!(all, [0, 1, 2]);
+    }
+
+    #[test]
+    fn extract_processes_choices() {
+        let processor = Some(&LowerAlphaNumStringProcessor as &dyn StringProcessor);
+        let best = extract_one("Brazil!", &CHOICES, None, processor, Some(1.0)).unwrap();
+        assert_eq!(("brazil", 2, 1.0), (best.choice, best.index, best.score));
+        assert_eq!(
+            None,
+            extract_one("Brazil!", &CHOICES, None, None, Some(1.0))
+        );
+        let all: Vec<usize> = extract_iter("brazil", &CHOICES, None, processor, Some(0.0))
+            .map(|m| m.index)
+            .collect();
+        assert_eq!(all, [0, 1, 2, 3, 4]);
+        assert_eq!(
+            None,
+            extract_one("brazil", &[] as &[&str], None, None, None)
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use super::StringProcessor;
+use std::num::NonZeroUsize;
+use std::thread;
+
+/// Applies `processor` to every choice, in order.
+///
+/// This is meant to preprocess a corpus once, e.g. before matching it with
+/// `get_top_n_preprocessed`, rather than processing each choice on every
+/// query.
+///
+/// ```
+/// use fuzzt::processors::{process_all, LowerAlphaNumStringProcessor};
+///
+/// let processed = process_all(&["New York!", " Paris "], &LowerAlphaNumStringProcessor);
+/// assert_eq!(processed, ["new york", "paris"]);
+/// ```
+pub fn process_all<S>(choices: &[S], processor: &dyn StringProcessor) -> Vec<String>
+where
+    S: AsRef<str>,
+{
+    let mut processed = Vec::with_capacity(choices.len());
+    processed.extend(
+        choices
+            .iter()
+            .map(|choice| processor.process(choice.as_ref())),
+    );
+    processed
+}
+
+/// Applies `processor` to every choice on several threads, in order.
+///
+/// The choices are split into one contiguous chunk per thread, and the
+/// processed chunks are concatenated, so the result is the same as the one of
+/// [`process_all`]. If `threads` isn't provided, the available parallelism of
+/// the machine is used.
+///
+/// ```
+/// use fuzzt::processors::{process_all_parallel, LowerAlphaNumStringProcessor};
+///
+/// let choices: Vec<String> = (0..1000).map(|i| format!("Choice #{i}")).collect();
+/// let processed = process_all_parallel(&choices, &LowerAlphaNumStringProcessor, Some(4));
+/// assert_eq!(processed[42], "choice 42");
+/// ```
+pub fn process_all_parallel<S>(
+    choices: &[S],
+    processor: &(dyn StringProcessor + Sync),
+    threads: Option<usize>,
+) -> Vec<String>
+where
+    S: AsRef<str> + Sync,
+{
+    let threads = threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get))
+        .max(1);
+    if threads == 1 || choices.len() < 2 {
+        return process_all(choices, processor);
+    }
+
+    let chunk_len = (choices.len() + threads - 1) / threads;
+    thread::scope(|scope| {
+        let handles: Vec<_> = choices
+            .chunks(chunk_len)
+            .map(|chunk| scope.spawn(move || process_all(chunk, processor)))
+            .collect();
+        let mut processed = Vec::with_capacity(choices.len());
+        for handle in handles {
+            processed.extend(handle.join().expect("a processor panicked"));
+        }
+        processed
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::{LowerAlphaNumStringProcessor, NullStringProcessor};
+
+    #[test]
+    fn parallel_processing_keeps_order() {
+        let choices: Vec<String> = (0..101).map(|i| format!("Choice-{i}")).collect();
+        let expected = process_all(&choices, &LowerAlphaNumStringProcessor);
+        assert_eq!(expected[7], "choice7");
+        for threads in [None, Some(0), Some(1), Some(3), Some(101), Some(500)] {
+            assert_eq!(
+                expected,
+                process_all_parallel(&choices, &LowerAlphaNumStringProcessor, threads)
+            );
+        }
+        let empty: [&str; 0] = [];
+        assert!(process_all_parallel(&empty, &NullStringProcessor, Some(4)).is_empty());
+    }
+}
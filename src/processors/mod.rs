@@ -1,6 +1,19 @@
+mod batch;
+mod mojibake;
 mod simple_processors;
+mod sketch;
+mod tokenizers;
+pub use batch::{process_all, process_all_parallel};
+pub use mojibake::{repair_mojibake, MojibakeStringProcessor};
 pub use simple_processors::{LowerAlphaNumStringProcessor, NullStringProcessor};
+pub use sketch::BigramSketcher;
+pub use tokenizers::{CompoundTokenizer, ConnectorMode, WhitespaceTokenizer, WordTokenizer};
 
 pub trait StringProcessor {
     fn process(&self, s: &str) -> String;
 }
+
+/// Splits strings into the tokens compared by token-based metrics.
+pub trait Tokenizer {
+    fn tokenize<'a>(&self, s: &'a str) -> Vec<&'a str>;
+}
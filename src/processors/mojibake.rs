@@ -0,0 +1,132 @@
+use super::StringProcessor;
+
+// the characters Windows-1252 decodes the bytes 0x80 to 0x9F to, the bytes it
+// leaves undefined being decoded like Latin-1
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+];
+
+/// Returns the byte that Windows-1252 or Latin-1 decodes to `c`, if any.
+fn legacy_byte(c: char) -> Option<u8> {
+    if let Some(i) = WINDOWS_1252_HIGH.iter().position(|&high| high == c) {
+        return Some(0x80 + i as u8);
+    }
+    u8::try_from(u32::from(c)).ok()
+}
+
+/// Repairs one layer of UTF-8 text that was decoded as Windows-1252 or
+/// Latin-1, leaving the characters that aren't part of such a sequence as
+/// they are. Returns `None` if nothing was repaired.
+fn repair_once(s: &str) -> Option<String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut repaired = String::with_capacity(s.len());
+    let mut changed = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let len = match legacy_byte(chars[i]) {
+            Some(0xC2..=0xDF) => 2,
+            Some(0xE0..=0xEF) => 3,
+            Some(0xF0..=0xF4) => 4,
+            _ => 1,
+        };
+        let bytes: Option<Vec<u8>> = chars
+            .get(i..i + len)
+            .filter(|_| len > 1)
+            .and_then(|sequence| sequence.iter().map(|&c| legacy_byte(c)).collect());
+        match bytes.as_deref().map(std::str::from_utf8) {
+            Some(Ok(decoded)) => {
+                repaired.push_str(decoded);
+                changed = true;
+                i += len;
+            }
+            _ => {
+                repaired.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+    changed.then_some(repaired)
+}
+
+/// Repairs text that was encoded as UTF-8 and then decoded as Windows-1252 or
+/// Latin-1, possibly several times, such as `"cafÃ©"` for `"café"`.
+///
+/// Each sequence of characters that would be valid UTF-8 once turned back
+/// into bytes is replaced by the character it encodes, so text mixing
+/// repaired and correct parts is handled. Text without such sequences is
+/// returned unchanged.
+///
+/// ```
+/// use fuzzt::processors::repair_mojibake;
+///
+/// assert_eq!("café", repair_mojibake("cafÃ©"));
+/// assert_eq!("€5 – naïve", repair_mojibake("â‚¬5 â€“ naÃ¯ve"));
+/// assert_eq!("é", repair_mojibake("ÃƒÂ©"));
+/// assert_eq!("café", repair_mojibake("café"));
+/// ```
+pub fn repair_mojibake(s: &str) -> String {
+    let mut repaired = s.to_owned();
+    while let Some(next) = repair_once(&repaired) {
+        repaired = next;
+    }
+    repaired
+}
+
+/// Repairs double-encoded text before it is compared, so strings ingested
+/// from legacy systems match their correctly encoded counterparts. See
+/// [`repair_mojibake`].
+///
+/// ```
+/// use fuzzt::{get_top_n, processors::MojibakeStringProcessor};
+///
+/// let names = ["Ã‰lodie", "Eloise", "Hélène"];
+/// let matches = get_top_n("Élodie", &names, Some(0.9), None, Some(&MojibakeStringProcessor), None);
+/// assert_eq!(matches, ["Ã‰lodie"]);
+/// ```
+pub struct MojibakeStringProcessor;
+
+impl StringProcessor for MojibakeStringProcessor {
+    fn process(&self, input: &str) -> String {
+        repair_mojibake(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mojibake_round_trips() {
+        // encode as UTF-8 and decode as Windows-1252, like a legacy system
+        let garble = |s: &str| -> String {
+            s.bytes()
+                .map(|b| match b {
+                    0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(b - 0x80)],
+                    b => char::from(b),
+                })
+                .collect()
+        };
+        for text in [
+            "",
+            "plain ascii",
+            "Zoë Ørsted",
+            "öঙ香",
+            "“quoted” — 😀",
+            "Œuvre Š",
+        ] {
+            assert_eq!(text, repair_mojibake(&garble(text)), "{text}");
+            assert_eq!(text, repair_mojibake(&garble(&garble(text))), "{text}");
+        }
+    }
+
+    #[test]
+    fn mojibake_keeps_valid_text() {
+        assert_eq!("Ã", repair_mojibake("Ã"));
+        assert_eq!("Ã x", repair_mojibake("Ã x"));
+        assert_eq!("naïve café", repair_mojibake("naïve café"));
+        assert_eq!("«é»", repair_mojibake("«Ã©»"));
+    }
+}
@@ -0,0 +1,185 @@
+use super::StringProcessor;
+use std::collections::{HashMap, HashSet};
+
+/// Reduces strings to short sketches that sort similar strings next to each
+/// other, to be used as the sorting key of a sorted neighbourhood blocking or
+/// by external tools.
+///
+/// A sketch is built from the rarest bigrams of the lowercased alphanumeric
+/// characters of a string, ordered from the rarest to the most common, with
+/// ties broken alphabetically. Rare bigrams are the most distinctive parts of
+/// a string, so strings sharing them sort close together even if they differ
+/// elsewhere. Frequencies are counted over a corpus, usually the strings that
+/// are sorted.
+///
+/// Bigrams found in fewer than `min_frequency` strings of the corpus, 2 by
+/// default, are skipped unless a string has no other bigrams: they are mostly
+/// typos or parts unique to a single string, which would separate it from its
+/// duplicates.
+///
+/// ```
+/// use fuzzt::processors::BigramSketcher;
+///
+/// let corpus = [
+///     "apple iphone 12",
+///     "apple iphone 13",
+///     "samsung galaxy s21",
+///     "samsung galaxy s22",
+/// ];
+/// let sketcher = BigramSketcher::from_corpus(corpus, 2);
+/// assert_eq!(sketcher.sketch("Apple iPhone 12"), sketcher.sketch("aple iphone-12"));
+/// assert_ne!(sketcher.sketch("Apple iPhone 12"), sketcher.sketch("Samsung Galaxy S21"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigramSketcher {
+    // number of strings of the corpus containing each bigram
+    frequencies: HashMap<(char, char), usize>,
+    len: usize,
+    min_frequency: usize,
+}
+
+impl BigramSketcher {
+    /// Creates a sketcher keeping `len` bigrams, without frequencies. Until
+    /// strings are added, sketches keep the alphabetically first bigrams.
+    pub fn new(len: usize) -> Self {
+        BigramSketcher {
+            frequencies: HashMap::new(),
+            len,
+            min_frequency: 2,
+        }
+    }
+
+    /// Sets the number of strings of the corpus a bigram must be found in to
+    /// be part of sketches.
+    pub fn with_min_frequency(mut self, min_frequency: usize) -> Self {
+        self.min_frequency = min_frequency;
+        self
+    }
+
+    /// Creates a sketcher keeping `len` bigrams, with the frequencies of the
+    /// bigrams of `corpus`.
+    pub fn from_corpus<'a, I>(corpus: I, len: usize) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut sketcher = BigramSketcher::new(len);
+        for s in corpus {
+            sketcher.add(s);
+        }
+        sketcher
+    }
+
+    /// Counts the bigrams of `s`, each at most once.
+    pub fn add(&mut self, s: &str) {
+        for bigram in bigrams(s) {
+            *self.frequencies.entry(bigram).or_insert(0) += 1;
+        }
+    }
+
+    /// Returns the number of strings of the corpus containing the bigram.
+    pub fn frequency(&self, bigram: (char, char)) -> usize {
+        self.frequencies.get(&bigram).copied().unwrap_or(0)
+    }
+
+    /// Returns the sketch of `s`. Strings with fewer than two alphanumeric
+    /// characters are their own sketch, lowercased.
+    pub fn sketch(&self, s: &str) -> String {
+        let all: Vec<(char, char)> = bigrams(s).into_iter().collect();
+        if all.is_empty() {
+            return normalize(s).into_iter().collect();
+        }
+        let mut bigrams: Vec<(char, char)> = all
+            .iter()
+            .copied()
+            .filter(|&bigram| self.frequency(bigram) >= self.min_frequency)
+            .collect();
+        if bigrams.is_empty() {
+            bigrams = all;
+        }
+        bigrams.sort_unstable_by_key(|&bigram| (self.frequency(bigram), bigram));
+        bigrams
+            .into_iter()
+            .take(self.len)
+            .flat_map(|(first, second)| [first, second])
+            .collect()
+    }
+}
+
+impl Default for BigramSketcher {
+    /// Keeps 3 bigrams, without frequencies.
+    fn default() -> Self {
+        BigramSketcher::new(3)
+    }
+}
+
+impl StringProcessor for BigramSketcher {
+    fn process(&self, input: &str) -> String {
+        self.sketch(input)
+    }
+}
+
+fn normalize(s: &str) -> Vec<char> {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn bigrams(s: &str) -> HashSet<(char, char)> {
+    normalize(s)
+        .windows(2)
+        .map(|window| (window[0], window[1]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sketch_without_frequencies() {
+        let sketcher = BigramSketcher::default();
+        assert_eq!("", sketcher.sketch(""));
+        assert_eq!("a", sketcher.sketch(" A! "));
+        assert_eq!("abbccd", sketcher.sketch("A-b, C d"));
+        assert_eq!(sketcher.sketch("abcd"), sketcher.process("ABCD"));
+        assert_eq!("öঙ", BigramSketcher::new(1).sketch("Öঙ香"));
+        assert_eq!("", BigramSketcher::new(0).sketch("abcd"));
+    }
+
+    #[test]
+    fn sketch_prefers_rare_bigrams() {
+        let corpus = ["jonathan smith", "jonathon smith", "maria garcia"];
+        let sketcher = BigramSketcher::from_corpus(corpus, 3);
+        assert_eq!(2, sketcher.frequency(('s', 'm')));
+        assert_eq!(1, sketcher.frequency(('a', 'n')));
+        assert_eq!(0, sketcher.frequency(('x', 'y')));
+        // "an" and "ha" are unique to the first name, and skipped
+        assert_eq!("atitjo", sketcher.sketch("Jonathan Smith"));
+        assert_eq!("atitjo", sketcher.sketch("jonathon smith"));
+        assert_eq!("atjomi", sketcher.sketch("Jonathan Smiht"));
+        assert_eq!("agarci", sketcher.sketch("maria garcia"));
+
+        let sketcher = sketcher.with_min_frequency(1);
+        assert_eq!("anhaat", sketcher.sketch("Jonathan Smith"));
+        assert_eq!("hoatit", sketcher.sketch("jonathon smith"));
+    }
+
+    #[test]
+    fn sketch_sorts_similar_strings_together() {
+        let corpus = [
+            "acme corporation",
+            "globex inc",
+            "acme corp",
+            "initech llc",
+            "globex incorporated",
+            "initech",
+        ];
+        let sketcher = BigramSketcher::from_corpus(corpus, 2);
+        let mut sorted = corpus.to_vec();
+        sorted.sort_by_key(|s| sketcher.sketch(s));
+        for pair in sorted.chunks(2) {
+            assert_eq!(pair[0][..4], pair[1][..4], "{sorted:?}");
+        }
+    }
+}
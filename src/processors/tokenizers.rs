@@ -0,0 +1,214 @@
+use super::Tokenizer;
+
+/// Splits strings on whitespace.
+pub struct WhitespaceTokenizer;
+
+/// Splits strings into words, i.e. runs of alphanumeric characters. Any other
+/// character, such as punctuation, separates words and is dropped.
+pub struct WordTokenizer;
+
+/// How a [`CompoundTokenizer`] handles a character joining two words, such as
+/// the hyphen in "state-of-the-art".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConnectorMode {
+    /// Keeps the joined words as a single token.
+    Keep,
+    /// Splits the joined words into separate tokens.
+    #[default]
+    Split,
+    /// Splits the joined words, and also keeps the joined token.
+    SplitAndKeepJoined,
+}
+
+/// Splits strings into words like [`WordTokenizer`], but with configurable
+/// handling of the hyphens, apostrophes and underscores between words.
+///
+/// Only connectors between two alphanumeric characters join words, so those
+/// at the start or end of a word are always dropped. By default all
+/// connectors split words.
+///
+/// ```
+/// use fuzzt::processors::{CompoundTokenizer, ConnectorMode, Tokenizer};
+///
+/// let tokenizer = CompoundTokenizer::new()
+///     .with_apostrophes(ConnectorMode::Keep)
+///     .with_hyphens(ConnectorMode::SplitAndKeepJoined);
+/// assert_eq!(
+///     tokenizer.tokenize("rock'n'roll is state-of-the-art"),
+///     ["rock'n'roll", "is", "state", "of", "the", "art", "state-of-the-art"]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompoundTokenizer {
+    hyphens: ConnectorMode,
+    apostrophes: ConnectorMode,
+    underscores: ConnectorMode,
+}
+
+impl CompoundTokenizer {
+    /// Creates a tokenizer splitting words at every connector.
+    pub fn new() -> Self {
+        CompoundTokenizer::default()
+    }
+
+    /// Sets how hyphens (`-` and `‐`) are handled.
+    pub fn with_hyphens(mut self, mode: ConnectorMode) -> Self {
+        self.hyphens = mode;
+        self
+    }
+
+    /// Sets how apostrophes (`'` and `’`) are handled.
+    pub fn with_apostrophes(mut self, mode: ConnectorMode) -> Self {
+        self.apostrophes = mode;
+        self
+    }
+
+    /// Sets how underscores are handled.
+    pub fn with_underscores(mut self, mode: ConnectorMode) -> Self {
+        self.underscores = mode;
+        self
+    }
+
+    fn mode(&self, connector: &str) -> Option<ConnectorMode> {
+        match connector {
+            "-" | "\u{2010}" => Some(self.hyphens),
+            "'" | "\u{2019}" => Some(self.apostrophes),
+            "_" => Some(self.underscores),
+            _ => None,
+        }
+    }
+
+    /// Pushes the tokens of a run of words, along with the modes of the
+    /// connectors between them.
+    fn push_compound<'a>(
+        s: &'a str,
+        words: &[(usize, usize)],
+        modes: &[ConnectorMode],
+        tokens: &mut Vec<&'a str>,
+    ) {
+        let span = |first: usize, last: usize| &s[words[first].0..words[last].1];
+        let mut unit_start = 0;
+        for unit_end in 0..words.len() {
+            if unit_end < modes.len() && modes[unit_end] != ConnectorMode::Split {
+                continue;
+            }
+            let mut part_start = unit_start;
+            for (part_end, &mode) in modes.iter().enumerate().take(unit_end).skip(unit_start) {
+                if mode == ConnectorMode::SplitAndKeepJoined {
+                    tokens.push(span(part_start, part_end));
+                    part_start = part_end + 1;
+                }
+            }
+            tokens.push(span(part_start, unit_end));
+            if part_start != unit_start {
+                tokens.push(span(unit_start, unit_end));
+            }
+            unit_start = unit_end + 1;
+        }
+    }
+}
+
+impl Tokenizer for CompoundTokenizer {
+    fn tokenize<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        let mut tokens = vec![];
+        // the byte ranges of the words of the current compound, and the modes
+        // of the connectors between them
+        let mut words: Vec<(usize, usize)> = vec![];
+        let mut modes = vec![];
+
+        for token in WordTokenizer.tokenize(s) {
+            let start = token.as_ptr() as usize - s.as_ptr() as usize;
+            let end = start + token.len();
+            let joined = words
+                .last()
+                .and_then(|&(_, previous_end)| self.mode(&s[previous_end..start]));
+            match joined {
+                Some(mode) => modes.push(mode),
+                None if !words.is_empty() => {
+                    Self::push_compound(s, &words, &modes, &mut tokens);
+                    words.clear();
+                    modes.clear();
+                }
+                None => {}
+            }
+            words.push((start, end));
+        }
+        if !words.is_empty() {
+            Self::push_compound(s, &words, &modes, &mut tokens);
+        }
+        tokens
+    }
+}
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn tokenize<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        s.split_whitespace().collect()
+    }
+}
+
+impl Tokenizer for WordTokenizer {
+    fn tokenize<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whitespace_tokenizer() {
+        assert_eq!(
+            WhitespaceTokenizer.tokenize("  the quick,\tbrown fox "),
+            ["the", "quick,", "brown", "fox"]
+        );
+        assert!(WhitespaceTokenizer.tokenize(" \n ").is_empty());
+    }
+
+    #[test]
+    fn compound_tokenizer_modes() {
+        let text = "'tis rock'n'roll, my_var state-of-the-art dogs' ";
+        assert_eq!(
+            CompoundTokenizer::new().tokenize(text),
+            WordTokenizer.tokenize(text)
+        );
+        assert_eq!(
+            CompoundTokenizer::new()
+                .with_hyphens(ConnectorMode::Keep)
+                .with_apostrophes(ConnectorMode::Keep)
+                .with_underscores(ConnectorMode::Keep)
+                .tokenize(text),
+            ["tis", "rock'n'roll", "my_var", "state-of-the-art", "dogs"]
+        );
+        assert_eq!(
+            CompoundTokenizer::new()
+                .with_underscores(ConnectorMode::SplitAndKeepJoined)
+                .tokenize("a_b-c_d e"),
+            ["a", "b", "a_b", "c", "d", "c_d", "e"]
+        );
+    }
+
+    #[test]
+    fn compound_tokenizer_mixed_connectors() {
+        let tokenizer = CompoundTokenizer::new()
+            .with_hyphens(ConnectorMode::Keep)
+            .with_apostrophes(ConnectorMode::SplitAndKeepJoined);
+        assert_eq!(
+            tokenizer.tokenize("o’neill-smith won't"),
+            ["o", "neill-smith", "o’neill-smith", "won", "t", "won't"]
+        );
+        assert_eq!(tokenizer.tokenize("a--b"), ["a", "b"]);
+        assert!(tokenizer.tokenize("-'_").is_empty());
+    }
+
+    #[test]
+    fn word_tokenizer() {
+        assert_eq!(
+            WordTokenizer.tokenize("the quick,brown-fox's café 42"),
+            ["the", "quick", "brown", "fox", "s", "café", "42"]
+        );
+        assert!(WordTokenizer.tokenize("?!").is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+use crate::{
+    algorithms::SimilarityMetric,
+    matcher::{compare_matches, TieBreak, DEFAULT_CUTOFF, DEFAULT_N, DEFAULT_PROCESSOR},
+    processors::StringProcessor,
+};
+
+type Field<'m, R> = Box<dyn Fn(&R) -> &str + 'm>;
+
+struct ScoredField<'m, R> {
+    field: Field<'m, R>,
+    scorer: &'m dyn SimilarityMetric,
+    weight: f64,
+}
+
+/// Matches structured records field by field.
+///
+/// Records are first restricted to the ones sharing every blocking key with
+/// the query (e.g. the same country or postcode), which is cheap and keeps
+/// obviously different records from being scored at all. The remaining
+/// records are scored by comparing each scored field with its own metric, and
+/// their score is the weighted average of the field scores. Distances are
+/// normalized by the length of the longer field, so they can be mixed with
+/// similarities.
+///
+/// # Example
+///
+/// ```
+/// extern crate fuzzt;
+/// use fuzzt::{
+///     algorithms::{JaroWinkler, NormalizedLevenshtein},
+///     RecordMatcher,
+/// };
+///
+/// struct Person {
+///     name: String,
+///     city: String,
+///     country: String,
+/// }
+///
+/// let person = |name: &str, city: &str, country: &str| Person {
+///     name: name.to_string(),
+///     city: city.to_string(),
+///     country: country.to_string(),
+/// };
+/// let people = [
+///     person("Jon Smith", "Lyon", "FR"),
+///     person("John Smith", "London", "UK"),
+///     person("Joan Smyth", "Leeds", "UK"),
+/// ];
+///
+/// let matcher = RecordMatcher::new()
+///     .block_on(|p: &Person| p.country.as_str())
+///     .field(|p: &Person| p.name.as_str(), &JaroWinkler, 2.0)
+///     .field(|p: &Person| p.city.as_str(), &NormalizedLevenshtein, 1.0);
+///
+/// let query = person("John Smith", "Londn", "UK");
+/// let matches = matcher.get_top_n(&query, &people, Some(0.8), None, None);
+/// assert_eq!(matches.len(), 1);
+/// assert_eq!(matches[0].city, "London");
+/// ```
+pub struct RecordMatcher<'m, R> {
+    blocking_keys: Vec<Field<'m, R>>,
+    fields: Vec<ScoredField<'m, R>>,
+}
+
+impl<'m, R> RecordMatcher<'m, R> {
+    /// Creates a matcher without any blocking key or scored field.
+    pub fn new() -> Self {
+        RecordMatcher {
+            blocking_keys: vec![],
+            fields: vec![],
+        }
+    }
+
+    /// Adds a blocking key. Only records whose key is equal to the key of the
+    /// query are scored.
+    pub fn block_on<F>(mut self, key: F) -> Self
+    where
+        F: Fn(&R) -> &str + 'm,
+    {
+        self.blocking_keys.push(Box::new(key));
+        self
+    }
+
+    /// Adds a field compared with `scorer`, counting `weight` times in the
+    /// score of the record.
+    pub fn field<F>(mut self, field: F, scorer: &'m dyn SimilarityMetric, weight: f64) -> Self
+    where
+        F: Fn(&R) -> &str + 'm,
+    {
+        self.fields.push(ScoredField {
+            field: Box::new(field),
+            scorer,
+            weight,
+        });
+        self
+    }
+
+    /// Scores `record` against `query`, or returns `None` if they don't share
+    /// every blocking key. Records without scored fields score 1.0.
+    ///
+    /// Fields are passed through `processor` before they are compared. If not
+    /// provided, `NullStringProcessor` is used.
+    pub fn score(
+        &self,
+        query: &R,
+        record: &R,
+        processor: Option<&dyn StringProcessor>,
+    ) -> Option<f64> {
+        if self
+            .blocking_keys
+            .iter()
+            .any(|key| key(query) != key(record))
+        {
+            return None;
+        }
+
+        let processor = processor.unwrap_or(DEFAULT_PROCESSOR);
+        let mut total = 0.0;
+        let mut weights = 0.0;
+        for ScoredField {
+            field,
+            scorer,
+            weight,
+        } in &self.fields
+        {
+            let a = processor.process(field(query));
+            let b = processor.process(field(record));
+            let max_len = a.chars().count().max(b.chars().count());
+            total += weight * scorer.compute_metric(&a, &b).normalize(max_len);
+            weights += weight;
+        }
+        if weights == 0.0 {
+            return Some(1.0);
+        }
+        Some(total / weights)
+    }
+
+    /// Returns the records that best match the query.
+    ///
+    /// Only records sharing the blocking keys of the query and scoring at
+    /// least `cutoff` (0.7 if not provided) are returned, at most `n` of them
+    /// (3 if not provided). Records with the same score keep their order.
+    pub fn get_top_n<'a>(
+        &self,
+        query: &R,
+        records: &'a [R],
+        cutoff: Option<f64>,
+        n: Option<usize>,
+        processor: Option<&dyn StringProcessor>,
+    ) -> Vec<&'a R> {
+        let cutoff = cutoff.unwrap_or(DEFAULT_CUTOFF);
+        let mut matches: Vec<(usize, f64)> = records
+            .iter()
+            .enumerate()
+            .filter_map(|(index, record)| {
+                self.score(query, record, processor)
+                    .filter(|&score| score >= cutoff)
+                    .map(|score| (index, score))
+            })
+            .collect();
+        matches.sort_by(|&(i, a), &(j, b)| {
+            compare_matches(TieBreak::OriginalOrder, (a, "", i), (b, "", j))
+        });
+        matches
+            .into_iter()
+            .take(n.unwrap_or(DEFAULT_N))
+            .map(|(index, _)| &records[index])
+            .collect()
+    }
+}
+
+impl<R> Default for RecordMatcher<'_, R> {
+    fn default() -> Self {
+        RecordMatcher::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RecordMatcher;
+    use crate::algorithms::{Levenshtein, SequenceMatcher};
+    use crate::processors::LowerAlphaNumStringProcessor;
+
+    struct Product {
+        brand: &'static str,
+        title: &'static str,
+    }
+
+    const PRODUCTS: [Product; 4] = [
+        Product {
+            brand: "acme",
+            title: "rocket skates",
+        },
+        Product {
+            brand: "globex",
+            title: "rocket skates",
+        },
+        Product {
+            brand: "acme",
+            title: "rocket skate",
+        },
+        Product {
+            brand: "acme",
+            title: "anvil",
+        },
+    ];
+
+    #[test]
+    fn record_matcher_blocks_and_ranks() {
+        let matcher = RecordMatcher::new().block_on(|p: &Product| p.brand).field(
+            |p: &Product| p.title,
+            &SequenceMatcher,
+            1.0,
+        );
+        let query = Product {
+            brand: "acme",
+            title: "Rocket Skates!",
+        };
+
+        assert_eq!(None, matcher.score(&query, &PRODUCTS[1], None));
+        let matches = matcher.get_top_n(
+            &query,
+            &PRODUCTS,
+            None,
+            None,
+            Some(&LowerAlphaNumStringProcessor),
+        );
+        let titles: Vec<&str> = matches.iter().map(|p| p.title).collect();
+        assert_eq!(titles, ["rocket skates", "rocket skate"]);
+    }
+
+    #[test]
+    fn record_matcher_normalizes_distances() {
+        let matcher = RecordMatcher::new()
+            .field(|p: &Product| p.brand, &Levenshtein, 1.0)
+            .field(|p: &Product| p.title, &Levenshtein, 3.0);
+        let score = matcher.score(&PRODUCTS[0], &PRODUCTS[2], None).unwrap();
+        assert!((score - (1.0 + 3.0 * 12.0 / 13.0) / 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn record_matcher_without_fields() {
+        let matcher = RecordMatcher::new().block_on(|p: &Product| p.title);
+        let matches = matcher.get_top_n(&PRODUCTS[0], &PRODUCTS, None, None, None);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[1].brand, "globex");
+    }
+}
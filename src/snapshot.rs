@@ -0,0 +1,312 @@
+//! Descriptions of matcher configurations that can be stored alongside the
+//! thresholds or indexes produced with them, and checked when they are
+//! loaded again.
+//!
+//! Scorers and processors are trait objects, so they are recorded by name
+//! along with a fingerprint of their behaviour: the hash of their results on
+//! a fixed set of probe strings. A fix or an optimisation that changes the
+//! scores of an algorithm on these strings between two versions of the crate
+//! changes its fingerprint, even if its name and parameters stay the same, so
+//! a stored threshold isn't silently reused with different scores. The probes
+//! cover the usual edge cases and include strings longer than 64 and 128
+//! characters, so the implementations for long strings are checked too, but
+//! a change that only affects other strings goes unnoticed.
+//!
+//! ```
+//! use fuzzt::algorithms::{JaroWinkler, NormalizedLevenshtein};
+//! use fuzzt::processors::LowerAlphaNumStringProcessor;
+//! use fuzzt::snapshot::ConfigSnapshot;
+//!
+//! let snapshot = ConfigSnapshot::new()
+//!     .with_scorer("scorer", &JaroWinkler)
+//!     .with_processor("processor", &LowerAlphaNumStringProcessor)
+//!     .with_param("cutoff", 0.87);
+//! let stored = snapshot.export();
+//!
+//! // later, possibly with another version of the crate
+//! assert!(snapshot.verify(&stored).is_ok());
+//! let changed = ConfigSnapshot::new()
+//!     .with_scorer("scorer", &NormalizedLevenshtein)
+//!     .with_processor("processor", &LowerAlphaNumStringProcessor)
+//!     .with_param("cutoff", 0.87);
+//! assert!(changed.verify(&stored).is_err());
+//! ```
+
+use crate::algorithms::{Similarity, SimilarityMetric};
+use crate::processors::StringProcessor;
+use crate::utils::FuzztError;
+use std::fmt::Display;
+
+/// The key holding the version of the crate that took the snapshot.
+pub const VERSION_KEY: &str = "fuzzt.version";
+
+// strings exercising the edge cases of the metrics: empty strings, case,
+// transpositions, repetitions, non-ASCII and multi-word strings, and strings
+// longer than 64 and 128 characters, which the bit-parallel implementations
+// split into several blocks
+const PROBES: [(&str, &str); 14] = [
+    ("", ""),
+    ("", "abc"),
+    ("kitten", "sitting"),
+    ("Kitten", "kITTEN"),
+    ("abcd", "abdc"),
+    ("aaaa", "aab"),
+    ("martha", "marhta"),
+    ("dixon", "dicksonx"),
+    ("öঙ香", "öঙ"),
+    ("New York, NY", "new-york ny!"),
+    ("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear"),
+    ("the quick brown fox", "the quick brown dog jumps"),
+    (
+        "It was the best of times, it was the worst of times, it was the age of wisdom",
+        "It was the best of times, it was the worst of times; it was the age of foolishness",
+    ),
+    (
+        "Call me Ishmael. Some years ago, never mind how long precisely, having little or no \
+         money in my purse, and nothing particular to interest me on shore, I thought I would \
+         sail about a little",
+        "Call me Ishmael. Some years ago - never mind how long precisely - having little or no \
+         money in my purse and nothing in particular to interest me on shore, I thought I \
+         would sail about a little and see the watery part of the world",
+    ),
+];
+
+// 64-bit FNV-1a, which unlike the hasher of the standard library is
+// guaranteed to give the same result in every version
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Returns the fingerprint of the scores of `scorer` on a fixed set of probe
+/// strings, as 16 hexadecimal digits.
+///
+/// ```
+/// use fuzzt::algorithms::{Levenshtein, NormalizedLevenshtein};
+/// use fuzzt::snapshot::scorer_fingerprint;
+///
+/// assert_eq!(scorer_fingerprint(&Levenshtein), scorer_fingerprint(&Levenshtein));
+/// assert_ne!(scorer_fingerprint(&Levenshtein), scorer_fingerprint(&NormalizedLevenshtein));
+/// ```
+pub fn scorer_fingerprint(scorer: &dyn SimilarityMetric) -> String {
+    let mut hash = FNV_OFFSET;
+    for (a, b) in PROBES {
+        for (a, b) in [(a, b), (b, a)] {
+            hash = match scorer.compute_metric(a, b) {
+                Similarity::Usize(distance) => {
+                    fnv1a(fnv1a(hash, b"u"), &(distance as u64).to_le_bytes())
+                }
+                Similarity::Float(score) => {
+                    fnv1a(fnv1a(hash, b"f"), &score.to_bits().to_le_bytes())
+                }
+            };
+        }
+    }
+    format!("{hash:016x}")
+}
+
+/// Returns the fingerprint of the output of `processor` on a fixed set of
+/// probe strings, as 16 hexadecimal digits.
+pub fn processor_fingerprint(processor: &dyn StringProcessor) -> String {
+    let mut hash = FNV_OFFSET;
+    for (a, b) in PROBES {
+        for s in [a, b] {
+            // the lengths keep the boundaries between the outputs
+            let processed = processor.process(s);
+            hash = fnv1a(hash, &(processed.len() as u64).to_le_bytes());
+            hash = fnv1a(hash, processed.as_bytes());
+        }
+    }
+    format!("{hash:016x}")
+}
+
+/// An ordered list of the settings of a matcher, along with the version of
+/// the crate.
+///
+/// Snapshots are exported as one `key=value` line per setting. Keys can't
+/// contain `=` and neither keys nor values can contain line breaks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigSnapshot {
+    entries: Vec<(String, String)>,
+}
+
+impl ConfigSnapshot {
+    /// Creates a snapshot holding the version of the crate.
+    pub fn new() -> Self {
+        ConfigSnapshot {
+            entries: vec![(
+                VERSION_KEY.to_string(),
+                env!("CARGO_PKG_VERSION").to_string(),
+            )],
+        }
+    }
+
+    /// Records a setting, replacing any previous value of `key`.
+    ///
+    /// Panics if `key` contains `=` or a line break, or if `value` contains a
+    /// line break.
+    pub fn with_param(mut self, key: &str, value: impl Display) -> Self {
+        let value = value.to_string();
+        assert!(
+            !key.contains(['=', '\n', '\r']),
+            "keys can't contain '=' or line breaks"
+        );
+        assert!(
+            !value.contains(['\n', '\r']),
+            "values can't contain line breaks"
+        );
+        match self.entries.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((key.to_string(), value)),
+        }
+        self
+    }
+
+    /// Records the fingerprint of `scorer` under `key`, see
+    /// [`scorer_fingerprint`].
+    pub fn with_scorer(self, key: &str, scorer: &dyn SimilarityMetric) -> Self {
+        self.with_param(key, scorer_fingerprint(scorer))
+    }
+
+    /// Records the fingerprint of `processor` under `key`, see
+    /// [`processor_fingerprint`].
+    pub fn with_processor(self, key: &str, processor: &dyn StringProcessor) -> Self {
+        self.with_param(key, processor_fingerprint(processor))
+    }
+
+    /// Returns the value recorded for `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the snapshot as one `key=value` line per setting, in the order
+    /// they were recorded.
+    pub fn export(&self) -> String {
+        self.entries
+            .iter()
+            .map(|(key, value)| format!("{key}={value}\n"))
+            .collect()
+    }
+
+    /// Reads a snapshot exported with [`ConfigSnapshot::export`]. Empty lines
+    /// are skipped.
+    ///
+    /// Returns `FuzztError::MalformedSnapshot` if a line has no `=` or if a
+    /// key is repeated.
+    pub fn parse(exported: &str) -> Result<Self, FuzztError> {
+        let mut entries: Vec<(String, String)> = vec![];
+        for line in exported.lines().filter(|line| !line.is_empty()) {
+            let (key, value) = line.split_once('=').ok_or(FuzztError::MalformedSnapshot)?;
+            if entries.iter().any(|(k, _)| k == key) {
+                return Err(FuzztError::MalformedSnapshot);
+            }
+            entries.push((key.to_string(), value.to_string()));
+        }
+        Ok(ConfigSnapshot { entries })
+    }
+
+    /// Returns the keys whose values differ between both snapshots, or that
+    /// are only recorded in one of them, in the order of this snapshot and
+    /// then of `other`. The version of the crate isn't compared.
+    pub fn differences(&self, other: &ConfigSnapshot) -> Vec<String> {
+        let mut keys: Vec<String> = vec![];
+        for (key, _) in self.entries.iter().chain(&other.entries) {
+            if key != VERSION_KEY
+                && self.get(key) != other.get(key)
+                && !keys.iter().any(|k| k == key)
+            {
+                keys.push(key.clone());
+            }
+        }
+        keys
+    }
+
+    /// Checks that the exported snapshot `stored` describes the same
+    /// configuration as this one.
+    ///
+    /// Snapshots taken with different versions of the crate are compatible as
+    /// long as every setting and fingerprint is the same. Returns
+    /// `FuzztError::MalformedSnapshot` if `stored` can't be read, and
+    /// `FuzztError::IncompatibleSnapshot` if a setting differs, which
+    /// [`ConfigSnapshot::differences`] tells.
+    pub fn verify(&self, stored: &str) -> Result<(), FuzztError> {
+        let stored = ConfigSnapshot::parse(stored)?;
+        if self.differences(&stored).is_empty() {
+            Ok(())
+        } else {
+            Err(FuzztError::IncompatibleSnapshot)
+        }
+    }
+}
+
+impl Default for ConfigSnapshot {
+    /// Creates a snapshot holding the version of the crate.
+    fn default() -> Self {
+        ConfigSnapshot::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::SequenceMatcher;
+    use crate::processors::{LowerAlphaNumStringProcessor, NullStringProcessor};
+
+    #[test]
+    fn probes_cover_long_strings() {
+        let lengths: Vec<usize> = PROBES
+            .iter()
+            .flat_map(|&(a, b)| [a, b])
+            .map(|s| s.chars().count())
+            .collect();
+        assert!(lengths.iter().any(|&len| len > 64 && len <= 128));
+        assert!(lengths.iter().any(|&len| len > 128));
+    }
+
+    #[test]
+    fn snapshot_round_trips() {
+        let snapshot = ConfigSnapshot::new()
+            .with_scorer("scorer", &SequenceMatcher)
+            .with_param("cutoff", 0.7)
+            .with_param("n", 3)
+            .with_param("cutoff", 0.8);
+        let exported = snapshot.export();
+        assert_eq!(4, exported.lines().count());
+        assert!(exported.contains("cutoff=0.8\n"));
+        assert_eq!(Ok(snapshot.clone()), ConfigSnapshot::parse(&exported));
+        assert_eq!(Some(env!("CARGO_PKG_VERSION")), snapshot.get(VERSION_KEY));
+
+        // the version isn't compared
+        let older = exported.replace(env!("CARGO_PKG_VERSION"), "0.0.1");
+        assert_eq!(Ok(()), snapshot.verify(&older));
+    }
+
+    #[test]
+    fn snapshot_detects_changes() {
+        let snapshot = ConfigSnapshot::new()
+            .with_processor("processor", &NullStringProcessor)
+            .with_param("cutoff", 0.7);
+        let other = ConfigSnapshot::new()
+            .with_processor("processor", &LowerAlphaNumStringProcessor)
+            .with_param("n", 2);
+        assert_eq!(snapshot.differences(&other), ["processor", "cutoff", "n"]);
+        assert_eq!(
+            Err(FuzztError::IncompatibleSnapshot),
+            snapshot.verify(&other.export())
+        );
+        assert_eq!(
+            Err(FuzztError::MalformedSnapshot),
+            snapshot.verify("cutoff\n")
+        );
+        assert_eq!(
+            Err(FuzztError::MalformedSnapshot),
+            ConfigSnapshot::parse("a=1\na=2\n")
+        );
+    }
+}
@@ -1,18 +1,37 @@
+//! Building blocks shared by the metrics of the crate.
+
 use std::char;
+use std::char::ToLowercase;
+use std::collections::VecDeque;
 use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt::{self, Display, Formatter};
-use std::str::Chars;
+use std::iter::{FlatMap, Map};
+use std::str::{Bytes, Chars};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+/// Errors returned by the fallible functions of the crate.
 #[derive(Debug, PartialEq)]
 pub enum FuzztError {
     DifferentLengthArgs,
+    MalformedProfile,
+    InputTooLong,
+    MalformedDictionary,
+    Io(std::io::ErrorKind),
+    MalformedSnapshot,
+    IncompatibleSnapshot,
 }
 
 impl Display for FuzztError {
     fn fmt(&self, fmt: &mut Formatter) -> Result<(), fmt::Error> {
         let text = match self {
             FuzztError::DifferentLengthArgs => "Differing length arguments provided",
+            FuzztError::MalformedProfile => "Malformed n-gram profile bytes provided",
+            FuzztError::InputTooLong => "Input exceeds the maximum supported length",
+            FuzztError::MalformedDictionary => "Malformed frequency dictionary line provided",
+            FuzztError::Io(kind) => return write!(fmt, "Failed to read input: {kind}"),
+            FuzztError::MalformedSnapshot => "Malformed configuration snapshot provided",
+            FuzztError::IncompatibleSnapshot => "Configuration differs from the snapshot",
         };
 
         write!(fmt, "{text}")
@@ -21,9 +40,15 @@ impl Display for FuzztError {
 
 impl Error for FuzztError {}
 
-pub struct StringWrapper<'a>(pub &'a str);
+impl From<std::io::Error> for FuzztError {
+    fn from(error: std::io::Error) -> Self {
+        FuzztError::Io(error.kind())
+    }
+}
+
+pub(crate) struct StringWrapper<'a>(pub &'a str);
 
-impl<'a, 'b> IntoIterator for &'a StringWrapper<'b> {
+impl<'b> IntoIterator for &StringWrapper<'b> {
     type Item = char;
     type IntoIter = Chars<'b>;
 
@@ -32,6 +57,42 @@ impl<'a, 'b> IntoIterator for &'a StringWrapper<'b> {
     }
 }
 
+/// Like `StringWrapper`, but lowercases the characters while iterating, so
+/// case-insensitive comparisons don't need to allocate lowercased copies.
+pub(crate) struct LowercaseStringWrapper<'a>(pub &'a str);
+
+impl<'b> IntoIterator for &LowercaseStringWrapper<'b> {
+    type Item = char;
+    type IntoIter = FlatMap<Chars<'b>, ToLowercase, fn(char) -> ToLowercase>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0
+            .chars()
+            .flat_map(char::to_lowercase as fn(char) -> ToLowercase)
+    }
+}
+
+/// Iterates over the bytes of a string, ASCII-lowercased if `IGNORE_CASE` is
+/// set. The flag is known at compile time, so iterating doesn't branch on it.
+pub(crate) struct AsciiWrapper<'a, const IGNORE_CASE: bool>(pub &'a str);
+
+impl<'b, const IGNORE_CASE: bool> IntoIterator for &AsciiWrapper<'b, IGNORE_CASE> {
+    type Item = u8;
+    type IntoIter = Map<Bytes<'b>, fn(u8) -> u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.bytes().map(ascii_fold::<IGNORE_CASE>)
+    }
+}
+
+fn ascii_fold<const IGNORE_CASE: bool>(b: u8) -> u8 {
+    if IGNORE_CASE {
+        b.to_ascii_lowercase()
+    } else {
+        b
+    }
+}
+
 #[derive(Default, Clone)]
 struct GrowingHashmapMapElemChar<ValueType> {
     key: u32,
@@ -172,7 +233,7 @@ where
     }
 }
 
-pub struct HybridGrowingHashmapChar<ValueType> {
+pub(crate) struct HybridGrowingHashmapChar<ValueType> {
     map: GrowingHashmapChar<ValueType>,
     extended_ascii: [ValueType; 256],
 }
@@ -215,7 +276,7 @@ where
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
-pub struct RowId {
+pub(crate) struct RowId {
     pub val: isize,
 }
 
@@ -226,21 +287,378 @@ impl Default for RowId {
 }
 
 /// Returns an Iterator of char tuples.
-pub fn bigrams(s: &str) -> impl Iterator<Item = (char, char)> + '_ {
+#[cfg(feature = "sorensen_dice")]
+pub(crate) fn bigrams(s: &str) -> impl Iterator<Item = (char, char)> + '_ {
     s.chars().zip(s.chars().skip(1))
 }
 
 /* Returns the final index for a value in a single vector that represents a fixed
 2d grid */
-pub fn flat_index(i: usize, j: usize, width: usize) -> usize {
+pub(crate) fn flat_index(i: usize, j: usize, width: usize) -> usize {
     j * width + i
 }
 
+static SCRATCH_CAPACITY: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(any(
+    feature = "gotoh",
+    feature = "levenshtein",
+    feature = "optimal_string_alignment"
+))]
+thread_local! {
+    static SCRATCH: std::cell::RefCell<Vec<usize>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Lets the edit distances reuse a scratch buffer of up to `capacity`
+/// elements per thread instead of allocating one on every call.
+///
+/// Each thread keeps its own buffer, so concurrent callers don't contend for
+/// it, and no handle needs to be passed around. Comparisons that need a larger
+/// buffer allocate as usual, which bounds the memory held by each thread to
+/// `capacity` machine words. A capacity of 0, the default, disables the
+/// buffers. The setting applies to all threads.
+///
+/// The Levenshtein and optimal string alignment distances need a buffer about
+/// one and three times the length of the second string, respectively.
+///
+/// ```
+/// use fuzzt::algorithms::levenshtein;
+/// use fuzzt::utils::set_scratch_capacity;
+///
+/// set_scratch_capacity(4096);
+/// assert_eq!(3, levenshtein("kitten", "sitting"));
+/// ```
+pub fn set_scratch_capacity(capacity: usize) {
+    SCRATCH_CAPACITY.store(capacity, Ordering::Relaxed);
+}
+
+/// Returns the capacity set with [`set_scratch_capacity`].
+pub fn scratch_capacity() -> usize {
+    SCRATCH_CAPACITY.load(Ordering::Relaxed)
+}
+
+/// Calls `f` with a zeroed buffer of `len` elements, borrowed from the
+/// thread-local scratch buffer if it's allowed to hold that many.
+#[cfg(any(
+    feature = "gotoh",
+    feature = "levenshtein",
+    feature = "optimal_string_alignment"
+))]
+pub(crate) fn with_scratch<R>(len: usize, f: impl FnOnce(&mut [usize]) -> R) -> R {
+    let capacity = scratch_capacity();
+    if len > 0 && len <= capacity {
+        return SCRATCH.with(|scratch| {
+            // the buffer is already in use if a metric calls another one
+            if let Ok(mut buffer) = scratch.try_borrow_mut() {
+                buffer.clear();
+                buffer.shrink_to(capacity);
+                buffer.resize(len, 0);
+                return f(&mut buffer);
+            }
+            f(&mut vec![0; len])
+        });
+    }
+    f(&mut vec![0; len])
+}
+
+// the polynomial hash is computed modulo the Mersenne prime 2^61 - 1: modulo
+// a power of two, strings such as the Thue-Morse sequence and its complement
+// collide whatever the base
+const MODULUS: u64 = (1 << 61) - 1;
+const BASE: u64 = 0x1ce4_e5b9_a2c7_1d41;
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    let product = u128::from(a) * u128::from(b);
+    // 2^61 is 1 modulo 2^61 - 1, so the high bits are added to the low ones
+    let sum = (product as u64 & MODULUS) + (product >> 61) as u64;
+    if sum >= MODULUS {
+        sum - MODULUS
+    } else {
+        sum
+    }
+}
+
+/// Scrambles the bits of a polynomial hash, whose low bits are too regular to
+/// be sampled directly.
+fn mix(mut h: u64) -> u64 {
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    h ^ (h >> 33)
+}
+
+/// Polynomial (Rabin-Karp) hash of the last `k` characters pushed into it.
+///
+/// Each push updates the hash in constant time, so hashing every k-gram of a
+/// string is linear in its length. The polynomial hash is computed modulo the
+/// prime 2^61 - 1 with a fixed base, so hashes are the same in every run, and
+/// its bits are scrambled before being returned, so hashes are evenly
+/// distributed and can be sampled by their value. Equal windows always
+/// produce equal hashes. Different windows rarely collide, but since the base
+/// is fixed, inputs can be crafted to collide, so callers that can't tolerate
+/// false positives should compare the windows themselves, as
+/// [`rabin_karp_find`] does.
+///
+/// ```
+/// use fuzzt::utils::RollingHash;
+///
+/// let mut hash = RollingHash::new(3);
+/// let hashes: Vec<u64> = "abcabc".chars().filter_map(|c| hash.push(c)).collect();
+/// assert_eq!(4, hashes.len());
+/// assert_eq!(hashes[0], hashes[3]);
+/// assert_ne!(hashes[0], hashes[1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingHash {
+    k: usize,
+    window: VecDeque<u64>,
+    hash: u64,
+    highest_power: u64,
+}
+
+impl RollingHash {
+    /// Creates a rolling hash over windows of `k` characters.
+    ///
+    /// Panics if `k` is 0.
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0, "windows must contain at least one character");
+        RollingHash {
+            k,
+            window: VecDeque::with_capacity(k),
+            hash: 0,
+            highest_power: (1..k).fold(1, |power, _| mul_mod(power, BASE)),
+        }
+    }
+
+    /// Returns the number of characters in a window.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Pushes the next character and returns the hash of the last `k`
+    /// characters, once at least `k` characters have been pushed.
+    pub fn push(&mut self, c: char) -> Option<u64> {
+        let value = u64::from(c);
+        if self.window.len() == self.k {
+            let outgoing = self.window.pop_front().expect("window is full");
+            let removed = mul_mod(outgoing, self.highest_power);
+            self.hash = (self.hash + MODULUS - removed) % MODULUS;
+        }
+        self.hash = (mul_mod(self.hash, BASE) + value) % MODULUS;
+        self.window.push_back(value);
+
+        if self.window.len() == self.k {
+            Some(mix(self.hash))
+        } else {
+            None
+        }
+    }
+
+    /// Forgets all pushed characters.
+    pub fn clear(&mut self) {
+        self.window.clear();
+        self.hash = 0;
+    }
+
+    /// Returns the hash `push` produces for a window of exactly these
+    /// characters, or `None` if `s` doesn't contain `k` characters.
+    pub fn hash_of(&self, s: &str) -> Option<u64> {
+        let mut hash = RollingHash::new(self.k);
+        let mut last = None;
+        for (i, c) in s.chars().enumerate() {
+            if i == self.k {
+                return None;
+            }
+            last = hash.push(c);
+        }
+        last
+    }
+}
+
+/// Returns the byte offsets of all occurrences of `needle` in `haystack`,
+/// including overlapping ones, using the Rabin-Karp algorithm.
+///
+/// Candidates whose hash matches are compared with the needle, so hash
+/// collisions never produce false positives. An empty needle matches nowhere.
+///
+/// ```
+/// use fuzzt::utils::rabin_karp_find;
+///
+/// assert_eq!(vec![0, 2, 4], rabin_karp_find("abababa", "aba"));
+/// assert_eq!(vec![5], rabin_karp_find("öঙ香ö", "香"));
+/// assert!(rabin_karp_find("abc", "").is_empty());
+/// ```
+pub fn rabin_karp_find(haystack: &str, needle: &str) -> Vec<usize> {
+    let k = needle.chars().count();
+    if k == 0 {
+        return vec![];
+    }
+    let mut hash = RollingHash::new(k);
+    let target = hash.hash_of(needle).expect("needle has k characters");
+
+    let mut starts: VecDeque<usize> = VecDeque::with_capacity(k);
+    let mut matches = vec![];
+    for (offset, c) in haystack.char_indices() {
+        if starts.len() == k {
+            starts.pop_front();
+        }
+        starts.push_back(offset);
+        if hash.push(c) == Some(target) {
+            let start = starts[0];
+            if haystack[start..].starts_with(needle) {
+                matches.push(start);
+            }
+        }
+    }
+    matches
+}
+
+/// Applies the casing pattern of `original` to `replacement`, so corrected
+/// words keep the case of the words they replace.
+///
+/// Words in upper case, title case or lower case have their replacement
+/// converted to the same case. For words with mixed case, each character of
+/// the replacement takes the case of the character at the same position in
+/// `original`, and characters past its end take the case of its last letter.
+/// Replacements of words without cased letters are returned as is.
+///
+/// ```
+/// use fuzzt::utils::match_case;
+///
+/// assert_eq!("Receive", match_case("Recieve", "receive"));
+/// assert_eq!("RECEIVE", match_case("RECIEVE", "receive"));
+/// assert_eq!("iPhones", match_case("iPhnes", "iphones"));
+/// ```
+pub fn match_case(original: &str, replacement: &str) -> String {
+    let cased: Vec<char> = original
+        .chars()
+        .filter(|c| c.is_uppercase() || c.is_lowercase())
+        .collect();
+    let Some(&first) = cased.first() else {
+        return replacement.to_owned();
+    };
+
+    if cased.len() > 1 && cased.iter().all(|c| c.is_uppercase()) {
+        return replacement.to_uppercase();
+    }
+    if cased.iter().all(|c| c.is_lowercase()) {
+        return replacement.to_lowercase();
+    }
+    if first.is_uppercase() && cased[1..].iter().all(|c| c.is_lowercase()) {
+        let mut chars = replacement.chars();
+        return match chars.next() {
+            Some(c) => c
+                .to_uppercase()
+                .chain(chars.as_str().to_lowercase().chars())
+                .collect(),
+            None => String::new(),
+        };
+    }
+
+    let mut pattern = original.chars();
+    let mut upper = first.is_uppercase();
+    let mut matched = String::with_capacity(replacement.len());
+    for c in replacement.chars() {
+        if let Some(o) = pattern.next() {
+            if o.is_uppercase() || o.is_lowercase() {
+                upper = o.is_uppercase();
+            }
+        }
+        if upper {
+            matched.extend(c.to_uppercase());
+        } else {
+            matched.extend(c.to_lowercase());
+        }
+    }
+    matched
+}
+
+/// Returns the set of characters of `s` as a 64-bit mask, ignoring case and
+/// whitespace.
+///
+/// ASCII letters and digits have a bit of their own, and all other characters
+/// share the remaining 28 bits, so distinct characters may set the same bit.
+/// Masks are cheap to store and compare, which makes them useful to discard
+/// candidates before running an expensive metric, see [`mask_overlap`] and
+/// [`mask_edit_lower_bound`].
+///
+/// ```
+/// use fuzzt::utils::char_mask;
+///
+/// assert_eq!(char_mask("Listen"), char_mask("silent"));
+/// assert_eq!(3, char_mask("abca").count_ones());
+/// assert_eq!(0, char_mask(" \t"));
+/// ```
+pub fn char_mask(s: &str) -> u64 {
+    s.chars().filter(|c| !c.is_whitespace()).fold(0, |mask, c| {
+        // characters lowercasing to several ones keep their own bit, so
+        // every character sets exactly one bit
+        let mut lowercase = c.to_lowercase();
+        let c = match lowercase.len() {
+            1 => lowercase.next().unwrap_or(c),
+            _ => c,
+        };
+        let bit = match c {
+            'a'..='z' => c as u32 - 'a' as u32,
+            '0'..='9' => 26 + c as u32 - '0' as u32,
+            _ => 36 + c as u32 % 28,
+        };
+        mask | 1 << bit
+    })
+}
+
+// the bits of `char_mask` shared by the characters other than ASCII letters
+// and digits
+const OTHER_CHAR_BITS: u64 = !0 << 36;
+
+/// Returns an upper bound on the number of distinct characters both strings
+/// share, from their masks.
+///
+/// The bound is exact for ASCII letters and digits. Other characters share
+/// their bits, so a single shared bit may stand for any number of them: if
+/// the masks have one of these bits in common, the bound is `u32::MAX`.
+///
+/// ```
+/// use fuzzt::utils::{char_mask, mask_overlap};
+///
+/// assert_eq!(3, mask_overlap(char_mask("kitten"), char_mask("sitting")));
+/// assert_eq!(u32::MAX, mask_overlap(char_mask("!="), char_mask("!=")));
+/// ```
+pub fn mask_overlap(a: u64, b: u64) -> u32 {
+    let shared = a & b;
+    if shared & OTHER_CHAR_BITS == 0 {
+        shared.count_ones()
+    } else {
+        u32::MAX
+    }
+}
+
+/// Returns a lower bound on the edit distance between two strings from their
+/// masks.
+///
+/// Every character missing from the other string takes at least one edit, and
+/// a substitution fixes at most one missing character of each string, so the
+/// bound holds for the Levenshtein, optimal string alignment and
+/// Damerau-Levenshtein distances, with or without ignoring case.
+///
+/// ```
+/// use fuzzt::utils::{char_mask, mask_edit_lower_bound};
+///
+/// // "kitten" lacks "g" and "s", "sitting" lacks "e" and "k"
+/// assert_eq!(2, mask_edit_lower_bound(char_mask("kitten"), char_mask("sitting")));
+/// ```
+pub fn mask_edit_lower_bound(a: u64, b: u64) -> u32 {
+    (a & !b).count_ones().max((b & !a).count_ones())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
 
     #[test]
+    #[cfg(feature = "sorensen_dice")]
     fn bigrams_iterator() {
         let mut bi = bigrams("abcde");
 
@@ -250,4 +668,160 @@ mod tests {
         assert_eq!(Some(('d', 'e')), bi.next());
         assert_eq!(None, bi.next());
     }
+
+    #[test]
+    #[cfg(feature = "levenshtein")]
+    fn scratch_buffer_zeroed_and_nested() {
+        set_scratch_capacity(64);
+        with_scratch(8, |buffer| buffer.fill(7));
+        let total = with_scratch(8, |outer| {
+            assert_eq!(&[0; 8], outer);
+            outer.fill(1);
+            with_scratch(4, |inner| {
+                assert_eq!(&[0; 4], inner);
+                inner.iter().sum::<usize>()
+            }) + outer.iter().sum::<usize>()
+        });
+        assert_eq!(8, total);
+        assert_eq!(65, with_scratch(65, |buffer| buffer.len()));
+    }
+
+    #[test]
+    fn mask_overlap_is_upper_bound() {
+        for (a, b, shared) in [
+            ("!=", "!=", 2),
+            ("öঙ香", "öঙ香", 3),
+            ("a-b", "b-a", 3),
+            ("kitten", "sitting", 3),
+        ] {
+            assert!(
+                mask_overlap(char_mask(a), char_mask(b)) >= shared,
+                "{a} {b}"
+            );
+        }
+        assert_eq!(2, mask_overlap(char_mask("ab!"), char_mask("ab")));
+    }
+
+    #[test]
+    fn rolling_hash_matches_direct_hash() {
+        let mut rolling = RollingHash::new(4);
+        let text = "the quick brown fox";
+        let chars: Vec<char> = text.chars().collect();
+        let hashes: Vec<u64> = text.chars().filter_map(|c| rolling.push(c)).collect();
+        assert_eq!(chars.len() - 3, hashes.len());
+        for (i, hash) in hashes.into_iter().enumerate() {
+            let window: String = chars[i..i + 4].iter().collect();
+            assert_eq!(Some(hash), rolling.hash_of(&window));
+        }
+    }
+
+    #[test]
+    fn rolling_hash_no_collisions_on_trigrams() {
+        let alphabet: Vec<char> = ('a'..='z').chain('0'..='9').collect();
+        let hash = RollingHash::new(3);
+        let mut seen = HashSet::new();
+        for &a in &alphabet {
+            for &b in &alphabet {
+                for &c in &alphabet {
+                    let trigram: String = [a, b, c].iter().collect();
+                    assert!(seen.insert(hash.hash_of(&trigram).unwrap()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rolling_hash_thue_morse() {
+        // the Thue-Morse sequence and its complement collide modulo 2^64 for
+        // every odd base once they have 2^11 characters
+        let mut bits = vec![false];
+        while bits.len() < 2048 {
+            bits.extend(bits.clone().into_iter().map(|bit| !bit));
+        }
+        let sequence: String = bits
+            .iter()
+            .map(|&bit| if bit { 'b' } else { 'a' })
+            .collect();
+        let complement: String = bits
+            .iter()
+            .map(|&bit| if bit { 'a' } else { 'b' })
+            .collect();
+        let hash = RollingHash::new(2048);
+        assert_ne!(hash.hash_of(&sequence), hash.hash_of(&complement));
+
+        let mut rolling = RollingHash::new(2048);
+        let hashes: Vec<u64> = sequence
+            .chars()
+            .chain(complement.chars())
+            .filter_map(|c| rolling.push(c))
+            .collect();
+        assert_eq!(hash.hash_of(&sequence), hashes.first().copied());
+        assert_eq!(hash.hash_of(&complement), hashes.last().copied());
+    }
+
+    #[test]
+    fn rolling_hash_order_sensitive() {
+        let hash = RollingHash::new(2);
+        assert_ne!(hash.hash_of("ab"), hash.hash_of("ba"));
+        assert_ne!(hash.hash_of("öঙ"), hash.hash_of("ঙö"));
+        assert_eq!(None, hash.hash_of("a"));
+        assert_eq!(None, hash.hash_of("abc"));
+    }
+
+    #[test]
+    fn rolling_hash_clear() {
+        let mut hash = RollingHash::new(2);
+        hash.push('a');
+        hash.clear();
+        assert_eq!(None, hash.push('b'));
+        assert_eq!(hash.hash_of("bc"), hash.push('c'));
+    }
+
+    #[test]
+    fn match_case_patterns() {
+        assert_eq!("the", match_case("teh", "The"));
+        assert_eq!("Straße", match_case("Strase", "straße"));
+        assert_eq!("STRASSE", match_case("STRAS", "straße"));
+        assert_eq!("I", match_case("I", "i"));
+        assert_eq!("McDonalds", match_case("McDonlds", "mcdonalds"));
+        assert_eq!("NeW yORK", match_case("NeW yOR", "new york"));
+        assert_eq!("42nd", match_case("42", "42nd"));
+        assert_eq!("", match_case("Teh", ""));
+    }
+
+    #[test]
+    fn char_mask_buckets() {
+        assert_eq!(char_mask("Ab c"), char_mask("cba"));
+        assert_eq!(3, char_mask("a1香").count_ones());
+        assert_eq!(1, char_mask("İİ").count_ones());
+        assert_eq!(0, char_mask(""));
+        assert_eq!(0, mask_overlap(char_mask("abc"), char_mask("xyz")));
+    }
+
+    #[test]
+    fn mask_edit_lower_bound_holds() {
+        // pairs and their Levenshtein distance
+        let pairs = [
+            ("", "", 0),
+            ("", "abc", 3),
+            ("kitten", "sitting", 3),
+            ("ab", "ba", 2),
+            ("abcdef", "fedcba", 6),
+            ("öঙ香", "香öঙ!", 2),
+            ("İstanbul", "istanbul", 1),
+        ];
+        for (a, b, distance) in pairs {
+            assert!(mask_edit_lower_bound(char_mask(a), char_mask(b)) <= distance);
+        }
+        assert_eq!(3, mask_edit_lower_bound(0, char_mask("abc")));
+        assert_eq!(0, mask_edit_lower_bound(char_mask("ab"), char_mask("ba")));
+    }
+
+    #[test]
+    fn rabin_karp_find_matches() {
+        assert_eq!(vec![4, 17], rabin_karp_find("the cat sat on a cat", "cat"));
+        assert_eq!(vec![0, 1, 2], rabin_karp_find("aaaa", "aa"));
+        assert!(rabin_karp_find("ab", "abc").is_empty());
+        assert!(rabin_karp_find("", "a").is_empty());
+    }
 }
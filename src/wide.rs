@@ -0,0 +1,192 @@
+//! Metrics over UTF-16 and UTF-32 buffers, as handed out by Java and
+//! JavaScript bridges or Windows APIs, without re-encoding them to UTF-8.
+//!
+//! UTF-32 slices are compared code point by code point. UTF-16 slices are
+//! decoded first, so a surrogate pair counts as a single character, like in
+//! the `&str` metrics. Unpaired surrogates are kept as they are and only equal
+//! the same surrogate.
+//!
+//! ```
+//! use fuzzt::algorithms::levenshtein;
+//! use fuzzt::wide::{levenshtein_utf16, levenshtein_utf32};
+//!
+//! let a: Vec<u16> = "kitten 🐱".encode_utf16().collect();
+//! let b: Vec<u16> = "sitting 🐈".encode_utf16().collect();
+//! assert_eq!(levenshtein("kitten 🐱", "sitting 🐈"), levenshtein_utf16(&a, &b));
+//!
+//! let a: Vec<u32> = "kitten".chars().map(u32::from).collect();
+//! let b: Vec<u32> = "sitting".chars().map(u32::from).collect();
+//! assert_eq!(3, levenshtein_utf32(&a, &b));
+//! ```
+
+use crate::algorithms::{jaro_iter, jaro_winkler_iter, levenshtein_iter};
+
+// decodes UTF-16 code units into code points, keeping unpaired surrogates
+fn code_points(units: &[u16]) -> Vec<u32> {
+    char::decode_utf16(units.iter().copied())
+        .map(|decoded| match decoded {
+            Ok(c) => u32::from(c),
+            Err(error) => u32::from(error.unpaired_surrogate()),
+        })
+        .collect()
+}
+
+fn normalize(distance: usize, a_len: usize, b_len: usize) -> f64 {
+    if a_len == 0 && b_len == 0 {
+        return 1.0;
+    }
+    1.0 - distance as f64 / a_len.max(b_len) as f64
+}
+
+/// Like [`levenshtein`](crate::algorithms::levenshtein), but compares
+/// UTF-32 code points.
+///
+/// ```
+/// use fuzzt::wide::levenshtein_utf32;
+///
+/// assert_eq!(1, levenshtein_utf32(&[0x61, 0x62], &[0x61, 0x1F431]));
+/// ```
+pub fn levenshtein_utf32(a: &[u32], b: &[u32]) -> usize {
+    levenshtein_iter(a, b)
+}
+
+/// Like [`levenshtein`](crate::algorithms::levenshtein), but compares
+/// UTF-16 code units, decoding surrogate pairs into single characters.
+///
+/// ```
+/// use fuzzt::wide::levenshtein_utf16;
+///
+/// // "ab" and "a🐱", whose cat is a surrogate pair
+/// assert_eq!(1, levenshtein_utf16(&[0x61, 0x62], &[0x61, 0xD83D, 0xDC31]));
+/// ```
+pub fn levenshtein_utf16(a: &[u16], b: &[u16]) -> usize {
+    levenshtein_utf32(&code_points(a), &code_points(b))
+}
+
+/// Like [`normalized_levenshtein`](crate::algorithms::normalized_levenshtein),
+/// but compares UTF-32 code points.
+///
+/// ```
+/// use fuzzt::wide::normalized_levenshtein_utf32;
+///
+/// assert_eq!(0.5, normalized_levenshtein_utf32(&[0x61, 0x62], &[0x61, 0x1F431]));
+/// ```
+pub fn normalized_levenshtein_utf32(a: &[u32], b: &[u32]) -> f64 {
+    normalize(levenshtein_utf32(a, b), a.len(), b.len())
+}
+
+/// Like [`normalized_levenshtein`](crate::algorithms::normalized_levenshtein),
+/// but compares UTF-16 code units, decoding surrogate pairs into single
+/// characters.
+///
+/// ```
+/// use fuzzt::wide::normalized_levenshtein_utf16;
+///
+/// assert_eq!(0.5, normalized_levenshtein_utf16(&[0x61, 0x62], &[0x61, 0xD83D, 0xDC31]));
+/// ```
+pub fn normalized_levenshtein_utf16(a: &[u16], b: &[u16]) -> f64 {
+    normalized_levenshtein_utf32(&code_points(a), &code_points(b))
+}
+
+/// Like [`jaro`](crate::algorithms::jaro), but compares UTF-32 code points.
+///
+/// ```
+/// use fuzzt::algorithms::jaro;
+/// use fuzzt::wide::jaro_utf32;
+///
+/// let a: Vec<u32> = "martha".chars().map(u32::from).collect();
+/// let b: Vec<u32> = "marhta".chars().map(u32::from).collect();
+/// assert_eq!(jaro("martha", "marhta"), jaro_utf32(&a, &b));
+/// ```
+pub fn jaro_utf32(a: &[u32], b: &[u32]) -> f64 {
+    jaro_iter(a, b)
+}
+
+/// Like [`jaro`](crate::algorithms::jaro), but compares UTF-16 code units,
+/// decoding surrogate pairs into single characters.
+///
+/// ```
+/// use fuzzt::algorithms::jaro;
+/// use fuzzt::wide::jaro_utf16;
+///
+/// let a: Vec<u16> = "martha".encode_utf16().collect();
+/// let b: Vec<u16> = "marhta".encode_utf16().collect();
+/// assert_eq!(jaro("martha", "marhta"), jaro_utf16(&a, &b));
+/// ```
+pub fn jaro_utf16(a: &[u16], b: &[u16]) -> f64 {
+    jaro_utf32(&code_points(a), &code_points(b))
+}
+
+/// Like [`jaro_winkler`](crate::algorithms::jaro_winkler), but compares
+/// UTF-32 code points.
+///
+/// ```
+/// use fuzzt::algorithms::jaro_winkler;
+/// use fuzzt::wide::jaro_winkler_utf32;
+///
+/// let a: Vec<u32> = "martha".chars().map(u32::from).collect();
+/// let b: Vec<u32> = "marhta".chars().map(u32::from).collect();
+/// assert_eq!(jaro_winkler("martha", "marhta"), jaro_winkler_utf32(&a, &b));
+/// ```
+pub fn jaro_winkler_utf32(a: &[u32], b: &[u32]) -> f64 {
+    jaro_winkler_iter(a, b)
+}
+
+/// Like [`jaro_winkler`](crate::algorithms::jaro_winkler), but compares
+/// UTF-16 code units, decoding surrogate pairs into single characters.
+///
+/// ```
+/// use fuzzt::algorithms::jaro_winkler;
+/// use fuzzt::wide::jaro_winkler_utf16;
+///
+/// let a: Vec<u16> = "martha".encode_utf16().collect();
+/// let b: Vec<u16> = "marhta".encode_utf16().collect();
+/// assert_eq!(jaro_winkler("martha", "marhta"), jaro_winkler_utf16(&a, &b));
+/// ```
+pub fn jaro_winkler_utf16(a: &[u16], b: &[u16]) -> f64 {
+    jaro_winkler_utf32(&code_points(a), &code_points(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{jaro_winkler, levenshtein, normalized_levenshtein};
+
+    fn utf16(s: &str) -> Vec<u16> {
+        s.encode_utf16().collect()
+    }
+
+    fn utf32(s: &str) -> Vec<u32> {
+        s.chars().map(u32::from).collect()
+    }
+
+    #[test]
+    fn wide_matches_str_metrics() {
+        let pairs = [
+            ("", ""),
+            ("", "öঙ香"),
+            ("kitten", "sitting"),
+            ("🐱 cat", "🐈 cat"),
+            ("cheeseburger", "cheese fries"),
+        ];
+        for (a, b) in pairs {
+            assert_eq!(levenshtein(a, b), levenshtein_utf16(&utf16(a), &utf16(b)));
+            assert_eq!(levenshtein(a, b), levenshtein_utf32(&utf32(a), &utf32(b)));
+            assert_eq!(
+                normalized_levenshtein(a, b),
+                normalized_levenshtein_utf16(&utf16(a), &utf16(b))
+            );
+            assert_eq!(jaro_winkler(a, b), jaro_winkler_utf16(&utf16(a), &utf16(b)));
+        }
+    }
+
+    #[test]
+    fn wide_unpaired_surrogates() {
+        assert_eq!(
+            vec![0x61, 0xD83D, 0x1F431],
+            code_points(&[0x61, 0xD83D, 0xD83D, 0xDC31])
+        );
+        assert_eq!(0, levenshtein_utf16(&[0xDC31, 0x61], &[0xDC31, 0x61]));
+        assert_eq!(1, levenshtein_utf16(&[0xD83D], &[0xFFFD]));
+    }
+}